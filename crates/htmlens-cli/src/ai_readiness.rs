@@ -0,0 +1,300 @@
+//! `htmlens ai-readiness` subcommand
+//!
+//! Runs the site-wide AI readiness check from `htmlens_core` and renders the
+//! resulting report in the same sectioned markdown style as the default
+//! extraction output.
+
+use std::fmt::Write as FmtWrite;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use url::Url;
+
+use htmlens_core::ai_readiness::{
+    self, AiReadinessReport, fetch::HttpFetcher, robots_txt::AccessLevel, well_known::FileCheck,
+};
+
+use crate::cli::HttpArgs;
+use crate::config::Config;
+use crate::crawler_access::load_crawler_registry;
+use crate::{push_key_value, push_section_header};
+
+#[derive(Args)]
+pub struct AiReadinessOptions {
+    /// Site to check
+    pub url: String,
+
+    /// TOML or JSON file extending the AI crawler registry
+    #[arg(long, value_name = "FILE")]
+    pub crawler_registry: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub http: HttpArgs,
+}
+
+pub async fn run(options: AiReadinessOptions, config: &Config) -> Result<()> {
+    let parsed_url = Url::parse(&options.url).context("invalid URL")?;
+    let crawler_registry = options
+        .crawler_registry
+        .or_else(|| config.crawler_registry.clone());
+    let registry = load_crawler_registry(crawler_registry.as_deref())?;
+    let fetcher = HttpFetcher::new(&options.http.with_config(config).fetch_options())?;
+    let report =
+        ai_readiness::check_site_with(&fetcher, parsed_url.as_str(), &registry, &config.scoring)
+            .await?;
+
+    let mut output = String::new();
+    render_report(&mut output, &report);
+    print!("{}", output);
+
+    Ok(())
+}
+
+fn render_report(buf: &mut String, report: &AiReadinessReport) {
+    push_section_header(buf, "🤖", "AI Readiness Report");
+    push_key_value(buf, "URL", &report.url);
+    push_key_value(buf, "Score", &format!("{}/100", report.score));
+    let _ = writeln!(buf);
+
+    push_section_header(buf, "📊", "Category Breakdown");
+    for category in &report.breakdown.categories {
+        push_key_value(
+            buf,
+            category.category.label(),
+            &format!(
+                "{:>3}% ({:.1}/{:.0} pts, {} issue{})",
+                category.score,
+                category.points,
+                category.max_points,
+                category.issue_count,
+                if category.issue_count == 1 { "" } else { "s" }
+            ),
+        );
+    }
+    let _ = writeln!(buf);
+
+    push_section_header(buf, "✅", "Strengths");
+    if report.strengths.is_empty() {
+        let _ = writeln!(buf, "No strengths detected.");
+    }
+    for strength in &report.strengths {
+        let _ = writeln!(buf, "  • {strength}");
+    }
+    let _ = writeln!(buf);
+
+    push_section_header(buf, "⚠️", "Issues");
+    if report.issues.is_empty() {
+        let _ = writeln!(buf, "No issues found.");
+    }
+    for issue in &report.issues {
+        let _ = writeln!(
+            buf,
+            "  • [{:?}] {}: {}",
+            issue.severity, issue.category, issue.message
+        );
+        if let Some(reference) = issue.reference.as_ref() {
+            let _ = writeln!(buf, "    ↳ {reference}");
+        }
+    }
+    let _ = writeln!(buf);
+
+    if !report.recommendations.is_empty() {
+        push_section_header(buf, "💡", "Recommendations");
+        for recommendation in &report.recommendations {
+            let _ = writeln!(buf, "  • {recommendation}");
+        }
+        let _ = writeln!(buf);
+    }
+
+    push_section_header(buf, "📁", ".well-known Files");
+    for check in [
+        &report.well_known.ai_plugin,
+        &report.well_known.mcp,
+        &report.well_known.openid_config,
+        &report.well_known.security_txt,
+        &report.well_known.apple_app_site_association,
+        &report.well_known.assetlinks,
+    ] {
+        push_key_value(buf, file_name(check), &file_check_status(check));
+    }
+    let _ = writeln!(buf);
+
+    push_section_header(buf, "🕷️", "robots.txt");
+    match report.robots_txt.as_ref() {
+        Some(robots) => {
+            push_key_value(buf, "Sitemaps", &robots.sitemaps.len().to_string());
+            for status in &robots.ai_crawler_status {
+                push_key_value(
+                    buf,
+                    &status.name,
+                    &format!(
+                        "{} ({}, {})",
+                        access_label(&status.access),
+                        status.vendor,
+                        status.purpose
+                    ),
+                );
+            }
+            if let Some(summary) = robots.purpose_summary() {
+                push_key_value(buf, "Policy", &summary);
+            }
+        }
+        None => {
+            let _ = writeln!(buf, "robots.txt not found.");
+        }
+    }
+    let _ = writeln!(buf);
+
+    push_section_header(buf, "🗺️", "Sitemap");
+    match report.sitemap.as_ref() {
+        Some(sitemap) => {
+            push_key_value(buf, "Type", &format!("{:?}", sitemap.sitemap_type));
+            push_key_value(
+                buf,
+                "URLs",
+                &sitemap
+                    .statistics
+                    .total_urls
+                    .max(sitemap.url_count)
+                    .to_string(),
+            );
+            push_key_value(
+                buf,
+                "With lastmod",
+                &sitemap.statistics.urls_with_lastmod.to_string(),
+            );
+            let mut content_types: Vec<_> = sitemap.statistics.content_types.iter().collect();
+            content_types.sort();
+            let rendered = content_types
+                .into_iter()
+                .map(|(content_type, count)| format!("{content_type}: {count}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            push_key_value(buf, "Content Types", &rendered);
+        }
+        None => {
+            let _ = writeln!(buf, "No sitemap found.");
+        }
+    }
+    let _ = writeln!(buf);
+
+    push_section_header(buf, "📝", "llms.txt");
+    match report.llms_txt.as_ref() {
+        Some(llms) => {
+            if let Some(title) = llms.title.as_ref() {
+                push_key_value(buf, "Title", title);
+            }
+            if let Some(summary) = llms.summary.as_ref() {
+                push_key_value(buf, "Summary", summary);
+            }
+            for section in &llms.sections {
+                push_key_value(
+                    buf,
+                    &section.title,
+                    &format!(
+                        "{} link{}{}",
+                        section.links.len(),
+                        if section.links.len() == 1 { "" } else { "s" },
+                        if section.optional { " (optional)" } else { "" }
+                    ),
+                );
+            }
+            push_key_value(
+                buf,
+                "llms-full.txt",
+                &match llms.full.as_ref() {
+                    Some(full) => format!("✅ found ({} bytes)", full.size_bytes),
+                    None => "❌ not found".to_string(),
+                },
+            );
+        }
+        None => {
+            let _ = writeln!(buf, "llms.txt not found.");
+        }
+    }
+    let _ = writeln!(buf);
+
+    if let Some(validation) = report.mcp.as_ref() {
+        push_section_header(buf, "🧠", "MCP Manifest");
+        if let Some(name) = validation.name.as_ref() {
+            push_key_value(buf, "Name", name);
+        }
+        if let Some(protocol) = validation.protocol_version.as_ref() {
+            push_key_value(buf, "Protocol", protocol);
+        }
+        if let Some(endpoint) = validation.endpoint.as_ref() {
+            push_key_value(buf, "Endpoint", endpoint);
+        }
+        push_key_value(buf, "Tools", &validation.tool_count.to_string());
+        let _ = writeln!(buf);
+    }
+
+    if let Some(validation) = report.openapi.as_ref() {
+        push_section_header(buf, "📘", "OpenAPI");
+        if let Some(title) = validation.title.as_ref() {
+            push_key_value(buf, "Title", title);
+        }
+        if let Some(version) = validation.version.as_ref() {
+            push_key_value(buf, "Version", version);
+        }
+        push_key_value(
+            buf,
+            "Operations",
+            &validation.stats.total_operations.to_string(),
+        );
+        let _ = writeln!(buf);
+    }
+
+    if let Some(analysis) = report.semantic_html.as_ref() {
+        push_section_header(buf, "🏗️", "Semantic HTML");
+        push_key_value(buf, "Main", yes_no(analysis.landmarks.has_main));
+        push_key_value(buf, "Navigation", yes_no(analysis.landmarks.has_navigation));
+        push_key_value(buf, "Single h1", yes_no(analysis.headings.has_single_h1));
+        push_key_value(
+            buf,
+            "Images with alt",
+            &format!(
+                "{}/{}",
+                analysis.images.images_with_alt, analysis.images.total_images
+            ),
+        );
+        push_key_value(
+            buf,
+            "Labeled inputs",
+            &format!(
+                "{}/{}",
+                analysis.forms.labeled_inputs, analysis.forms.total_inputs
+            ),
+        );
+        let _ = writeln!(buf);
+    }
+}
+
+fn file_name(check: &FileCheck) -> &str {
+    check.path.rsplit('/').next().unwrap_or(&check.path)
+}
+
+fn file_check_status(check: &FileCheck) -> String {
+    match (check.found, check.valid) {
+        (true, true) => "✅ found".to_string(),
+        (true, false) => "⚠️ found, invalid content".to_string(),
+        _ => match check.status_code {
+            Some(code) => format!("❌ not found (HTTP {code})"),
+            None => "❌ not reachable".to_string(),
+        },
+    }
+}
+
+fn access_label(access: &AccessLevel) -> &'static str {
+    match access {
+        AccessLevel::Allowed => "✅ allowed",
+        AccessLevel::Partial => "◐ partial",
+        AccessLevel::Blocked => "❌ blocked",
+        AccessLevel::Default => "• default",
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
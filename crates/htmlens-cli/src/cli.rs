@@ -0,0 +1,401 @@
+//! Command-line arguments
+//!
+//! `htmlens <URL>` predates the subcommands, so arguments that don't start
+//! with a command are run as `extract`. [`normalize_args`] also rewrites the
+//! legacy spellings that clap can't express (`-dd`, `-s PATH`).
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{ArgAction, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use url::Url;
+
+use htmlens_core::{
+    export::{MermaidDirection, MermaidGrouping},
+    parser::FetchOptions,
+};
+
+use crate::ExportFormat;
+use crate::ai_readiness::AiReadinessOptions;
+use crate::config::Config;
+use crate::crawl::CrawlOptions;
+use crate::crawler_access::CrawlerAccessOptions;
+use crate::diff::DiffOptions;
+use crate::generate::GenerateLlmsTxtOptions;
+use crate::validate::ValidateOptions;
+
+#[derive(Parser)]
+#[command(
+    name = "htmlens",
+    version,
+    about = "A semantic lens for the web",
+    help_template = "{name} — {about}\n\n{usage-heading} {usage}\n\n{all-args}{after-help}",
+    after_help = "Without a command, the arguments are passed to `extract`:\n  htmlens [OPTIONS] <INPUT>\n\nDeveloped by Pon Datalab",
+    arg_required_else_help = true,
+    disable_version_flag = true
+)]
+pub struct Cli {
+    /// Print version
+    #[arg(short = 'v', long, action = ArgAction::Version)]
+    version: Option<bool>,
+
+    /// Read defaults from FILE instead of htmlens.toml
+    #[arg(long, global = true, env = "HTMLENS_CONFIG", value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Extract page content, structured data and the knowledge graph (default)
+    Extract(ExtractArgs),
+
+    /// Extract every page listed in a site's sitemap
+    Crawl(CrawlOptions),
+
+    /// Compare the knowledge graphs of two pages
+    #[command(
+        after_help = "Exit status: 0 if the graphs are the same, 1 if they differ, 2 on errors"
+    )]
+    Diff(DiffOptions),
+
+    /// Check a page's structured data, exiting non-zero on errors
+    #[command(
+        after_help = "Exit status: 0 if no problems were found, 1 if there are any, 2 on errors"
+    )]
+    Validate(ValidateOptions),
+
+    /// Check how well a site supports AI crawlers and agents
+    AiReadiness(AiReadinessOptions),
+
+    /// Show which AI crawlers robots.txt lets fetch which URLs
+    CrawlerAccess(CrawlerAccessOptions),
+
+    /// Generate files for a site
+    #[command(subcommand)]
+    Generate(GenerateCommand),
+
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GenerateCommand {
+    /// Draft an llms.txt from the sitemap and page metadata
+    LlmsTxt(GenerateLlmsTxtOptions),
+}
+
+#[derive(Args)]
+pub struct ExtractArgs {
+    /// Web page URL, HTML or JSON-LD file, directory of them, '-' for stdin,
+    /// or JSON-LD (must start with '{' or '[')
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Resolve relative IRIs against URL (default: the page URL, or the
+    /// file's file:// URL)
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<Url>,
+
+    /// Output condensed graph summary only (no markdown)
+    #[arg(short = 'g', long, conflicts_with = "graph_summary")]
+    pub graph_only: bool,
+
+    /// Output product summaries only (no markdown)
+    #[arg(short = 'G', long)]
+    pub graph_summary: bool,
+
+    /// Include DataDownload references in output (also -dd)
+    #[arg(long)]
+    pub data_downloads: bool,
+
+    /// Include Mermaid diagram visualization of the knowledge graph
+    #[arg(short = 'm', long)]
+    pub mermaid: bool,
+
+    /// Mermaid diagram direction [default: TD]
+    #[arg(long, value_name = "TD|LR|BT|RL")]
+    pub mermaid_direction: Option<MermaidDirection>,
+
+    /// Maximum nodes in the Mermaid diagram [default: 100]
+    #[arg(long, value_name = "N")]
+    pub mermaid_max_nodes: Option<usize>,
+
+    /// Siblings of one type shown before collapsing the rest into a "+N more"
+    /// node [default: 5]
+    #[arg(long, value_name = "N")]
+    pub mermaid_siblings: Option<usize>,
+
+    /// Mermaid subgraphs [default: product-group]
+    #[arg(long, value_name = "product-group|type|none")]
+    pub mermaid_group: Option<MermaidGrouping>,
+
+    /// Save output to a file in PATH (default: current directory)
+    #[arg(
+        short = 's',
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "."
+    )]
+    pub save: Option<PathBuf>,
+
+    /// Output format: markdown (default), json or ndjson (the report as
+    /// JSON), or the knowledge graph only as ntriples, nquads, turtle, jsonld
+    /// (compacted), dot, graphml or cytoscape
+    #[arg(long, value_name = "FORMAT", value_parser = ExportFormat::parse)]
+    pub format: Option<ExportFormat>,
+
+    #[command(flatten)]
+    pub fetch: FetchArgs,
+}
+
+/// How pages are fetched and their structured data is read
+#[derive(Args, Clone)]
+pub struct FetchArgs {
+    /// Resolve JSON-LD contexts without the network (bundled schema.org
+    /// context and --context-cache only)
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Cache remote JSON-LD contexts in DIR
+    #[arg(long, value_name = "DIR")]
+    pub context_cache: Option<PathBuf>,
+
+    /// Repair almost-JSON JSON-LD blocks (trailing commas, HTML entities,
+    /// ...) instead of skipping them
+    #[arg(long)]
+    pub lenient: bool,
+
+    #[command(flatten)]
+    pub http: HttpArgs,
+}
+
+impl FetchArgs {
+    /// Fill options not given on the command line from the config file
+    pub fn with_config(mut self, config: &Config) -> Self {
+        self.offline |= config.offline;
+        self.lenient |= config.lenient;
+        self.context_cache = self.context_cache.or_else(|| config.context_cache.clone());
+        self.http = self.http.with_config(config);
+        self
+    }
+
+    pub fn fetch_options(&self) -> FetchOptions {
+        self.http.fetch_options()
+    }
+}
+
+/// HTTP settings for every request a command makes
+#[derive(Args, Clone)]
+pub struct HttpArgs {
+    /// User-Agent header for HTTP requests
+    #[arg(long, value_name = "UA")]
+    pub user_agent: Option<String>,
+
+    /// HTTP request timeout in seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+}
+
+impl HttpArgs {
+    /// Fill options not given on the command line from the config file
+    pub fn with_config(mut self, config: &Config) -> Self {
+        self.user_agent = self.user_agent.or_else(|| config.user_agent.clone());
+        self.timeout = self.timeout.or(config.timeout);
+        self
+    }
+
+    pub fn fetch_options(&self) -> FetchOptions {
+        FetchOptions {
+            user_agent: self.user_agent.clone(),
+            timeout: self.timeout.map(Duration::from_secs),
+        }
+    }
+}
+
+/// `--format` of commands that print either a markdown report or JSON
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    #[value(alias = "md")]
+    Markdown,
+    Json,
+}
+
+/// Turn legacy invocations into `extract` ones
+///
+/// `args` excludes the program name.
+pub fn normalize_args(mut args: Vec<String>) -> Vec<String> {
+    let cli = Cli::command();
+
+    // Global options may come before the command
+    let mut start = 0;
+    while start < args.len() {
+        match args[start].as_str() {
+            "--config" => start += 2,
+            arg if arg.starts_with("--config=") => start += 1,
+            _ => break,
+        }
+    }
+    let Some(first) = args.get(start) else {
+        return args;
+    };
+    let is_command = cli
+        .get_subcommands()
+        .any(|command| command.get_name() == first)
+        || matches!(
+            first.as_str(),
+            "help" | "-h" | "--help" | "-v" | "--version"
+        );
+    if is_command {
+        return args;
+    }
+    args.insert(start, "extract".to_string());
+
+    let extract = cli
+        .find_subcommand("extract")
+        .expect("extract subcommand is defined");
+    let takes_value = |flag: &str| {
+        extract.get_arguments().any(|arg| {
+            arg.get_action().takes_values()
+                && !arg.is_require_equals_set()
+                && (arg
+                    .get_long()
+                    .is_some_and(|long| flag == format!("--{long}"))
+                    || arg
+                        .get_short()
+                        .is_some_and(|short| flag == format!("-{short}")))
+        }) || flag == "--config"
+    };
+
+    let mut normalized = Vec::with_capacity(args.len());
+    let mut seen_input = false;
+    let mut rest = args.into_iter().peekable();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-dd" => normalized.push("--data-downloads".to_string()),
+            // `-s PATH` only means a path once the input has been given,
+            // otherwise `htmlens -s <URL>` would save to the URL
+            "-s" | "--save" if seen_input => match rest.next_if(|next| !next.starts_with('-')) {
+                Some(path) => normalized.push(format!("--save={path}")),
+                None => normalized.push(arg),
+            },
+            flag if takes_value(flag) => {
+                normalized.push(arg);
+                normalized.extend(rest.next());
+            }
+            positional if !positional.starts_with('-') || positional == "-" => {
+                // The first positional is the `extract` command itself
+                if normalized.len() > start {
+                    seen_input = true;
+                }
+                normalized.push(arg);
+            }
+            _ => normalized.push(arg),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn parse(raw: &[&str]) -> Result<Cli, clap::Error> {
+        let mut argv = args(&["htmlens"]);
+        argv.extend(normalize_args(args(raw)));
+        Cli::try_parse_from(argv)
+    }
+
+    #[test]
+    fn test_legacy_invocation_runs_extract() {
+        assert_eq!(
+            normalize_args(args(&["-g", "https://example.com"])),
+            args(&["extract", "-g", "https://example.com"])
+        );
+        assert_eq!(
+            normalize_args(args(&["--config", "x.toml", "https://example.com"])),
+            args(&["--config", "x.toml", "extract", "https://example.com"])
+        );
+        assert_eq!(
+            normalize_args(args(&["ai-readiness", "https://example.com"])),
+            args(&["ai-readiness", "https://example.com"])
+        );
+        assert_eq!(normalize_args(args(&["-v"])), args(&["-v"]));
+    }
+
+    #[test]
+    fn test_legacy_flags() {
+        let Command::Extract(extract) = parse(&["-dd", "https://example.com", "-s", "out"])
+            .unwrap()
+            .command
+        else {
+            panic!("expected extract");
+        };
+        assert!(extract.data_downloads);
+        assert_eq!(extract.save, Some(PathBuf::from("out")));
+
+        // Before the input, -s has no path
+        let Command::Extract(extract) = parse(&["-s", "https://example.com"]).unwrap().command
+        else {
+            panic!("expected extract");
+        };
+        assert_eq!(extract.input, "https://example.com");
+        assert_eq!(extract.save, Some(PathBuf::from(".")));
+
+        // Flag values are not mistaken for the input
+        let Command::Extract(extract) = parse(&["--format", "json", "-s", "https://example.com"])
+            .unwrap()
+            .command
+        else {
+            panic!("expected extract");
+        };
+        assert_eq!(extract.input, "https://example.com");
+        assert_eq!(extract.save, Some(PathBuf::from(".")));
+    }
+
+    #[test]
+    fn test_http_flags_reach_network_commands() {
+        let Command::AiReadiness(options) = parse(&[
+            "ai-readiness",
+            "--user-agent",
+            "bot/1.0",
+            "https://example.com",
+        ])
+        .unwrap()
+        .command
+        else {
+            panic!("expected ai-readiness");
+        };
+        let config = Config {
+            timeout: Some(5),
+            user_agent: Some("config/1.0".into()),
+            ..Config::default()
+        };
+        let fetch = options.http.with_config(&config).fetch_options();
+        assert_eq!(fetch.user_agent.as_deref(), Some("bot/1.0"));
+        assert_eq!(fetch.timeout, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_conflicting_graph_options() {
+        assert!(parse(&["-g", "-G", "https://example.com"]).is_err());
+    }
+
+    #[test]
+    fn test_definition() {
+        Cli::command().debug_assert();
+    }
+}
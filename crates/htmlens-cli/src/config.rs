@@ -0,0 +1,143 @@
+//! `htmlens.toml` configuration file
+//!
+//! Defaults for options that are otherwise passed on every invocation. The
+//! file is read from `--config` (or `HTMLENS_CONFIG`), else `./htmlens.toml`,
+//! else `~/.config/htmlens/htmlens.toml`. Command-line flags take precedence.
+//!
+//! ```toml
+//! user_agent = "MyBot/1.0 (+https://example.com/bot)"
+//! timeout = 30
+//! format = "json"
+//! offline = false
+//! lenient = true
+//! context_cache = ".htmlens/contexts"
+//! crawlers = ["GPTBot", "ClaudeBot"]
+//! crawler_registry = "crawlers.toml"
+//!
+//! [scoring]
+//! crawler_access = 40
+//! semantic_html = 5
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use htmlens_core::ai_readiness::scoring::ScoringWeights;
+
+/// Name of the config file looked up in the working and config directories
+pub const CONFIG_FILE_NAME: &str = "htmlens.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// `User-Agent` for HTTP requests
+    pub user_agent: Option<String>,
+
+    /// HTTP request timeout in seconds
+    pub timeout: Option<u64>,
+
+    /// Default `--format` of `extract`
+    pub format: Option<String>,
+
+    /// Default `--offline`
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Default `--lenient`
+    #[serde(default)]
+    pub lenient: bool,
+
+    /// Default `--context-cache`
+    pub context_cache: Option<PathBuf>,
+
+    /// Default `--crawler` list of `crawler-access`
+    #[serde(default)]
+    pub crawlers: Vec<String>,
+
+    /// Default `--crawler-registry` of `ai-readiness` and `crawler-access`
+    pub crawler_registry: Option<PathBuf>,
+
+    /// Category weights of the `ai-readiness` score
+    #[serde(default)]
+    pub scoring: ScoringWeights,
+}
+
+impl Config {
+    /// Load the config from `path`, or from the first default location that
+    /// exists; no file means an empty config
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match default_locations().into_iter().find(|path| path.is_file()) {
+                Some(path) => path,
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        let mut config: Self = toml::from_str(&content)
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+
+        // Paths in the file are relative to the file, not the working directory
+        let base = path.parent().unwrap_or(Path::new(""));
+        config.context_cache = config.context_cache.map(|dir| base.join(dir));
+        config.crawler_registry = config.crawler_registry.map(|file| base.join(file));
+        Ok(config)
+    }
+}
+
+fn default_locations() -> Vec<PathBuf> {
+    let mut locations = vec![PathBuf::from(CONFIG_FILE_NAME)];
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    if let Some(dir) = config_home {
+        locations.push(dir.join("htmlens").join(CONFIG_FILE_NAME));
+    }
+    locations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_relative_to_config_file() {
+        let dir = env::temp_dir().join(format!("htmlens-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE_NAME);
+        fs::write(
+            &path,
+            "timeout = 5\ncrawlers = [\"GPTBot\"]\ncontext_cache = \"contexts\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(Some(&path)).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config.timeout, Some(5));
+        assert_eq!(config.crawlers, vec!["GPTBot"]);
+        assert_eq!(config.context_cache, Some(dir.join("contexts")));
+        assert!(!config.offline);
+    }
+
+    #[test]
+    fn test_scoring_weights() {
+        let config: Config = toml::from_str("[scoring]\ncrawler_access = 40\n").unwrap();
+        assert_eq!(config.scoring.crawler_access, 40);
+        assert_eq!(
+            config.scoring.discovery,
+            ScoringWeights::default().discovery
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        assert!(toml::from_str::<Config>("user-agent = \"x\"").is_err());
+    }
+}
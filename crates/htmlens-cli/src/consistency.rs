@@ -0,0 +1,469 @@
+//! Structured data consistency
+//!
+//! Cross-checks product values declared in structured data (name, SKU, price,
+//! availability) against the visible page content. Search engines penalize
+//! markup that disagrees with what users see, e.g. a JSON-LD price of 49.99
+//! on a page that shows 59.99, or `InStock` on a page that says "sold out".
+
+use std::collections::HashSet;
+use std::fmt::Write as FmtWrite;
+
+use htmlens_core::insights::{ProductGroupSummary, format_price};
+
+use crate::{push_key_value, push_section_header};
+
+/// Visible phrases for each availability group
+const IN_STOCK_PHRASES: &[&str] = &[
+    "in stock",
+    "add to cart",
+    "add to basket",
+    "add to bag",
+    "buy now",
+    "available now",
+];
+const OUT_OF_STOCK_PHRASES: &[&str] = &[
+    "out of stock",
+    "sold out",
+    "unavailable",
+    "no longer available",
+    "discontinued",
+    "notify me",
+];
+const PRE_ORDER_PHRASES: &[&str] = &["pre order", "preorder", "coming soon"];
+const BACK_ORDER_PHRASES: &[&str] = &["back order", "backorder", "backordered"];
+
+/// Currency markers that make a number on the page a price; letter codes
+/// only count as whole words
+const CURRENCY_MARKERS: &[&str] = &["€", "$", "£", "¥", "eur", "usd", "gbp", "chf"];
+
+/// Verdict for one structured value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    /// The value appears in the visible text
+    Found,
+
+    /// The value could not be found
+    NotFound,
+
+    /// The visible text says something else (what it says)
+    Contradicted(String),
+}
+
+/// One structured value checked against the page
+#[derive(Debug, Clone)]
+pub struct ConsistencyCheck {
+    /// Product the value belongs to (name or SKU)
+    pub subject: String,
+
+    /// Field name ("name", "sku", "price", "availability")
+    pub field: &'static str,
+
+    /// Structured value as displayed
+    pub value: String,
+
+    pub verdict: Verdict,
+}
+
+/// Check product group and variant values against the visible page text
+pub fn check_products(groups: &[ProductGroupSummary], page_text: &str) -> Vec<ConsistencyCheck> {
+    let page = PageText::new(page_text);
+    let mut checks = Vec::new();
+
+    for group in groups {
+        let group_name = group.name.clone().unwrap_or_else(|| "Product".to_string());
+        if let Some(name) = group.name.as_deref() {
+            checks.push(ConsistencyCheck {
+                subject: group_name.clone(),
+                field: "name",
+                value: name.to_string(),
+                verdict: page.find_words(name),
+            });
+        }
+
+        for variant in &group.variants {
+            let subject = variant
+                .sku
+                .clone()
+                .or_else(|| variant.name.clone())
+                .unwrap_or_else(|| group_name.clone());
+            let mut check = |field, value: String, verdict| {
+                checks.push(ConsistencyCheck {
+                    subject: subject.clone(),
+                    field,
+                    value,
+                    verdict,
+                })
+            };
+
+            // Variant names often just repeat the group name
+            if let Some(name) = variant.name.as_deref()
+                && group.name.as_deref() != Some(name)
+            {
+                check("name", name.to_string(), page.find_words(name));
+            }
+            if let Some(sku) = variant.sku.as_deref() {
+                check("sku", sku.to_string(), page.find_words(sku));
+            }
+            if let Some(price) = variant.price_numeric {
+                check(
+                    "price",
+                    format_price(price, variant.price_currency.as_deref()),
+                    page.find_price(price),
+                );
+            }
+            if let Some(availability) = variant.availability.as_deref() {
+                check(
+                    "availability",
+                    availability.to_string(),
+                    page.find_availability(availability),
+                );
+            }
+        }
+    }
+
+    checks
+}
+
+pub fn render_consistency(buf: &mut String, checks: &[ConsistencyCheck]) {
+    if checks.is_empty() {
+        return;
+    }
+
+    push_section_header(buf, "🔎", "Structured Data Consistency");
+    let count =
+        |wanted: fn(&Verdict) -> bool| checks.iter().filter(|check| wanted(&check.verdict)).count();
+    push_key_value(
+        buf,
+        "Checked",
+        &format!(
+            "{} values: {} found, {} not found, {} contradicted",
+            checks.len(),
+            count(|v| *v == Verdict::Found),
+            count(|v| *v == Verdict::NotFound),
+            count(|v| matches!(v, Verdict::Contradicted(_))),
+        ),
+    );
+
+    let mut subject = None;
+    for check in checks {
+        if subject != Some(&check.subject) {
+            let _ = writeln!(buf);
+            let _ = writeln!(buf, "**{}**", check.subject);
+            subject = Some(&check.subject);
+        }
+        let verdict = match &check.verdict {
+            Verdict::Found => "✅ found".to_string(),
+            Verdict::NotFound => "⚠️ not found on page".to_string(),
+            Verdict::Contradicted(seen) => format!("❌ page shows {seen}"),
+        };
+        push_key_value(buf, check.field, &format!("{} → {}", check.value, verdict));
+    }
+    let _ = writeln!(buf);
+}
+
+/// Visible page text prepared for lookups
+struct PageText {
+    /// Text with markdown escapes removed, lowercased
+    raw: String,
+
+    /// Lowercase words separated by single spaces, padded with spaces
+    words: String,
+}
+
+impl PageText {
+    fn new(text: &str) -> Self {
+        let raw = text.replace('\\', "").to_lowercase();
+        Self {
+            words: format!(" {} ", normalize_words(&raw)),
+            raw,
+        }
+    }
+
+    fn contains_words(&self, phrase: &str) -> bool {
+        let phrase = normalize_words(phrase);
+        !phrase.is_empty() && self.words.contains(&format!(" {phrase} "))
+    }
+
+    fn find_words(&self, value: &str) -> Verdict {
+        if self.contains_words(value) {
+            Verdict::Found
+        } else {
+            Verdict::NotFound
+        }
+    }
+
+    /// Found if a price on the page equals the price; contradicted if the
+    /// page only shows other prices
+    ///
+    /// Numbers next to a currency marker are prices. Only on pages without
+    /// any, numbers written like prices (two decimals, e.g. `59.99`) are used
+    /// instead, so "ships in 2 days" never matches a price of 2.00.
+    fn find_price(&self, price: f64) -> Verdict {
+        let numbers = number_candidates(&self.raw);
+        let marked: Vec<_> = numbers
+            .iter()
+            .filter(|(range, _)| has_currency_marker(&self.raw, range.clone()))
+            .collect();
+        let prices = if marked.is_empty() {
+            numbers
+                .iter()
+                .filter(|(range, _)| looks_like_price(&self.raw[range.clone()]))
+                .collect()
+        } else {
+            marked
+        };
+
+        if prices.is_empty() {
+            return Verdict::NotFound;
+        }
+        if prices
+            .iter()
+            .any(|(_, values)| values.iter().any(|value| (value - price).abs() < 0.005))
+        {
+            return Verdict::Found;
+        }
+
+        let mut shown: Vec<String> = prices
+            .iter()
+            .map(|(range, _)| self.raw[range.clone()].to_string())
+            .collect();
+        // Keep the page's order, which puts the old price before the new one
+        let mut seen = HashSet::new();
+        shown.retain(|number| seen.insert(number.clone()));
+        shown.truncate(3);
+        Verdict::Contradicted(shown.join(", "))
+    }
+
+    /// Look for phrases matching the declared availability, then for phrases
+    /// that contradict it
+    fn find_availability(&self, availability: &str) -> Verdict {
+        let (expected, conflicting): (&[&str], &[&str]) = match availability
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "instock" | "limitedavailability" | "onlineonly" | "instoreonly" => {
+                (IN_STOCK_PHRASES, OUT_OF_STOCK_PHRASES)
+            }
+            "outofstock" | "soldout" | "discontinued" => (OUT_OF_STOCK_PHRASES, IN_STOCK_PHRASES),
+            "preorder" | "presale" => (PRE_ORDER_PHRASES, OUT_OF_STOCK_PHRASES),
+            "backorder" => (BACK_ORDER_PHRASES, OUT_OF_STOCK_PHRASES),
+            _ => return Verdict::NotFound,
+        };
+
+        if expected.iter().any(|phrase| self.contains_words(phrase)) {
+            return Verdict::Found;
+        }
+        match conflicting
+            .iter()
+            .find(|phrase| self.contains_words(phrase))
+        {
+            Some(phrase) => Verdict::Contradicted(format!("\"{phrase}\"")),
+            None => Verdict::NotFound,
+        }
+    }
+}
+
+fn normalize_words(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Numbers in the text with their possible values
+///
+/// `1.299,00`, `1,299.00` and `1299` all read as 1299; `3.999` and `3,999`
+/// also read as 3.999 since the separator is ambiguous.
+fn number_candidates(text: &str) -> Vec<(std::ops::Range<usize>, Vec<f64>)> {
+    let mut numbers = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        if c.is_ascii_digit() {
+            start.get_or_insert(index);
+            end = index + 1;
+        } else if (c == '.' || c == ',') && start.is_some() && end == index {
+            continue;
+        } else if let Some(begin) = start.take() {
+            let values = number_values(&text[begin..end]);
+            if !values.is_empty() {
+                numbers.push((begin..end, values));
+            }
+        }
+    }
+
+    numbers
+}
+
+fn number_values(number: &str) -> Vec<f64> {
+    let Some(last) = number.rfind(['.', ',']) else {
+        return number.parse().into_iter().collect();
+    };
+
+    let digits: String = number.chars().filter(char::is_ascii_digit).collect();
+    let integer: String = number[..last]
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    let decimal = format!("{integer}.{}", &number[last + 1..]);
+
+    let mut values: Vec<f64> = Vec::new();
+    // Two decimals (or one): the last separator is the decimal point
+    if number.len() - last <= 3 {
+        values.extend(decimal.parse::<f64>());
+    } else {
+        values.extend(digits.parse::<f64>());
+        // A single separator followed by three digits may still be decimal
+        if number.matches(['.', ',']).count() == 1 {
+            values.extend(decimal.parse::<f64>());
+        }
+    }
+    values
+}
+
+/// Whether a number has two decimals, like `59.99` or `1.299,00`
+fn looks_like_price(number: &str) -> bool {
+    number
+        .rfind(['.', ','])
+        .is_some_and(|separator| number.len() - separator == 3)
+}
+
+fn has_currency_marker(text: &str, range: std::ops::Range<usize>) -> bool {
+    let before = text[..range.start].trim_end();
+    let after = text[range.end..].trim_start();
+    CURRENCY_MARKERS.iter().any(|marker| {
+        // "eur" in "amateur 5" is not a currency
+        let is_code = marker.chars().all(|c| c.is_ascii_alphabetic());
+        let preceded = before
+            .strip_suffix(marker)
+            .is_some_and(|rest| !is_code || !rest.ends_with(char::is_alphanumeric));
+        let followed = after
+            .strip_prefix(marker)
+            .is_some_and(|rest| !is_code || !rest.starts_with(char::is_alphanumeric));
+        preceded || followed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use htmlens_core::insights::VariantSummary;
+
+    fn group(price: f64, availability: &str) -> ProductGroupSummary {
+        ProductGroupSummary {
+            name: Some("Paralane 8.8".to_string()),
+            variants: vec![VariantSummary {
+                name: Some("Paralane 8.8".to_string()),
+                sku: Some("PL-88-M".to_string()),
+                price_numeric: Some(price),
+                price_currency: Some("EUR".to_string()),
+                availability: Some(availability.to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn verdicts(checks: &[ConsistencyCheck]) -> Vec<(&str, Verdict)> {
+        checks
+            .iter()
+            .map(|check| (check.field, check.verdict.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_matching_page() {
+        let page =
+            "# Paralane 8\\.8\n\nArt. PL\\-88\\-M\n\n**€ 3.999,00**\n\nIn stock, ships in 2 days";
+        let checks = check_products(&[group(3999.0, "InStock")], page);
+        assert_eq!(
+            verdicts(&checks),
+            vec![
+                ("name", Verdict::Found),
+                ("sku", Verdict::Found),
+                ("price", Verdict::Found),
+                ("availability", Verdict::Found),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_contradicting_page() {
+        let page = "# Paralane 8.8\n\nWas €69.99, now €59.99\n\nSOLD OUT";
+        let checks = check_products(&[group(49.99, "InStock")], page);
+        assert_eq!(
+            verdicts(&checks),
+            vec![
+                ("name", Verdict::Found),
+                ("sku", Verdict::NotFound),
+                ("price", Verdict::Contradicted("69.99, 59.99".to_string())),
+                (
+                    "availability",
+                    Verdict::Contradicted("\"sold out\"".to_string())
+                ),
+            ]
+        );
+
+        let mut output = String::new();
+        render_consistency(&mut output, &checks);
+        assert!(output.contains("4 values: 1 found, 1 not found, 2 contradicted"));
+        assert!(output.contains("❌ page shows 69.99, 59.99"));
+    }
+
+    #[test]
+    fn test_unmarked_numbers_are_a_fallback() {
+        // Without currency markers, only price-like numbers count
+        let checks = check_products(
+            &[group(49.99, "OutOfStock")],
+            "Paralane 8.8 price 59.99 sold out",
+        );
+        assert_eq!(
+            checks[2].verdict,
+            Verdict::Contradicted("59.99".to_string())
+        );
+
+        // A non-price number equal to the price does not match it
+        let checks = check_products(&[group(2.0, "InStock")], "Paralane 8.8, ships in 2 days");
+        assert_eq!(checks[2].verdict, Verdict::NotFound);
+
+        // Currency-marked numbers win over other numbers
+        let checks = check_products(
+            &[group(49.99, "InStock")],
+            "Paralane 8.8 (49.99 kg) for €59.99",
+        );
+        assert_eq!(
+            checks[2].verdict,
+            Verdict::Contradicted("59.99".to_string())
+        );
+    }
+
+    #[test]
+    fn test_currency_codes_are_whole_words() {
+        assert!(has_currency_marker("59.99 eur", 0..5));
+        assert!(has_currency_marker("eur 59.99", 4..9));
+        assert!(has_currency_marker("price chf 59.99", 10..15));
+        assert!(!has_currency_marker("amateur 5", 8..9));
+        assert!(!has_currency_marker("5 usds", 0..1));
+
+        // Repeated prices are listed once, even when not adjacent
+        let checks = check_products(
+            &[group(49.99, "InStock")],
+            "Paralane: €59.99, amateur 5, €69.99, €59.99",
+        );
+        assert_eq!(
+            checks[2].verdict,
+            Verdict::Contradicted("59.99, 69.99".to_string())
+        );
+    }
+
+    #[test]
+    fn test_number_values() {
+        assert_eq!(number_values("1.299,00"), vec![1299.0]);
+        assert_eq!(number_values("1,299.00"), vec![1299.0]);
+        assert_eq!(number_values("49,99"), vec![49.99]);
+        assert_eq!(number_values("3,999"), vec![3999.0, 3.999]);
+        assert_eq!(number_values("1.299.000"), vec![1299000.0]);
+    }
+}
@@ -0,0 +1,75 @@
+//! `htmlens crawl` subcommand
+//!
+//! Extracts every page listed in a site's sitemap, one after the other, and
+//! writes one output per page: by default one NDJSON report line per page, so
+//! a crawl can be piped straight into `jq` or loaded as a table.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use url::Url;
+
+use htmlens_core::ai_readiness::{self, fetch::HttpFetcher};
+
+use crate::cli::FetchArgs;
+use crate::config::Config;
+use crate::{ExportFormat, InputSource, MarkdownOptions, extract, render_output, write_output};
+
+/// Maximum number of sitemap pages extracted by default
+pub const DEFAULT_PAGE_LIMIT: usize = 20;
+
+#[derive(Args)]
+pub struct CrawlOptions {
+    /// Site whose sitemap is crawled
+    pub url: String,
+
+    /// Maximum sitemap pages to extract
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_PAGE_LIMIT)]
+    pub limit: usize,
+
+    /// Output format per page (see `extract --format`)
+    #[arg(long, value_name = "FORMAT", default_value = "ndjson", value_parser = ExportFormat::parse)]
+    pub format: ExportFormat,
+
+    /// Also save each page's output to a file in DIR
+    #[arg(short = 's', long, value_name = "DIR")]
+    pub save: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub fetch: FetchArgs,
+}
+
+pub async fn run(options: CrawlOptions, config: &Config) -> Result<()> {
+    let parsed_url = Url::parse(&options.url).context("invalid URL")?;
+    let fetch = options.fetch.with_config(config);
+    let fetcher = HttpFetcher::new(&fetch.fetch_options())?;
+    let urls =
+        ai_readiness::sitemap_page_urls(&fetcher, parsed_url.as_str(), options.limit).await?;
+    let markdown_options = MarkdownOptions {
+        lenient: fetch.lenient,
+        ..MarkdownOptions::default()
+    };
+
+    let mut failed = 0;
+    for url in &urls {
+        // One broken page should not end the crawl
+        let extraction = match extract(&InputSource::Url(url.clone()), None, &fetch).await {
+            Ok(extraction) => extraction,
+            Err(err) => {
+                eprintln!("Skipping {url}: {err:#}");
+                failed += 1;
+                continue;
+            }
+        };
+        let (output, extension) = render_output(&extraction, options.format, &markdown_options)?;
+        write_output(&output, options.save.as_deref(), &extraction, extension)?;
+    }
+
+    eprintln!(
+        "Crawled {} of {} sitemap pages",
+        urls.len() - failed,
+        urls.len()
+    );
+    Ok(())
+}
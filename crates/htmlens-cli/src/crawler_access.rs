@@ -0,0 +1,273 @@
+//! `htmlens crawler-access` subcommand
+//!
+//! Evaluates robots.txt for every (URL × crawler) pair and renders the
+//! resulting access matrix as a markdown table, or as JSON with
+//! `--format json`. Crawlers found in the registry are listed with their
+//! vendor and purpose.
+
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Serialize;
+use url::Url;
+
+use htmlens_core::ai_readiness::{
+    self,
+    access_matrix::AccessMatrix,
+    crawlers::{CrawlerConfig, CrawlerInfo, CrawlerRegistry},
+    fetch::HttpFetcher,
+    robots_txt::{PathDecision, RuleKind},
+};
+
+use crate::cli::{HttpArgs, OutputFormat};
+use crate::config::Config;
+use crate::push_section_header;
+
+/// Maximum number of sitemap URLs checked when no `--path` is given
+pub const DEFAULT_SITEMAP_LIMIT: usize = 20;
+
+#[derive(Args)]
+pub struct CrawlerAccessOptions {
+    /// Site whose robots.txt is checked
+    pub url: String,
+
+    /// URL or path to check (repeatable; default: sitemap URLs)
+    #[arg(long = "path", value_name = "PATH|URL")]
+    pub paths: Vec<String>,
+
+    /// Crawler user agent to check (repeatable; default: known AI crawlers)
+    #[arg(long = "crawler", value_name = "NAME")]
+    pub crawlers: Vec<String>,
+
+    /// TOML or JSON file extending the AI crawler registry
+    #[arg(long, value_name = "FILE")]
+    pub crawler_registry: Option<PathBuf>,
+
+    /// Maximum sitemap URLs to check
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_SITEMAP_LIMIT)]
+    pub limit: usize,
+
+    /// Output format
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t)]
+    pub format: OutputFormat,
+
+    #[command(flatten)]
+    pub http: HttpArgs,
+}
+
+pub async fn run(options: CrawlerAccessOptions, config: &Config) -> Result<()> {
+    let parsed_url = Url::parse(&options.url).context("invalid URL")?;
+    let crawler_registry = options
+        .crawler_registry
+        .or_else(|| config.crawler_registry.clone());
+    let registry = load_crawler_registry(crawler_registry.as_deref())?;
+
+    let names = if !options.crawlers.is_empty() {
+        options.crawlers
+    } else {
+        config.crawlers.clone()
+    };
+    let crawlers = resolve_crawlers(names, &registry);
+    let known: Vec<&CrawlerInfo> = crawlers
+        .iter()
+        .filter_map(|crawler| registry.get(crawler))
+        .collect();
+
+    let matrix = ai_readiness::check_crawler_access(
+        &HttpFetcher::new(&options.http.with_config(config).fetch_options())?,
+        parsed_url.as_str(),
+        &options.paths,
+        &crawlers,
+        options.limit,
+    )
+    .await?;
+
+    if options.format == OutputFormat::Json {
+        let output = CrawlerAccessOutput {
+            matrix: &matrix,
+            crawler_info: known,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let mut output = String::new();
+    render_matrix(&mut output, &matrix, &known);
+    print!("{}", output);
+
+    Ok(())
+}
+
+/// User agents to check: the named crawlers, in the registry's spelling so
+/// its metadata applies, or else every registry entry
+fn resolve_crawlers(names: Vec<String>, registry: &CrawlerRegistry) -> Vec<String> {
+    if names.is_empty() {
+        return registry
+            .user_agents()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+    }
+    names
+        .into_iter()
+        .map(|name| {
+            registry
+                .get(&name)
+                .map_or(name, |crawler| crawler.user_agent.clone())
+        })
+        .collect()
+}
+
+/// JSON output: the matrix plus registry entries of its crawlers
+#[derive(Serialize)]
+struct CrawlerAccessOutput<'a> {
+    #[serde(flatten)]
+    matrix: &'a AccessMatrix,
+    crawler_info: Vec<&'a CrawlerInfo>,
+}
+
+/// Built-in crawler registry, extended by a TOML or JSON file if given
+pub fn load_crawler_registry(path: Option<&Path>) -> Result<CrawlerRegistry> {
+    let Some(path) = path else {
+        return Ok(CrawlerRegistry::default());
+    };
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read crawler registry {}", path.display()))?;
+    let config: CrawlerConfig = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&content)
+            .with_context(|| format!("invalid crawler registry {}", path.display()))?
+    } else {
+        serde_json::from_str(&content)
+            .with_context(|| format!("invalid crawler registry {}", path.display()))?
+    };
+
+    Ok(CrawlerRegistry::with_config(config))
+}
+
+fn render_matrix(buf: &mut String, matrix: &AccessMatrix, known: &[&CrawlerInfo]) {
+    push_section_header(buf, "🕷️", "Crawler Access Matrix");
+    let header = matrix
+        .crawlers
+        .iter()
+        .map(|crawler| escape_cell(crawler))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let _ = writeln!(buf, "| Path | {} |", header);
+    let _ = writeln!(buf, "|---|{}", "---|".repeat(matrix.crawlers.len()));
+    for row in &matrix.rows {
+        let cells = row
+            .cells
+            .iter()
+            .map(|cell| if cell.decision.allowed { "✅" } else { "❌" })
+            .collect::<Vec<_>>()
+            .join(" | ");
+        let _ = writeln!(buf, "| {} | {} |", escape_cell(&row.path), cells);
+    }
+    let _ = writeln!(buf);
+    let _ = writeln!(
+        buf,
+        "{} of {} checks blocked.",
+        matrix.blocked_count(),
+        matrix.rows.len() * matrix.crawlers.len()
+    );
+    let _ = writeln!(buf);
+
+    if !known.is_empty() {
+        push_section_header(buf, "🤖", "Crawlers");
+        for crawler in known {
+            let _ = writeln!(
+                buf,
+                "- {}: {}, {}",
+                crawler.user_agent, crawler.vendor, crawler.purpose
+            );
+        }
+        let _ = writeln!(buf);
+    }
+
+    push_section_header(buf, "🔎", "Deciding Rules");
+    let mut any_rule = false;
+    for row in &matrix.rows {
+        // Crawlers decided by the same rule share one line
+        let mut decisions: Vec<(&PathDecision, Vec<&str>)> = Vec::new();
+        for cell in row.cells.iter().filter(|cell| cell.decision.rule.is_some()) {
+            match decisions.iter_mut().find(|(d, _)| *d == &cell.decision) {
+                Some((_, crawlers)) => crawlers.push(&cell.crawler),
+                None => decisions.push((&cell.decision, vec![&cell.crawler])),
+            }
+        }
+        if decisions.is_empty() {
+            continue;
+        }
+
+        any_rule = true;
+        let _ = writeln!(buf, "{}", row.path);
+        for (decision, crawlers) in decisions {
+            let _ = writeln!(
+                buf,
+                "  • {} {}: {}",
+                if decision.allowed { "✅" } else { "❌" },
+                crawlers.join(", "),
+                rule_label(decision)
+            );
+        }
+    }
+    if !any_rule {
+        let _ = writeln!(buf, "No robots.txt rules matched; all paths are allowed.");
+    }
+}
+
+fn rule_label(decision: &PathDecision) -> String {
+    let Some(rule) = decision.rule.as_ref() else {
+        return "no matching rule".to_string();
+    };
+    let kind = match rule.kind {
+        RuleKind::Allow => "Allow",
+        RuleKind::Disallow => "Disallow",
+    };
+    format!(
+        "{kind}: {} (line {}, group {})",
+        rule.pattern,
+        rule.line,
+        decision.group.as_deref().unwrap_or("-")
+    )
+}
+
+/// Escape `|` so paths with query strings like `?a|b` stay in one table cell
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_crawlers_use_the_registry() {
+        let registry = CrawlerRegistry::builtin();
+        let crawlers = resolve_crawlers(vec!["claudebot".into(), "MyBot".into()], &registry);
+        assert_eq!(crawlers, ["ClaudeBot", "MyBot"]);
+        assert_eq!(registry.get(&crawlers[0]).unwrap().vendor, "Anthropic");
+
+        let all = resolve_crawlers(Vec::new(), &registry);
+        assert_eq!(all.len(), registry.crawlers().len());
+    }
+
+    #[test]
+    fn test_pipes_in_paths_are_escaped() {
+        let robots =
+            ai_readiness::robots_txt::parse_robots_txt("User-agent: *\nDisallow: /private\n");
+        let matrix = ai_readiness::access_matrix::build_access_matrix(
+            &robots,
+            &["/search?q=a|b".to_string()],
+            &["GPTBot"],
+        );
+
+        let mut buf = String::new();
+        render_matrix(&mut buf, &matrix, &[]);
+        assert!(buf.contains("| /search?q=a\\|b | ✅ |"), "{buf}");
+    }
+}
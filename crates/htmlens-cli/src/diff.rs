@@ -0,0 +1,532 @@
+//! `htmlens diff` subcommand
+//!
+//! Compares the knowledge graphs of two inputs, e.g. a page before and after
+//! a deploy, or staging against production. Nodes are matched by `@id`.
+//! Blank nodes are labeled by their content, so a changed blank node gets a
+//! new label; the remaining blank nodes are paired by type and a shared
+//! identifier (`url`, `sameAs`, GTIN, SKU) or else `name`, and only those
+//! without a counterpart show up as removed or added. Exits with status 1 when the graphs
+//! differ and 2 on errors, like `diff`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as FmtWrite;
+use std::process;
+
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use url::Url;
+
+use htmlens_core::{
+    GraphEdge, GraphNode, KnowledgeGraph, export::shorten_iri, node_identifier_keys,
+};
+
+use crate::cli::{FetchArgs, OutputFormat};
+use crate::config::Config;
+use crate::{InputSource, extract, push_key_value, push_section_header};
+
+#[derive(Args)]
+pub struct DiffOptions {
+    /// Old page: URL, HTML or JSON-LD file, '-' for stdin, or JSON-LD
+    #[arg(value_name = "OLD")]
+    pub old: String,
+
+    /// New page: URL, HTML or JSON-LD file, '-' for stdin, or JSON-LD
+    #[arg(value_name = "NEW")]
+    pub new: String,
+
+    /// Resolve relative IRIs against URL (default: the page URL, or the
+    /// file's file:// URL)
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<Url>,
+
+    /// Output format
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t)]
+    pub format: OutputFormat,
+
+    #[command(flatten)]
+    pub fetch: FetchArgs,
+}
+
+/// Differences between two knowledge graphs
+#[derive(Debug, Serialize)]
+pub struct GraphDiff {
+    pub old_hash: String,
+    pub new_hash: String,
+    pub added_nodes: Vec<NodeRef>,
+    pub removed_nodes: Vec<NodeRef>,
+    pub changed_nodes: Vec<NodeChange>,
+    pub added_edges: Vec<GraphEdge>,
+    pub removed_edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodeRef {
+    pub id: String,
+    pub types: Vec<String>,
+}
+
+/// A node present in both graphs with different types or properties
+#[derive(Debug, Serialize)]
+pub struct NodeChange {
+    pub id: String,
+    /// Label of a blank node in the old graph, when it differs from `id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_id: Option<String>,
+    pub types: Vec<String>,
+    pub properties: Vec<PropertyChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PropertyChange {
+    /// Predicate IRI, or `@type`
+    pub property: String,
+    pub old: Option<JsonValue>,
+    pub new: Option<JsonValue>,
+}
+
+impl GraphDiff {
+    pub fn new(old: &KnowledgeGraph, new: &KnowledgeGraph) -> Self {
+        let old_nodes: BTreeMap<&str, &GraphNode> = old
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node))
+            .collect();
+        let new_nodes: BTreeMap<&str, &GraphNode> = new
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node))
+            .collect();
+
+        // Old blank node label -> new one
+        let pairs = pair_blank_nodes(old, new, &old_nodes, &new_nodes);
+        let paired_new: BTreeSet<&str> = pairs.values().copied().collect();
+
+        let node_ref = |node: &GraphNode| NodeRef {
+            id: node.id.clone(),
+            types: node.types.clone(),
+        };
+        let added_nodes = new_nodes
+            .iter()
+            .filter(|(id, _)| !old_nodes.contains_key(*id) && !paired_new.contains(*id))
+            .map(|(_, node)| node_ref(node))
+            .collect();
+        let removed_nodes = old_nodes
+            .iter()
+            .filter(|(id, _)| !new_nodes.contains_key(*id) && !pairs.contains_key(*id))
+            .map(|(_, node)| node_ref(node))
+            .collect();
+        let changed_nodes = old_nodes
+            .iter()
+            .filter_map(|(id, old_node)| {
+                let new_id = pairs.get(id).copied().unwrap_or(id);
+                let new_node = new_nodes.get(new_id)?;
+                let properties = property_changes(old_node, new_node);
+                (!properties.is_empty()).then(|| NodeChange {
+                    id: new_id.to_string(),
+                    old_id: (new_id != *id).then(|| id.to_string()),
+                    types: new_node.types.clone(),
+                    properties,
+                })
+            })
+            .collect();
+
+        // Old edges between paired blank nodes are compared under the new labels
+        let relabel = |id: &String| {
+            pairs
+                .get(id.as_str())
+                .map_or_else(|| id.clone(), |new_id| new_id.to_string())
+        };
+        let old_edges: BTreeSet<GraphEdge> = old
+            .edges
+            .iter()
+            .map(|edge| GraphEdge {
+                from: relabel(&edge.from),
+                to: relabel(&edge.to),
+                predicate: edge.predicate.clone(),
+            })
+            .collect();
+        let new_edges: BTreeSet<GraphEdge> = new.edges.iter().cloned().collect();
+
+        Self {
+            old_hash: old.canonical_hash(),
+            new_hash: new.canonical_hash(),
+            added_nodes,
+            removed_nodes,
+            changed_nodes,
+            added_edges: new_edges.difference(&old_edges).cloned().collect(),
+            removed_edges: old_edges.difference(&new_edges).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+/// Pair the blank nodes that only one graph has
+///
+/// Nodes pair up when their types match and they share an identifier key
+/// (see [`node_identifier_keys`]); nodes without one pair up by `name`.
+fn pair_blank_nodes<'a>(
+    old: &'a KnowledgeGraph,
+    new: &'a KnowledgeGraph,
+    old_nodes: &BTreeMap<&'a str, &'a GraphNode>,
+    new_nodes: &BTreeMap<&'a str, &'a GraphNode>,
+) -> BTreeMap<&'a str, &'a str> {
+    let old_blank = unmatched_blank_nodes(old_nodes, new_nodes, &old.edges);
+    let new_blank = unmatched_blank_nodes(new_nodes, old_nodes, &new.edges);
+
+    let same_identifier =
+        |a: &BlankNode, b: &BlankNode| a.types == b.types && !a.keys.is_disjoint(&b.keys);
+    let same_name = |a: &BlankNode, b: &BlankNode| {
+        a.types == b.types
+            && a.keys.is_empty()
+            && b.keys.is_empty()
+            && a.name.is_some()
+            && a.name == b.name
+    };
+
+    let mut pairs = BTreeMap::new();
+    let mut taken = BTreeSet::new();
+    for matches in [
+        &same_identifier as &dyn Fn(&BlankNode, &BlankNode) -> bool,
+        &same_name,
+    ] {
+        for old_node in &old_blank {
+            if pairs.contains_key(old_node.id) {
+                continue;
+            }
+            if let Some(new_node) = new_blank
+                .iter()
+                .find(|new_node| !taken.contains(new_node.id) && matches(old_node, new_node))
+            {
+                pairs.insert(old_node.id, new_node.id);
+                taken.insert(new_node.id);
+            }
+        }
+    }
+    pairs
+}
+
+/// What [`pair_blank_nodes`] compares
+struct BlankNode<'a> {
+    id: &'a str,
+    types: BTreeSet<&'a str>,
+    keys: BTreeSet<String>,
+    name: Option<&'a JsonValue>,
+}
+
+/// Blank nodes of `nodes` whose label `others` lacks
+fn unmatched_blank_nodes<'a>(
+    nodes: &BTreeMap<&'a str, &'a GraphNode>,
+    others: &BTreeMap<&'a str, &'a GraphNode>,
+    edges: &[GraphEdge],
+) -> Vec<BlankNode<'a>> {
+    nodes
+        .iter()
+        .filter(|(id, _)| id.starts_with("_:") && !others.contains_key(*id))
+        .map(|(id, node)| BlankNode {
+            id,
+            types: node.types.iter().map(String::as_str).collect(),
+            keys: node_identifier_keys(node, edges),
+            name: ["https://schema.org/name", "http://schema.org/name"]
+                .iter()
+                .find_map(|key| node.properties.get(*key)),
+        })
+        .collect()
+}
+
+fn property_changes(old: &GraphNode, new: &GraphNode) -> Vec<PropertyChange> {
+    let mut changes = Vec::new();
+
+    let sorted = |types: &[String]| types.iter().cloned().collect::<BTreeSet<_>>();
+    if sorted(&old.types) != sorted(&new.types) {
+        changes.push(PropertyChange {
+            property: "@type".to_string(),
+            old: Some(JsonValue::from(old.types.clone())),
+            new: Some(JsonValue::from(new.types.clone())),
+        });
+    }
+
+    let properties: BTreeSet<&String> =
+        old.properties.keys().chain(new.properties.keys()).collect();
+    for property in properties {
+        let old_value = old.properties.get(property);
+        let new_value = new.properties.get(property);
+        if old_value != new_value {
+            changes.push(PropertyChange {
+                property: property.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            });
+        }
+    }
+
+    changes
+}
+
+pub async fn run(options: DiffOptions, config: &Config) -> Result<()> {
+    let fetch = options.fetch.with_config(config);
+    let base_url = options.base_url.as_ref();
+    let old = extract(&InputSource::parse(&options.old), base_url, &fetch).await?;
+    let new = extract(&InputSource::parse(&options.new), base_url, &fetch).await?;
+    let diff = GraphDiff::new(&old.graph, &new.graph);
+
+    if options.format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        let mut output = String::new();
+        render_diff(&mut output, &diff);
+        print!("{}", output);
+    }
+
+    if !diff.is_empty() {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn render_diff(buf: &mut String, diff: &GraphDiff) {
+    push_section_header(buf, "🔀", "Knowledge Graph Diff");
+    push_key_value(buf, "Old graph hash", &diff.old_hash);
+    push_key_value(buf, "New graph hash", &diff.new_hash);
+    if diff.is_empty() {
+        let _ = writeln!(buf, "\nThe graphs are identical.");
+        let _ = writeln!(buf);
+        return;
+    }
+    push_key_value(
+        buf,
+        "Nodes",
+        &format!(
+            "{} added, {} removed, {} changed",
+            diff.added_nodes.len(),
+            diff.removed_nodes.len(),
+            diff.changed_nodes.len()
+        ),
+    );
+    push_key_value(
+        buf,
+        "Edges",
+        &format!(
+            "{} added, {} removed",
+            diff.added_edges.len(),
+            diff.removed_edges.len()
+        ),
+    );
+    let _ = writeln!(buf);
+
+    for (title, nodes, marker) in [
+        ("Added nodes", &diff.added_nodes, '+'),
+        ("Removed nodes", &diff.removed_nodes, '-'),
+    ] {
+        if nodes.is_empty() {
+            continue;
+        }
+        let _ = writeln!(buf, "**{title}**");
+        for node in nodes {
+            let _ = writeln!(buf, "{marker} {}", node_label(&node.id, &node.types));
+        }
+        let _ = writeln!(buf);
+    }
+
+    if !diff.changed_nodes.is_empty() {
+        let _ = writeln!(buf, "**Changed nodes**");
+        for node in &diff.changed_nodes {
+            let _ = match &node.old_id {
+                Some(old_id) => writeln!(
+                    buf,
+                    "~ {} (was {old_id})",
+                    node_label(&node.id, &node.types)
+                ),
+                None => writeln!(buf, "~ {}", node_label(&node.id, &node.types)),
+            };
+            for change in &node.properties {
+                let value = |value: &Option<JsonValue>| match value {
+                    Some(value) => value.to_string(),
+                    None => "–".to_string(),
+                };
+                let _ = writeln!(
+                    buf,
+                    "    {}: {} → {}",
+                    shorten_iri(&change.property),
+                    value(&change.old),
+                    value(&change.new)
+                );
+            }
+        }
+        let _ = writeln!(buf);
+    }
+
+    for (title, edges, marker) in [
+        ("Added edges", &diff.added_edges, '+'),
+        ("Removed edges", &diff.removed_edges, '-'),
+    ] {
+        if edges.is_empty() {
+            continue;
+        }
+        let _ = writeln!(buf, "**{title}**");
+        for edge in edges {
+            let _ = writeln!(
+                buf,
+                "{marker} {} —{}→ {}",
+                edge.from,
+                shorten_iri(&edge.predicate),
+                edge.to
+            );
+        }
+        let _ = writeln!(buf);
+    }
+}
+
+fn node_label(id: &str, types: &[String]) -> String {
+    if types.is_empty() {
+        return id.to_string();
+    }
+    let types: Vec<String> = types.iter().map(|t| shorten_iri(t)).collect();
+    format!("{id} ({})", types.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, name: &str) -> GraphNode {
+        let mut node = GraphNode::new(id.to_string());
+        node.types.push("http://schema.org/Product".to_string());
+        node.properties
+            .insert("http://schema.org/name".to_string(), JsonValue::from(name));
+        node
+    }
+
+    fn edge(from: &str, to: &str) -> GraphEdge {
+        GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            predicate: "http://schema.org/isRelatedTo".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_identical_graphs() {
+        let graph = KnowledgeGraph {
+            nodes: vec![node("https://example.com/a", "A")],
+            edges: Vec::new(),
+        };
+        let diff = GraphDiff::new(&graph, &graph);
+        assert!(diff.is_empty());
+        assert_eq!(diff.old_hash, diff.new_hash);
+    }
+
+    #[test]
+    fn test_added_removed_and_changed() {
+        let old = KnowledgeGraph {
+            nodes: vec![
+                node("https://example.com/a", "A"),
+                node("https://example.com/b", "B"),
+            ],
+            edges: vec![edge("https://example.com/a", "https://example.com/b")],
+        };
+        let new = KnowledgeGraph {
+            nodes: vec![
+                node("https://example.com/a", "A2"),
+                node("https://example.com/c", "C"),
+            ],
+            edges: vec![edge("https://example.com/a", "https://example.com/c")],
+        };
+        let diff = GraphDiff::new(&old, &new);
+
+        assert_eq!(diff.added_nodes.len(), 1);
+        assert_eq!(diff.added_nodes[0].id, "https://example.com/c");
+        assert_eq!(diff.removed_nodes[0].id, "https://example.com/b");
+        assert_eq!(diff.changed_nodes.len(), 1);
+        let change = &diff.changed_nodes[0].properties[0];
+        assert_eq!(change.property, "http://schema.org/name");
+        assert_eq!(change.old, Some(JsonValue::from("A")));
+        assert_eq!(change.new, Some(JsonValue::from("A2")));
+        assert_eq!(diff.added_edges[0].to, "https://example.com/c");
+        assert_eq!(diff.removed_edges[0].to, "https://example.com/b");
+
+        let mut output = String::new();
+        render_diff(&mut output, &diff);
+        assert!(output.contains("~ https://example.com/a (Product)"));
+        assert!(output.contains("name: \"A\" → \"A2\""));
+    }
+
+    #[test]
+    fn test_blank_nodes_are_paired_by_identifier() {
+        let offer = |id: &str, price: &str| {
+            let mut node = GraphNode::new(id.to_string());
+            node.types.push("http://schema.org/Offer".to_string());
+            node.properties
+                .insert("http://schema.org/sku".to_string(), JsonValue::from("B-1"));
+            node.properties.insert(
+                "http://schema.org/price".to_string(),
+                JsonValue::from(price),
+            );
+            node
+        };
+        let product = "https://example.com/bike";
+        let old = KnowledgeGraph {
+            nodes: vec![node(product, "Bike"), offer("_:old", "49.99")],
+            edges: vec![edge(product, "_:old")],
+        };
+        let new = KnowledgeGraph {
+            nodes: vec![
+                node(product, "Bike"),
+                offer("_:new", "59.99"),
+                node("_:other", "Helmet"),
+            ],
+            edges: vec![edge(product, "_:new")],
+        };
+        let diff = GraphDiff::new(&old, &new);
+
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.added_nodes.len(), 1);
+        assert_eq!(diff.added_nodes[0].id, "_:other");
+        assert_eq!(diff.changed_nodes.len(), 1);
+        assert_eq!(diff.changed_nodes[0].id, "_:new");
+        assert_eq!(diff.changed_nodes[0].old_id.as_deref(), Some("_:old"));
+        assert_eq!(
+            diff.changed_nodes[0].properties[0].new,
+            Some(JsonValue::from("59.99"))
+        );
+        assert!(diff.added_edges.is_empty());
+        assert!(diff.removed_edges.is_empty());
+    }
+
+    #[test]
+    fn test_blank_nodes_without_identifiers_are_paired_by_name() {
+        let old = KnowledgeGraph {
+            nodes: vec![node("_:a", "Bike")],
+            edges: Vec::new(),
+        };
+        let mut changed = node("_:b", "Bike");
+        changed.properties.insert(
+            "http://schema.org/color".to_string(),
+            JsonValue::from("red"),
+        );
+        let new = KnowledgeGraph {
+            nodes: vec![changed],
+            edges: Vec::new(),
+        };
+        let diff = GraphDiff::new(&old, &new);
+
+        assert!(diff.added_nodes.is_empty());
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(
+            diff.changed_nodes[0].properties[0].property,
+            "http://schema.org/color"
+        );
+
+        let mut output = String::new();
+        render_diff(&mut output, &diff);
+        assert!(output.contains("~ _:b (Product) (was _:a)"));
+    }
+}
@@ -0,0 +1,72 @@
+//! `htmlens generate` subcommand
+//!
+//! Drafts files for a site from what htmlens can already read. Currently
+//! supports `llms-txt`, which builds llms.txt (and optionally llms-full.txt)
+//! from the sitemap and each page's title, description and JSON-LD types.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use url::Url;
+
+use htmlens_core::ai_readiness::{self, fetch::HttpFetcher};
+
+use crate::cli::HttpArgs;
+use crate::config::Config;
+
+/// Maximum number of sitemap URLs listed by default
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+
+#[derive(Args)]
+pub struct GenerateLlmsTxtOptions {
+    /// Site to draft the llms.txt for
+    pub url: String,
+
+    /// Maximum sitemap URLs to list
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_PAGE_LIMIT)]
+    pub limit: usize,
+
+    /// Also generate llms-full.txt
+    #[arg(long, requires = "output")]
+    pub full: bool,
+
+    /// Write llms.txt to DIR instead of stdout
+    #[arg(short = 'o', long, value_name = "DIR")]
+    pub output: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub http: HttpArgs,
+}
+
+pub async fn run_llms_txt(options: GenerateLlmsTxtOptions, config: &Config) -> Result<()> {
+    let parsed_url = Url::parse(&options.url).context("invalid URL")?;
+    let draft = ai_readiness::draft_llms_txt(
+        &HttpFetcher::new(&options.http.with_config(config).fetch_options())?,
+        parsed_url.as_str(),
+        options.limit,
+        options.full,
+    )
+    .await?;
+
+    let Some(dir) = options.output else {
+        print!("{}", draft.llms_txt);
+        return Ok(());
+    };
+
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("failed to create directory {}", dir.display()))?;
+    let path = dir.join("llms.txt");
+    fs::write(&path, &draft.llms_txt)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    eprintln!("Wrote {} ({} pages)", path.display(), draft.pages.len());
+
+    if let Some(full) = draft.llms_full_txt {
+        let path = dir.join("llms-full.txt");
+        fs::write(&path, full).with_context(|| format!("failed to write {}", path.display()))?;
+        eprintln!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
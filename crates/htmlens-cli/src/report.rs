@@ -0,0 +1,157 @@
+//! Machine-readable report (`--format json` / `--format ndjson`)
+//!
+//! One JSON document per page with everything the markdown report is built
+//! from: page metadata, markdown, structured data blocks, the graph, insights
+//! and diagnostics. The layout is described by `schema/report.schema.json`.
+//! `schema_version` gets a new minor version when fields are added and a new
+//! major version when fields are removed or change meaning.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use htmlens_core::{
+    GraphInsights, KnowledgeGraph,
+    parser::{BlockDiagnostic, PageMetadata, SOURCE_PROPERTY, StructuredDataBlock},
+};
+
+/// Version of the report layout, written to `schema_version`
+pub const SCHEMA_VERSION: &str = "1.0";
+
+/// Report layout selected with `--format`
+#[derive(Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    /// One pretty-printed document
+    Json,
+    /// One document per line
+    Ndjson,
+}
+
+impl ReportFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(Self::Json),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Ndjson => "ndjson",
+        }
+    }
+
+    pub fn render(&self, report: &Report) -> Result<String> {
+        let mut output = match self {
+            Self::Json => serde_json::to_string_pretty(report)?,
+            Self::Ndjson => serde_json::to_string(report)?,
+        };
+        output.push('\n');
+        Ok(output)
+    }
+}
+
+/// The report document
+#[derive(Serialize)]
+pub struct Report<'a> {
+    pub schema_version: &'static str,
+    /// `htmlens <version>`
+    pub generator: String,
+    /// Fetched page or `--base-url`, `None` for other local input
+    pub url: Option<&'a str>,
+    pub page_metadata: Option<&'a PageMetadata>,
+    /// The page as markdown, or `None` for JSON-LD input
+    pub markdown: Option<&'a str>,
+    /// JSON-LD scripts, microdata items and RDFa resources as JSON-LD
+    pub json_ld_blocks: Vec<ReportBlock>,
+    pub graph: &'a KnowledgeGraph,
+    /// SHA-256 of the canonical graph JSON
+    pub graph_hash: String,
+    pub insights: &'a GraphInsights,
+    pub diagnostics: &'a [BlockDiagnostic],
+}
+
+/// A structured data block and where it was found
+#[derive(Serialize)]
+pub struct ReportBlock {
+    /// "json-ld", "microdata" or "rdfa"
+    pub source: &'static str,
+    /// Position among the page's blocks of that syntax (0-based)
+    pub index: usize,
+    /// Byte offset of the script's content in the HTML (JSON-LD only)
+    pub offset: Option<usize>,
+    pub json: JsonValue,
+}
+
+impl ReportBlock {
+    pub fn new(block: &StructuredDataBlock) -> Self {
+        let mut json = serde_json::from_str(&block.json).unwrap_or(JsonValue::Null);
+        // The syntax is recorded once per block
+        strip_source_tags(&mut json);
+        Self {
+            source: block.source.as_str(),
+            index: block.index,
+            offset: block.offset,
+            json,
+        }
+    }
+}
+
+fn strip_source_tags(value: &mut JsonValue) {
+    match value {
+        JsonValue::Array(items) => items.iter_mut().for_each(strip_source_tags),
+        JsonValue::Object(map) => {
+            map.remove(SOURCE_PROPERTY);
+            map.values_mut().for_each(strip_source_tags);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use htmlens_core::parser::StructuredData;
+
+    #[test]
+    fn test_blocks_without_source_tags() {
+        let data = StructuredData::from_json_ld(
+            r#"{"@context": "https://schema.org", "@type": "Product", "offers": {"@type": "Offer"}}"#,
+        );
+        let block = ReportBlock::new(&data.blocks[0]);
+
+        assert_eq!(block.source, "json-ld");
+        assert_eq!(block.json["@type"], "Product");
+        assert!(block.json.get(SOURCE_PROPERTY).is_none());
+        assert!(block.json["offers"].get(SOURCE_PROPERTY).is_none());
+    }
+
+    #[test]
+    fn test_ndjson_is_one_line() {
+        let graph = KnowledgeGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+        let insights = GraphInsights::default();
+        let report = Report {
+            schema_version: SCHEMA_VERSION,
+            generator: "htmlens test".to_string(),
+            url: None,
+            page_metadata: None,
+            markdown: Some("line one\nline two"),
+            json_ld_blocks: Vec::new(),
+            graph: &graph,
+            graph_hash: graph.canonical_hash(),
+            insights: &insights,
+            diagnostics: &[],
+        };
+
+        let line = ReportFormat::Ndjson.render(&report).unwrap();
+        assert_eq!(line.matches('\n').count(), 1);
+        let parsed: JsonValue = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["schema_version"], SCHEMA_VERSION);
+        assert!(ReportFormat::Json.render(&report).unwrap().lines().count() > 1);
+    }
+}
@@ -0,0 +1,105 @@
+//! `htmlens validate` subcommand
+//!
+//! A pass/fail check for CI: reports structured data blocks that strict
+//! parsers reject and product values the visible page contradicts, and exits
+//! with status 1 if there are any (2 on errors).
+
+use std::fmt::Write as FmtWrite;
+use std::process;
+
+use anyhow::Result;
+use clap::Args;
+use url::Url;
+
+use htmlens_core::GraphInsights;
+
+use crate::cli::FetchArgs;
+use crate::config::Config;
+use crate::consistency::{self, ConsistencyCheck, Verdict};
+use crate::{
+    Extraction, InputSource, extract, push_key_value, push_section_header, render_diagnostics,
+};
+
+#[derive(Args)]
+pub struct ValidateOptions {
+    /// Web page URL, HTML or JSON-LD file, '-' for stdin,
+    /// or JSON-LD (must start with '{' or '[')
+    #[arg(value_name = "INPUT")]
+    pub input: String,
+
+    /// Resolve relative IRIs against URL (default: the page URL, or the
+    /// file's file:// URL)
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<Url>,
+
+    #[command(flatten)]
+    pub fetch: FetchArgs,
+}
+
+pub async fn run(options: ValidateOptions, config: &Config) -> Result<()> {
+    let fetch = options.fetch.with_config(config);
+    let extraction = extract(
+        &InputSource::parse(&options.input),
+        options.base_url.as_ref(),
+        &fetch,
+    )
+    .await?;
+    let checks = consistency_checks(&extraction);
+
+    let mut output = String::new();
+    let problems = render_validation(&mut output, &extraction, &checks, fetch.lenient);
+    print!("{}", output);
+
+    if problems > 0 {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn consistency_checks(extraction: &Extraction) -> Vec<ConsistencyCheck> {
+    // Direct JSON-LD input has no visible page to check against
+    if extraction.markdown.is_empty() {
+        return Vec::new();
+    }
+    let insights = GraphInsights::from(&extraction.graph);
+    consistency::check_products(&insights.product_groups, &extraction.markdown)
+}
+
+/// Render the validation result, returning the number of problems
+fn render_validation(
+    buf: &mut String,
+    extraction: &Extraction,
+    checks: &[ConsistencyCheck],
+    lenient: bool,
+) -> usize {
+    let contradicted = checks
+        .iter()
+        .filter(|check| matches!(check.verdict, Verdict::Contradicted(_)))
+        .count();
+    let problems = extraction.diagnostics.len() + contradicted;
+
+    push_section_header(buf, "🧪", "Structured Data Validation");
+    push_key_value(buf, "Blocks", &extraction.blocks.len().to_string());
+    push_key_value(
+        buf,
+        "Graph nodes",
+        &extraction.graph.nodes.len().to_string(),
+    );
+    push_key_value(
+        buf,
+        "Block errors",
+        &extraction.diagnostics.len().to_string(),
+    );
+    push_key_value(buf, "Contradictions", &contradicted.to_string());
+    let _ = writeln!(buf);
+    if problems == 0 {
+        let _ = writeln!(buf, "✅ No problems found.");
+    } else {
+        let _ = writeln!(buf, "❌ {problems} problem(s) found.");
+    }
+    let _ = writeln!(buf);
+
+    render_diagnostics(buf, &extraction.diagnostics, lenient);
+    consistency::render_consistency(buf, checks);
+    problems
+}
@@ -3,8 +3,9 @@
 //! These tests run the CLI as a subprocess to test end-to-end functionality
 
 use std::env;
-use std::path::Path;
-use std::process::Command;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 #[allow(dead_code)]
 fn get_cli_binary() -> String {
@@ -27,11 +28,15 @@ fn get_cli_binary() -> String {
     binary_name.to_string()
 }
 
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
 #[test]
 fn test_cli_help() {
     let output = Command::new("cargo")
         .args(["run", "--package", "htmlens-cli", "--", "--help"])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor") // Go to workspace root
+        .current_dir(workspace_root()) // Go to workspace root
         .output()
         .expect("Failed to run CLI");
 
@@ -55,7 +60,7 @@ fn test_cli_help() {
 fn test_cli_version() {
     let output = Command::new("cargo")
         .args(["run", "--package", "htmlens-cli", "--", "--version"])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor")
+        .current_dir(workspace_root())
         .output()
         .expect("Failed to run CLI");
 
@@ -77,8 +82,15 @@ fn test_cli_direct_json_ld() {
     let json_ld = r#"{"@context": "https://schema.org", "@type": "Product", "name": "Test Product", "description": "A test product"}"#;
 
     let output = Command::new("cargo")
-        .args(["run", "--package", "htmlens-cli", "--", json_ld])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
         .output()
         .expect("Failed to run CLI");
 
@@ -95,8 +107,16 @@ fn test_cli_graph_only_mode() {
         r#"{"@context": "https://schema.org", "@type": "Product", "name": "Test Product"}"#;
 
     let output = Command::new("cargo")
-        .args(["run", "--package", "htmlens-cli", "--", "-g", json_ld])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "-g",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
         .output()
         .expect("Failed to run CLI");
 
@@ -113,7 +133,7 @@ fn test_cli_invalid_json() {
 
     let output = Command::new("cargo")
         .args(["run", "--package", "htmlens-cli", "--", invalid_json])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor")
+        .current_dir(workspace_root())
         .output()
         .expect("Failed to run CLI");
 
@@ -125,7 +145,7 @@ fn test_cli_invalid_json() {
 fn test_cli_invalid_url() {
     let output = Command::new("cargo")
         .args(["run", "--package", "htmlens-cli", "--", "not-a-url"])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor")
+        .current_dir(workspace_root())
         .output()
         .expect("Failed to run CLI");
 
@@ -140,8 +160,16 @@ fn test_cli_mermaid_flag() {
         r#"{"@context": "https://schema.org", "@type": "Product", "name": "Test Product"}"#;
 
     let output = Command::new("cargo")
-        .args(["run", "--package", "htmlens-cli", "--", "-m", json_ld])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "-m",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
         .output()
         .expect("Failed to run CLI");
 
@@ -182,8 +210,15 @@ fn test_cli_complex_product_group() {
     }"#;
 
     let output = Command::new("cargo")
-        .args(["run", "--package", "htmlens-cli", "--", json_ld])
-        .current_dir("/home/apiest/git/DS/md_knowledge_graph_extractor")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
         .output()
         .expect("Failed to run CLI");
 
@@ -196,3 +231,573 @@ fn test_cli_complex_product_group() {
     assert!(stdout.contains("Variants")); // Should show variant section
     assert!(stdout.contains("ProductGroup")); // Should identify as ProductGroup
 }
+
+#[test]
+fn test_cli_ai_readiness_missing_url() {
+    let output = Command::new("cargo")
+        .args(["run", "--package", "htmlens-cli", "--", "ai-readiness"])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("required arguments were not provided"));
+    assert!(stderr.contains("Usage: htmlens ai-readiness"));
+}
+
+#[test]
+fn test_cli_ai_readiness_invalid_url() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "ai-readiness",
+            "not-a-url",
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("invalid URL"));
+}
+
+#[test]
+fn test_cli_crawler_access_missing_url() {
+    let output = Command::new("cargo")
+        .args(["run", "--package", "htmlens-cli", "--", "crawler-access"])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("required arguments were not provided"));
+    assert!(stderr.contains("Usage: htmlens crawler-access"));
+}
+
+#[test]
+fn test_cli_crawler_access_invalid_limit() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "crawler-access",
+            "https://example.com",
+            "--limit",
+            "many",
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("invalid value 'many' for '--limit <N>'"));
+}
+
+#[test]
+fn test_cli_generate_unknown_target() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "generate",
+            "robots-txt",
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("unrecognized subcommand 'robots-txt'"));
+}
+
+#[test]
+fn test_cli_generate_llms_txt_full_requires_output() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "generate",
+            "llms-txt",
+            "https://example.com",
+            "--full",
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("required arguments were not provided"));
+    assert!(stderr.contains("--output <DIR>"));
+}
+
+#[test]
+fn test_cli_ai_readiness_missing_crawler_registry() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "ai-readiness",
+            "https://example.com",
+            "--crawler-registry",
+            "does-not-exist.toml",
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("failed to read crawler registry"));
+}
+
+#[test]
+fn test_cli_offline_reports_unavailable_context() {
+    let json_ld = r#"{"@context": "https://vocab.example/context.jsonld", "@type": "Thing"}"#;
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    // The expansion error is reported instead of silently producing an empty graph
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("Structured Data Errors"));
+    assert!(stdout.contains("json-ld block #1 (expansion)"));
+    assert!(stdout.contains("not bundled or cached (offline mode)"));
+}
+
+#[test]
+fn test_cli_lenient_recovers_almost_json() {
+    let json_ld =
+        r#"{"@context": "https://schema.org", "@type": "Product", "name": "Lenient Bike",}"#;
+
+    let strict = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+    assert!(!strict.status.success());
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "--lenient",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    // The repaired block is used, and the strict rejection is still reported
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("Lenient Bike"));
+    assert!(stdout.contains("json-ld block #1, line 1, column"));
+    assert!(stdout.contains("↳ recovered: removed trailing comma"));
+}
+
+#[test]
+fn test_cli_format_turtle() {
+    let json_ld = r#"{"@context": "https://schema.org", "@type": "Product", "@id": "https://example.com/bike", "name": "Turtle Bike"}"#;
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "--format",
+            "turtle",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    // Only the graph is printed
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.starts_with("@prefix schema: <http://schema.org/> ."));
+    assert!(stdout.contains("<https://example.com/bike> a schema:Product ;"));
+    assert!(stdout.contains("schema:name \"Turtle Bike\" ."));
+    assert!(!stdout.contains("─"));
+}
+
+#[test]
+fn test_cli_format_unknown() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--format=rdfxml",
+            "https://example.com",
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("unknown format 'rdfxml'"));
+}
+
+#[test]
+fn test_cli_format_dot() {
+    let json_ld = r#"{"@context": "https://schema.org", "@type": "Product", "name": "Dot Bike", "offers": {"@type": "Offer", "price": "10"}}"#;
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "--format=dot",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.starts_with("digraph knowledge_graph {"));
+    assert!(stdout.contains("label=\"Dot Bike\""));
+    assert!(stdout.contains("[label=\"offers\"];"));
+}
+
+#[test]
+fn test_cli_format_json_matches_schema() {
+    let json_ld = r#"{"@context": "https://schema.org", "@type": "Product", "name": "Json Bike", "sku": "JB-1", "offers": {"@type": "Offer", "price": "10", "priceCurrency": "EUR"}}"#;
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "--format",
+            "ndjson",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().count(), 1);
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let schema: serde_json::Value =
+        serde_json::from_str(include_str!("../schema/report.schema.json")).unwrap();
+    for key in schema["required"].as_array().unwrap() {
+        assert!(report.get(key.as_str().unwrap()).is_some(), "missing {key}");
+    }
+    assert!(report["schema_version"].as_str().unwrap().starts_with("1."));
+    assert!(report["url"].is_null());
+    assert_eq!(report["json_ld_blocks"][0]["json"]["name"], "Json Bike");
+
+    let group = &report["insights"]["product_groups"][0];
+    assert_eq!(group["name"], "Json Bike");
+    assert_eq!(group["variants"][0]["sku"], "JB-1");
+    assert_eq!(group["variants"][0]["price_display"], "€10");
+}
+
+#[test]
+fn test_cli_extract_subcommand_matches_legacy_invocation() {
+    let json_ld = r#"{"@context": "https://schema.org", "@type": "Product", "@id": "https://example.com/bike", "name": "Alias Bike"}"#;
+
+    let run = |args: &[&str]| {
+        let output = Command::new("cargo")
+            .args(["run", "--package", "htmlens-cli", "--"])
+            .args(args)
+            .current_dir(workspace_root())
+            .output()
+            .expect("Failed to run CLI");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let legacy = run(&["--offline", "-dd", "--format", "ntriples", json_ld]);
+    let extract = run(&[
+        "extract",
+        "--offline",
+        "--data-downloads",
+        "--format",
+        "ntriples",
+        json_ld,
+    ]);
+    assert!(legacy.contains("\"Alias Bike\""));
+    assert_eq!(legacy, extract);
+}
+
+#[test]
+fn test_cli_config_file_sets_defaults() {
+    let json_ld = r#"{"@context": "https://schema.org", "@type": "Product", "@id": "https://example.com/bike", "name": "Config Bike"}"#;
+    let config = env::temp_dir().join(format!("htmlens-test-{}.toml", std::process::id()));
+    std::fs::write(&config, "format = \"turtle\"\noffline = true\n").unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--package", "htmlens-cli", "--", "--config"])
+        .arg(&config)
+        .arg(json_ld)
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+    let overridden = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--format",
+            "ntriples",
+        ])
+        .arg(json_ld)
+        .env("HTMLENS_CONFIG", &config)
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+    std::fs::remove_file(&config).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.starts_with("@prefix schema: <http://schema.org/> ."));
+
+    // Flags take precedence over the file
+    let stdout = String::from_utf8(overridden.stdout).unwrap();
+    assert!(overridden.status.success());
+    assert!(stdout.starts_with("<https://example.com/bike> "));
+}
+
+#[test]
+fn test_cli_completions() {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "completions",
+            "bash",
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("_htmlens()"));
+    assert!(stdout.contains("crawler-access"));
+}
+
+#[test]
+fn test_cli_diff_exit_status() {
+    let old = r#"{"@context": "https://schema.org", "@type": "Product", "@id": "https://example.com/bike", "name": "Old Bike"}"#;
+    let new = r#"{"@context": "https://schema.org", "@type": "Product", "@id": "https://example.com/bike", "name": "New Bike"}"#;
+
+    let diff = |new: &str| {
+        Command::new("cargo")
+            .args([
+                "run",
+                "--package",
+                "htmlens-cli",
+                "--",
+                "diff",
+                "--offline",
+                old,
+                new,
+            ])
+            .current_dir(workspace_root())
+            .output()
+            .expect("Failed to run CLI")
+    };
+
+    let same = diff(old);
+    assert!(same.status.success());
+    assert!(
+        String::from_utf8(same.stdout)
+            .unwrap()
+            .contains("identical")
+    );
+
+    let changed = diff(new);
+    let stdout = String::from_utf8(changed.stdout).unwrap();
+    assert_eq!(changed.status.code(), Some(1));
+    assert!(stdout.contains("name: \"Old Bike\" → \"New Bike\""));
+
+    // Errors are told apart from differences
+    let failed = diff("does-not-exist.html");
+    assert_eq!(failed.status.code(), Some(2));
+}
+
+#[test]
+fn test_cli_validate_fails_on_block_errors() {
+    let json_ld = r#"{"@context": "https://vocab.example/context.jsonld", "@type": "Thing"}"#;
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "validate",
+            "--offline",
+            json_ld,
+        ])
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("1 problem(s) found"));
+    assert!(stdout.contains("json-ld block #1 (expansion)"));
+}
+
+#[test]
+fn test_cli_html_file_with_base_url() {
+    let dir = env::temp_dir().join(format!("htmlens-test-html-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let page = dir.join("bike.html");
+    std::fs::write(
+        &page,
+        r##"<html><body><h1>Bike</h1><script type="application/ld+json">{"@context": "https://schema.org", "@type": "Product", "@id": "#product", "name": "Snapshot Bike"}</script></body></html>"##,
+    )
+    .unwrap();
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "--format",
+            "ntriples",
+            "--base-url",
+            "https://shop.example/bikes/bike",
+        ])
+        .arg(&page)
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("<https://shop.example/bikes/bike#product> "));
+    assert!(stdout.contains("\"Snapshot Bike\""));
+}
+
+#[test]
+fn test_cli_stdin_json_ld() {
+    let mut child = Command::new("cargo")
+        .args([
+            "run",
+            "--package",
+            "htmlens-cli",
+            "--",
+            "--offline",
+            "--format",
+            "ntriples",
+            "-",
+        ])
+        .current_dir(workspace_root())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run CLI");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(br#"{"@context": "https://schema.org", "@type": "Product", "@id": "https://example.com/bike", "name": "Piped Bike"}"#)
+        .unwrap();
+    let output = child.wait_with_output().expect("Failed to run CLI");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success());
+    assert!(stdout.contains("<https://example.com/bike> "));
+    assert!(stdout.contains("\"Piped Bike\""));
+}
+
+#[test]
+fn test_cli_directory_saves_one_file_per_input() {
+    let dir = env::temp_dir().join(format!("htmlens-test-dir-{}", std::process::id()));
+    let pages = dir.join("pages");
+    let out = dir.join("out");
+    std::fs::create_dir_all(&pages).unwrap();
+    std::fs::create_dir_all(&out).unwrap();
+    std::fs::write(
+        pages.join("bike.html"),
+        r#"<html><body><script type="application/ld+json">{"@context": "https://schema.org", "@type": "Product", "name": "Bike"}</script></body></html>"#,
+    )
+    .unwrap();
+    std::fs::write(
+        pages.join("org.jsonld"),
+        r#"{"@context": "https://schema.org", "@type": "Organization", "name": "Pon"}"#,
+    )
+    .unwrap();
+    std::fs::write(pages.join("notes.txt"), "not a page").unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--package", "htmlens-cli", "--", "--offline"])
+        .arg(&pages)
+        .arg(format!("--save={}", out.display()))
+        .current_dir(workspace_root())
+        .output()
+        .expect("Failed to run CLI");
+    let mut saved: Vec<String> = std::fs::read_dir(&out)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    saved.sort();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(saved, vec!["bike.md", "org.md"]);
+}
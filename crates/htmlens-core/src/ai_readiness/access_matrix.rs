@@ -0,0 +1,196 @@
+//! Per-URL crawler access matrix
+//!
+//! Evaluates a list of URLs against robots.txt for a set of crawlers and
+//! records, for every (URL × crawler) pair, whether the crawler may fetch the
+//! URL and which rule decided it. This answers questions like "can ClaudeBot
+//! reach our product pages but not /checkout" that a single site-wide
+//! [`AccessLevel`](super::robots_txt::AccessLevel) per crawler cannot.
+
+use serde::{Deserialize, Serialize};
+
+use super::robots_txt::{PathDecision, RobotsTxtAnalysis, evaluate_path};
+
+/// Access decisions for a set of URLs and crawlers
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessMatrix {
+    /// Crawlers evaluated, in column order
+    pub crawlers: Vec<String>,
+
+    /// One row per URL, in input order
+    pub rows: Vec<AccessMatrixRow>,
+}
+
+/// Access decisions for one URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessMatrixRow {
+    /// URL (or path) as given
+    pub url: String,
+
+    /// Path and query matched against robots.txt rules
+    pub path: String,
+
+    /// One cell per crawler, in [`AccessMatrix::crawlers`] order
+    pub cells: Vec<AccessCell>,
+}
+
+/// Access decision for one (URL × crawler) pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessCell {
+    /// Crawler user agent
+    pub crawler: String,
+
+    /// Outcome and the rule that decided it
+    #[serde(flatten)]
+    pub decision: PathDecision,
+}
+
+impl AccessMatrix {
+    /// Look up the decision for a URL and crawler
+    pub fn decision(&self, url: &str, crawler: &str) -> Option<&PathDecision> {
+        self.rows
+            .iter()
+            .find(|row| row.url == url)?
+            .cells
+            .iter()
+            .find(|cell| cell.crawler == crawler)
+            .map(|cell| &cell.decision)
+    }
+
+    /// Number of (URL × crawler) pairs that are blocked
+    pub fn blocked_count(&self) -> usize {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.cells)
+            .filter(|cell| !cell.decision.allowed)
+            .count()
+    }
+}
+
+/// Build the access matrix for the given URLs and crawlers
+///
+/// URLs may be absolute (`https://example.com/page?q=1`) or plain paths
+/// (`/page`). A missing robots.txt should be passed as
+/// `RobotsTxtAnalysis::default()`, which allows everything.
+pub fn build_access_matrix<S: AsRef<str>>(
+    robots: &RobotsTxtAnalysis,
+    urls: &[String],
+    crawlers: &[S],
+) -> AccessMatrix {
+    let rows = urls
+        .iter()
+        .map(|url| {
+            let path = robots_path(url);
+            let cells = crawlers
+                .iter()
+                .map(|crawler| AccessCell {
+                    crawler: crawler.as_ref().to_string(),
+                    decision: evaluate_path(&path, crawler.as_ref(), robots),
+                })
+                .collect();
+
+            AccessMatrixRow {
+                url: url.clone(),
+                path,
+                cells,
+            }
+        })
+        .collect();
+
+    AccessMatrix {
+        crawlers: crawlers.iter().map(|c| c.as_ref().to_string()).collect(),
+        rows,
+    }
+}
+
+/// Path (plus query) that robots.txt rules are matched against
+fn robots_path(url: &str) -> String {
+    if let Ok(parsed) = url::Url::parse(url) {
+        let mut path = parsed.path().to_string();
+        if let Some(query) = parsed.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+        return path;
+    }
+
+    if url.starts_with('/') {
+        url.to_string()
+    } else {
+        format!("/{url}")
+    }
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+    use crate::ai_readiness::robots_txt::{RuleKind, parse_robots_txt};
+
+    const ROBOTS: &str = r#"
+User-agent: ClaudeBot
+Disallow: /checkout
+Allow: /
+
+User-agent: GPTBot
+Disallow: /
+
+User-agent: *
+Disallow: /admin/
+"#;
+
+    #[test]
+    fn test_matrix_records_deciding_rule() {
+        let robots = parse_robots_txt(ROBOTS);
+        let urls = vec![
+            "https://shop.example/products/widget".to_string(),
+            "https://shop.example/checkout?step=2".to_string(),
+        ];
+        let matrix = build_access_matrix(&robots, &urls, &["ClaudeBot", "GPTBot", "Bingbot"]);
+
+        assert_eq!(matrix.crawlers, vec!["ClaudeBot", "GPTBot", "Bingbot"]);
+        assert_eq!(matrix.rows[1].path, "/checkout?step=2");
+
+        let product = matrix.decision(&urls[0], "ClaudeBot").unwrap();
+        assert!(product.allowed);
+        assert_eq!(product.rule.as_ref().unwrap().kind, RuleKind::Allow);
+
+        let checkout = matrix.decision(&urls[1], "ClaudeBot").unwrap();
+        assert!(!checkout.allowed);
+        assert_eq!(checkout.rule.as_ref().unwrap().pattern, "/checkout");
+        assert_eq!(checkout.group.as_deref(), Some("ClaudeBot"));
+
+        assert!(!matrix.decision(&urls[0], "GPTBot").unwrap().allowed);
+
+        // Bingbot falls back to the wildcard group, where no rule matches
+        let bing = matrix.decision(&urls[1], "Bingbot").unwrap();
+        assert!(bing.allowed);
+        assert_eq!(bing.group.as_deref(), Some("*"));
+        assert!(bing.rule.is_none());
+
+        assert_eq!(matrix.blocked_count(), 3);
+    }
+
+    #[test]
+    fn test_missing_robots_allows_everything() {
+        let robots = RobotsTxtAnalysis::default();
+        let urls = vec!["admin/settings".to_string()];
+        let matrix = build_access_matrix(&robots, &urls, &["GPTBot"]);
+
+        assert_eq!(matrix.rows[0].path, "/admin/settings");
+        let decision = matrix.decision("admin/settings", "GPTBot").unwrap();
+        assert!(decision.allowed);
+        assert!(decision.group.is_none());
+    }
+
+    #[test]
+    fn test_json_cells_are_flat() {
+        let robots = parse_robots_txt(ROBOTS);
+        let matrix = build_access_matrix(&robots, &["/admin/".to_string()], &["Bingbot"]);
+        let json = serde_json::to_value(&matrix).unwrap();
+        let cell = &json["rows"][0]["cells"][0];
+
+        assert_eq!(cell["crawler"], "Bingbot");
+        assert_eq!(cell["allowed"], false);
+        assert_eq!(cell["rule"]["kind"], "disallow");
+        assert_eq!(cell["rule"]["pattern"], "/admin/");
+    }
+}
@@ -0,0 +1,435 @@
+//! Site-wide AI readiness check
+//!
+//! Fetches every resource the analyzers need through a [`Fetcher`] and
+//! aggregates their results into a single [`AiReadinessReport`].
+
+use anyhow::Result;
+
+use super::crawlers::CrawlerRegistry;
+use super::fetch::Fetcher;
+#[cfg(feature = "ai-readiness")]
+use super::fetch::HttpFetcher;
+use super::scoring::ScoringWeights;
+use super::{
+    AiReadinessReport, access_matrix, llms_txt, llms_txt_generator, openapi, robots_txt,
+    semantic_html, sitemap, structured_data, well_known,
+};
+use crate::url_utils::normalize_origin;
+
+/// Maximum number of llms.txt links checked for reachability
+const LLMS_TXT_LINK_CHECK_LIMIT: usize = 20;
+
+/// Run all AI readiness checks against a site and build the scored report
+#[cfg(feature = "ai-readiness")]
+pub async fn check_site(url: &str) -> Result<AiReadinessReport> {
+    let fetcher = HttpFetcher::new(&crate::parser::FetchOptions::default())?;
+    check_site_with(
+        &fetcher,
+        url,
+        &CrawlerRegistry::default(),
+        &ScoringWeights::default(),
+    )
+    .await
+}
+
+/// Run all AI readiness checks, analyzing robots.txt for the crawlers in
+/// `registry` and scoring the report with `weights`
+pub async fn check_site_with<F: Fetcher>(
+    fetcher: &F,
+    url: &str,
+    registry: &CrawlerRegistry,
+    weights: &ScoringWeights,
+) -> Result<AiReadinessReport> {
+    let parsed = url::Url::parse(url)?;
+    let origin = normalize_origin(parsed.as_str());
+
+    let mut report = AiReadinessReport::new(parsed.to_string());
+
+    report.add_well_known(well_known::check_well_known_files(fetcher, &origin).await);
+
+    if let Some(api_url) = report.openapi_url().map(str::to_string) {
+        let validation = match openapi::fetch_and_validate_openapi(fetcher, &api_url).await {
+            Ok(validation) => validation,
+            Err(e) => {
+                let mut validation = openapi::OpenApiValidation::new();
+                validation.add_issue(format!("{:#}", e));
+                validation
+            }
+        };
+        report.add_openapi(validation);
+    }
+
+    let robots = fetch_text(fetcher, &format!("{}/robots.txt", origin))
+        .await
+        .map(|content| robots_txt::parse_robots_txt_with(&content, registry));
+
+    let mut sitemap_urls = robots
+        .as_ref()
+        .map(|analysis| analysis.sitemaps.clone())
+        .unwrap_or_default();
+    sitemap_urls.push(format!("{}/sitemap.xml", origin));
+    report.add_robots_txt(robots);
+
+    report.add_sitemap(fetch_sitemap(fetcher, &origin, &sitemap_urls, None).await);
+    report.add_llms_txt(fetch_llms_txt(fetcher, &origin).await);
+
+    if let Some(html) = fetch_text(fetcher, parsed.as_str()).await {
+        report.add_semantic_html(semantic_html::analyze_semantic_html(&html));
+        report.add_structured_data(structured_data::analyze_structured_data(
+            &html,
+            parsed.as_str(),
+        ));
+    }
+
+    report.calculate_score_with(weights);
+    Ok(report)
+}
+
+/// Build the per-URL crawler access matrix for a site
+///
+/// `urls` may be absolute URLs or paths. When empty, up to `limit` URLs are
+/// taken from the site's sitemap, falling back to the checked URL itself.
+pub async fn check_crawler_access<F: Fetcher, S: AsRef<str>>(
+    fetcher: &F,
+    url: &str,
+    urls: &[String],
+    crawlers: &[S],
+    limit: usize,
+) -> Result<access_matrix::AccessMatrix> {
+    let parsed = url::Url::parse(url)?;
+    let origin = normalize_origin(parsed.as_str());
+
+    // An unavailable robots.txt allows everything (RFC 9309 §2.3.1.3)
+    let robots = fetch_text(fetcher, &format!("{}/robots.txt", origin))
+        .await
+        .map(|content| robots_txt::parse_robots_txt(&content))
+        .unwrap_or_default();
+
+    let mut urls = urls.to_vec();
+    if urls.is_empty() {
+        let mut sitemap_urls = robots.sitemaps.clone();
+        sitemap_urls.push(format!("{}/sitemap.xml", origin));
+        if let Some(sitemap) = fetch_sitemap(fetcher, &origin, &sitemap_urls, Some(limit)).await {
+            urls.extend(
+                sitemap
+                    .url_entries
+                    .into_iter()
+                    .take(limit)
+                    .map(|entry| entry.loc),
+            );
+        }
+    }
+    if urls.is_empty() {
+        urls.push(parsed.to_string());
+    }
+
+    Ok(access_matrix::build_access_matrix(&robots, &urls, crawlers))
+}
+
+/// Up to `limit` page URLs from a site's sitemap
+///
+/// Sitemaps are looked up in robots.txt and at `/sitemap.xml`; nested
+/// sitemaps of a sitemap index are followed.
+pub async fn sitemap_page_urls<F: Fetcher>(
+    fetcher: &F,
+    url: &str,
+    limit: usize,
+) -> Result<Vec<String>> {
+    let parsed = url::Url::parse(url)?;
+    let origin = normalize_origin(parsed.as_str());
+
+    let sitemap = find_sitemap(fetcher, &origin, limit).await?;
+
+    Ok(sitemap
+        .url_entries
+        .into_iter()
+        .take(limit)
+        .map(|entry| entry.loc)
+        .collect())
+}
+
+/// Draft an llms.txt for a site from up to `limit` of its sitemap URLs
+///
+/// Each listed page is fetched for its title, description and JSON-LD types;
+/// with `include_full`, their markdown is also inlined into llms-full.txt.
+pub async fn draft_llms_txt<F: Fetcher>(
+    fetcher: &F,
+    url: &str,
+    limit: usize,
+    include_full: bool,
+) -> Result<llms_txt_generator::LlmsTxtDraft> {
+    let parsed = url::Url::parse(url)?;
+    let origin = normalize_origin(parsed.as_str());
+
+    let home = fetch_text(fetcher, parsed.as_str())
+        .await
+        .unwrap_or_default();
+    let site =
+        llms_txt_generator::LlmsTxtSite::from_html(&home, parsed.host_str().unwrap_or(&origin));
+
+    let sitemap = find_sitemap(fetcher, &origin, limit).await?;
+
+    let mut pages = Vec::new();
+    for entry in sitemap.url_entries.iter().take(limit) {
+        let html = fetch_text(fetcher, &entry.loc).await.unwrap_or_default();
+        pages.push(llms_txt_generator::LlmsTxtPage::from_html(
+            &entry.loc,
+            &html,
+            include_full,
+        ));
+    }
+
+    Ok(llms_txt_generator::LlmsTxtDraft {
+        llms_txt: llms_txt_generator::generate_llms_txt(&site, &pages),
+        llms_full_txt: include_full
+            .then(|| llms_txt_generator::generate_llms_full_txt(&site, &pages)),
+        pages,
+    })
+}
+
+/// Fetch a URL, returning the body only for successful responses
+async fn fetch_text<F: Fetcher>(fetcher: &F, url: &str) -> Option<String> {
+    let response = fetcher.get(url, true).await.ok()?;
+    response.is_success().then_some(response.body)
+}
+
+/// Fetch a plain-text file, treating an HTML page as missing
+///
+/// Sites without the file often answer with an HTML page instead of a 404:
+/// a soft 404, or the shell of a single-page app.
+async fn fetch_plain_text<F: Fetcher>(fetcher: &F, url: &str) -> Option<String> {
+    let response = fetcher.get(url, true).await.ok()?;
+    (response.is_success() && !response.is_html()).then_some(response.body)
+}
+
+/// Fetch and parse llms.txt, its companion llms-full.txt, and check that its
+/// same-origin links are reachable
+async fn fetch_llms_txt<F: Fetcher>(
+    fetcher: &F,
+    origin: &str,
+) -> Option<llms_txt::LlmsTxtAnalysis> {
+    let url = format!("{}/llms.txt", origin);
+    let content = fetch_plain_text(fetcher, &url).await?;
+    let mut analysis = llms_txt::parse_llms_txt(&content, &url);
+
+    let full_url = format!("{}/llms-full.txt", origin);
+    if let Some(full) = fetch_plain_text(fetcher, &full_url).await {
+        analysis.add_full(llms_txt::analyze_llms_full_txt(&full, &full_url));
+    }
+
+    for link in analysis.links_to_check(LLMS_TXT_LINK_CHECK_LIMIT) {
+        let status = link_status(fetcher, &link).await;
+        analysis.record_link_status(&link, status);
+    }
+
+    Some(analysis)
+}
+
+/// HTTP status of a URL, falling back to GET for servers that reject HEAD
+async fn link_status<F: Fetcher>(fetcher: &F, url: &str) -> Option<u16> {
+    let status = fetcher.head(url).await.ok()?;
+    // 405 Method Not Allowed, 501 Not Implemented
+    if status == 405 || status == 501 {
+        return Some(fetcher.get(url, true).await.ok()?.status);
+    }
+    Some(status)
+}
+
+/// The sitemap listed in robots.txt, falling back to `/sitemap.xml`, with
+/// at least `limit` URL entries if the site has that many
+async fn find_sitemap<F: Fetcher>(
+    fetcher: &F,
+    origin: &str,
+    limit: usize,
+) -> Result<sitemap::SitemapAnalysis> {
+    let mut sitemap_urls = fetch_text(fetcher, &format!("{}/robots.txt", origin))
+        .await
+        .map(|content| robots_txt::parse_robots_txt(&content).sitemaps)
+        .unwrap_or_default();
+    sitemap_urls.push(format!("{}/sitemap.xml", origin));
+    fetch_sitemap(fetcher, origin, &sitemap_urls, Some(limit))
+        .await
+        .ok_or_else(|| anyhow::anyhow!("no sitemap found for {}", origin))
+}
+
+/// Fetch the first sitemap that parses, following nested sitemaps of an index
+///
+/// With a `url_limit`, nested sitemaps stop being fetched once that many URL
+/// entries are collected, so the counts only cover the fetched part.
+async fn fetch_sitemap<F: Fetcher>(
+    fetcher: &F,
+    origin: &str,
+    sitemap_urls: &[String],
+    url_limit: Option<usize>,
+) -> Option<sitemap::SitemapAnalysis> {
+    for sitemap_url in sitemap_urls {
+        let Some(content) = fetch_text(fetcher, sitemap_url).await else {
+            continue;
+        };
+        let Ok(mut analysis) = sitemap::parse_sitemap(&content, origin) else {
+            continue;
+        };
+
+        let mut nested = Vec::new();
+        let mut collected = 0;
+        for nested_url in &analysis.nested_sitemaps {
+            if url_limit.is_some_and(|limit| collected >= limit) {
+                break;
+            }
+            if let Some(nested_content) = fetch_text(fetcher, nested_url).await
+                && let Ok(nested_analysis) = sitemap::parse_sitemap(&nested_content, origin)
+            {
+                collected += nested_analysis.url_entries.len();
+                nested.push(nested_analysis);
+            }
+        }
+        analysis.merge_nested(nested);
+
+        return Some(analysis);
+    }
+
+    None
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+    use crate::ai_readiness::fetch::FetchResponse;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// Serves fixed bodies, 404 for anything else, and records requests
+    #[derive(Default)]
+    struct MockFetcher {
+        pages: HashMap<String, String>,
+        requests: RefCell<Vec<String>>,
+    }
+
+    impl MockFetcher {
+        fn with(mut self, url: &str, body: &str) -> Self {
+            self.pages.insert(url.to_string(), body.to_string());
+            self
+        }
+    }
+
+    impl Fetcher for MockFetcher {
+        async fn get(&self, url: &str, _follow_redirects: bool) -> Result<FetchResponse> {
+            self.requests.borrow_mut().push(url.to_string());
+            Ok(match self.pages.get(url) {
+                Some(body) => FetchResponse {
+                    status: 200,
+                    body: body.clone(),
+                    content_type: None,
+                },
+                None => FetchResponse {
+                    status: 404,
+                    body: String::new(),
+                    content_type: None,
+                },
+            })
+        }
+
+        async fn head(&self, url: &str) -> Result<u16> {
+            Ok(if self.pages.contains_key(url) {
+                200
+            } else {
+                404
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_site_uses_robots_sitemap_and_llms_txt() {
+        let fetcher = MockFetcher::default()
+            .with(
+                "https://example.com/robots.txt",
+                "User-agent: *\nAllow: /\n\nSitemap: https://example.com/pages.xml\n",
+            )
+            .with(
+                "https://example.com/pages.xml",
+                r#"<urlset><url><loc>https://example.com/a</loc></url></urlset>"#,
+            )
+            .with(
+                "https://example.com/llms.txt",
+                "# Example\n\n> Summary\n\n## Docs\n\n- [A](/a)\n- [B](/b)\n",
+            )
+            .with("https://example.com/a", "<html></html>");
+
+        let report = check_site_with(
+            &fetcher,
+            "https://example.com/",
+            &CrawlerRegistry::default(),
+            &ScoringWeights::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.sitemap.as_ref().unwrap().url_count, 1);
+        assert!(report.robots_txt.is_some());
+        let llms = report.llms_txt.as_ref().unwrap();
+        assert_eq!(llms.sections[0].links[1].status_code, Some(404));
+        // robots.txt sitemaps are tried before /sitemap.xml
+        assert!(
+            !fetcher
+                .requests
+                .borrow()
+                .contains(&"https://example.com/sitemap.xml".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_html_llms_txt_counts_as_missing() {
+        let fetcher = MockFetcher::default().with(
+            "https://example.com/llms.txt",
+            "<!DOCTYPE html>\n<html><head><title>Not found</title></head></html>",
+        );
+
+        let report = check_site_with(
+            &fetcher,
+            "https://example.com/",
+            &CrawlerRegistry::default(),
+            &ScoringWeights::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(report.llms_txt.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sitemap_index_is_fetched_up_to_the_limit() {
+        let mut fetcher = MockFetcher::default().with(
+            "https://example.com/sitemap.xml",
+            r#"<sitemapindex>
+                <sitemap><loc>https://example.com/s1.xml</loc></sitemap>
+                <sitemap><loc>https://example.com/s2.xml</loc></sitemap>
+                <sitemap><loc>https://example.com/s3.xml</loc></sitemap>
+            </sitemapindex>"#,
+        );
+        for n in 1..=3 {
+            fetcher = fetcher.with(
+                &format!("https://example.com/s{n}.xml"),
+                &format!(
+                    "<urlset><url><loc>https://example.com/{n}a</loc></url>\
+                     <url><loc>https://example.com/{n}b</loc></url></urlset>"
+                ),
+            );
+        }
+
+        let urls = sitemap_page_urls(&fetcher, "https://example.com/", 3)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/1a",
+                "https://example.com/1b",
+                "https://example.com/2a"
+            ]
+        );
+        let requests = fetcher.requests.borrow();
+        assert!(requests.contains(&"https://example.com/s2.xml".to_string()));
+        assert!(!requests.contains(&"https://example.com/s3.xml".to_string()));
+    }
+}
@@ -0,0 +1,339 @@
+//! Registry of known AI crawlers
+//!
+//! Each crawler is described by its robots.txt user agent token, the vendor
+//! operating it and what it fetches pages for, so reports can distinguish
+//! "blocks training" from "blocks AI search". The built-in list can be
+//! extended or overridden with a [`CrawlerConfig`] loaded from TOML or JSON:
+//!
+//! ```toml
+//! [[crawlers]]
+//! user_agent = "ExampleBot"
+//! vendor = "Example Inc."
+//! purpose = "training"
+//! docs_url = "https://example.com/bot"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a crawler fetches pages for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrawlerPurpose {
+    /// Collects content to train AI models
+    Training,
+
+    /// Indexes content for search or retrieval-augmented answers
+    Search,
+
+    /// Fetches a page on behalf of a user request
+    UserFetch,
+}
+
+impl CrawlerPurpose {
+    /// All purposes, in display order
+    pub const ALL: [CrawlerPurpose; 3] = [
+        CrawlerPurpose::Training,
+        CrawlerPurpose::Search,
+        CrawlerPurpose::UserFetch,
+    ];
+
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            CrawlerPurpose::Training => "training",
+            CrawlerPurpose::Search => "search/retrieval",
+            CrawlerPurpose::UserFetch => "user-triggered fetch",
+        }
+    }
+}
+
+impl fmt::Display for CrawlerPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// A known crawler
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrawlerInfo {
+    /// User agent token matched against robots.txt groups
+    pub user_agent: String,
+
+    /// Company or project operating the crawler
+    pub vendor: String,
+
+    /// What the crawler fetches pages for
+    pub purpose: CrawlerPurpose,
+
+    /// Documentation describing the crawler
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+}
+
+/// User-supplied additions to the crawler registry
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrawlerConfig {
+    /// Drop the built-in crawlers instead of extending them
+    #[serde(default)]
+    pub replace_builtin: bool,
+
+    /// Crawlers to add; entries with a known user agent override it
+    #[serde(default)]
+    pub crawlers: Vec<CrawlerInfo>,
+}
+
+/// Set of crawlers that reports evaluate robots.txt for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrawlerRegistry {
+    crawlers: Vec<CrawlerInfo>,
+}
+
+/// Built-in crawlers: (user agent, vendor, purpose, documentation)
+///
+/// Only crawlers whose operator documents the user agent token are listed;
+/// others can be added with a [`CrawlerConfig`].
+const BUILTIN_CRAWLERS: &[(&str, &str, CrawlerPurpose, Option<&str>)] = &[
+    (
+        "GPTBot",
+        "OpenAI",
+        CrawlerPurpose::Training,
+        Some("https://platform.openai.com/docs/bots"),
+    ),
+    (
+        "OAI-SearchBot",
+        "OpenAI",
+        CrawlerPurpose::Search,
+        Some("https://platform.openai.com/docs/bots"),
+    ),
+    (
+        "ChatGPT-User",
+        "OpenAI",
+        CrawlerPurpose::UserFetch,
+        Some("https://platform.openai.com/docs/bots"),
+    ),
+    (
+        "ClaudeBot",
+        "Anthropic",
+        CrawlerPurpose::Training,
+        Some(
+            "https://support.anthropic.com/en/articles/8896518-does-anthropic-crawl-data-from-the-web-and-how-can-site-owners-block-the-crawler",
+        ),
+    ),
+    (
+        "Claude-SearchBot",
+        "Anthropic",
+        CrawlerPurpose::Search,
+        Some(
+            "https://support.anthropic.com/en/articles/8896518-does-anthropic-crawl-data-from-the-web-and-how-can-site-owners-block-the-crawler",
+        ),
+    ),
+    (
+        "Claude-User",
+        "Anthropic",
+        CrawlerPurpose::UserFetch,
+        Some(
+            "https://support.anthropic.com/en/articles/8896518-does-anthropic-crawl-data-from-the-web-and-how-can-site-owners-block-the-crawler",
+        ),
+    ),
+    (
+        "Google-Extended",
+        "Google",
+        CrawlerPurpose::Training,
+        Some("https://developers.google.com/search/docs/crawling-indexing/google-common-crawlers"),
+    ),
+    (
+        "Bingbot",
+        "Microsoft",
+        CrawlerPurpose::Search,
+        Some("https://www.bing.com/webmasters/help/which-crawlers-does-bing-use-8c184ec0"),
+    ),
+    (
+        "Applebot",
+        "Apple",
+        CrawlerPurpose::Search,
+        Some("https://support.apple.com/en-us/119829"),
+    ),
+    (
+        "Applebot-Extended",
+        "Apple",
+        CrawlerPurpose::Training,
+        Some("https://support.apple.com/en-us/119829"),
+    ),
+    (
+        "PerplexityBot",
+        "Perplexity",
+        CrawlerPurpose::Search,
+        Some("https://docs.perplexity.ai/guides/bots"),
+    ),
+    (
+        "Perplexity-User",
+        "Perplexity",
+        CrawlerPurpose::UserFetch,
+        Some("https://docs.perplexity.ai/guides/bots"),
+    ),
+    (
+        "CCBot",
+        "Common Crawl",
+        CrawlerPurpose::Training,
+        Some("https://commoncrawl.org/ccbot"),
+    ),
+    (
+        "Meta-ExternalAgent",
+        "Meta",
+        CrawlerPurpose::Training,
+        Some("https://developers.facebook.com/docs/sharing/webmasters/web-crawlers"),
+    ),
+    (
+        "Meta-ExternalFetcher",
+        "Meta",
+        CrawlerPurpose::UserFetch,
+        Some("https://developers.facebook.com/docs/sharing/webmasters/web-crawlers"),
+    ),
+    (
+        "Amazonbot",
+        "Amazon",
+        CrawlerPurpose::Search,
+        Some("https://developer.amazon.com/amazonbot"),
+    ),
+];
+
+impl Default for CrawlerRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl CrawlerRegistry {
+    /// Registry with the built-in crawlers
+    pub fn builtin() -> Self {
+        let crawlers = BUILTIN_CRAWLERS
+            .iter()
+            .map(|(user_agent, vendor, purpose, docs_url)| CrawlerInfo {
+                user_agent: user_agent.to_string(),
+                vendor: vendor.to_string(),
+                purpose: *purpose,
+                docs_url: docs_url.map(str::to_string),
+            })
+            .collect();
+        Self { crawlers }
+    }
+
+    /// Built-in registry with a user config applied
+    pub fn with_config(config: CrawlerConfig) -> Self {
+        let mut registry = if config.replace_builtin {
+            Self {
+                crawlers: Vec::new(),
+            }
+        } else {
+            Self::builtin()
+        };
+        for crawler in config.crawlers {
+            registry.insert(crawler);
+        }
+        registry
+    }
+
+    /// Add a crawler, replacing any entry with the same user agent
+    pub fn insert(&mut self, crawler: CrawlerInfo) {
+        match self
+            .crawlers
+            .iter_mut()
+            .find(|c| c.user_agent.eq_ignore_ascii_case(&crawler.user_agent))
+        {
+            Some(existing) => *existing = crawler,
+            None => self.crawlers.push(crawler),
+        }
+    }
+
+    /// Look up a crawler by user agent (case-insensitive)
+    pub fn get(&self, user_agent: &str) -> Option<&CrawlerInfo> {
+        self.crawlers
+            .iter()
+            .find(|c| c.user_agent.eq_ignore_ascii_case(user_agent))
+    }
+
+    /// All crawlers, in registry order
+    pub fn crawlers(&self) -> &[CrawlerInfo] {
+        &self.crawlers
+    }
+
+    /// User agent tokens of all crawlers
+    pub fn user_agents(&self) -> Vec<&str> {
+        self.crawlers
+            .iter()
+            .map(|c| c.user_agent.as_str())
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_covers_current_crawlers() {
+        let registry = CrawlerRegistry::builtin();
+        for user_agent in [
+            "OAI-SearchBot",
+            "CCBot",
+            "Claude-SearchBot",
+            "Meta-ExternalAgent",
+            "Amazonbot",
+            "Perplexity-User",
+        ] {
+            assert!(registry.get(user_agent).is_some(), "{user_agent}");
+        }
+
+        let gptbot = registry.get("gptbot").unwrap();
+        assert_eq!(gptbot.vendor, "OpenAI");
+        assert_eq!(gptbot.purpose, CrawlerPurpose::Training);
+        assert_eq!(
+            registry.get("Bingbot").unwrap().purpose,
+            CrawlerPurpose::Search
+        );
+    }
+
+    #[test]
+    fn test_builtin_crawlers_are_documented() {
+        for crawler in CrawlerRegistry::builtin().crawlers() {
+            assert!(
+                crawler
+                    .docs_url
+                    .as_deref()
+                    .is_some_and(|url| url.starts_with("https://")),
+                "{} has no documentation URL",
+                crawler.user_agent
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_extends_and_overrides() {
+        let config: CrawlerConfig = serde_json::from_str(
+            r#"{"crawlers": [
+                {"user_agent": "ExampleBot", "vendor": "Example", "purpose": "search"},
+                {"user_agent": "bingbot", "vendor": "Microsoft", "purpose": "training"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let builtin_len = CrawlerRegistry::builtin().crawlers().len();
+        let registry = CrawlerRegistry::with_config(config.clone());
+        assert_eq!(registry.crawlers().len(), builtin_len + 1);
+        assert_eq!(
+            registry.get("ExampleBot").unwrap().purpose,
+            CrawlerPurpose::Search
+        );
+        assert_eq!(
+            registry.get("Bingbot").unwrap().purpose,
+            CrawlerPurpose::Training
+        );
+
+        let registry = CrawlerRegistry::with_config(CrawlerConfig {
+            replace_builtin: true,
+            ..config
+        });
+        assert_eq!(registry.user_agents(), vec!["ExampleBot", "bingbot"]);
+    }
+}
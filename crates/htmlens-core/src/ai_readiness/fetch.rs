@@ -0,0 +1,115 @@
+//! HTTP access for the site-wide checks
+//!
+//! The checks in this module fetch through a [`Fetcher`] so that the CLI
+//! (reqwest, [`HttpFetcher`]) and the Cloudflare Worker (its `Fetch` API)
+//! run the same orchestration.
+
+use anyhow::Result;
+
+#[cfg(feature = "ai-readiness")]
+use crate::parser::FetchOptions;
+
+/// Response to a [`Fetcher::get`] request
+#[derive(Debug, Clone)]
+pub struct FetchResponse {
+    /// HTTP status code
+    pub status: u16,
+
+    /// Response body
+    pub body: String,
+
+    /// `Content-Type` header
+    pub content_type: Option<String>,
+}
+
+impl FetchResponse {
+    /// Whether the status is 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Whether the response is an HTML page, by its `Content-Type` or, for
+    /// servers that label everything `text/plain`, a body starting with `<`
+    pub fn is_html(&self) -> bool {
+        let html_type = self.content_type.as_deref().is_some_and(|content_type| {
+            let media_type = content_type.split(';').next().unwrap_or_default().trim();
+            media_type.eq_ignore_ascii_case("text/html")
+                || media_type.eq_ignore_ascii_case("application/xhtml+xml")
+        });
+        html_type || self.body.trim_start().starts_with('<')
+    }
+}
+
+/// HTTP client used by the AI readiness checks
+///
+/// Futures are not required to be `Send`, so WASM fetch APIs can implement it.
+#[allow(async_fn_in_trait)]
+pub trait Fetcher {
+    /// GET `url`; with `follow_redirects` false, a redirect is returned as
+    /// the response
+    async fn get(&self, url: &str, follow_redirects: bool) -> Result<FetchResponse>;
+
+    /// Status of a HEAD request to `url`
+    async fn head(&self, url: &str) -> Result<u16>;
+}
+
+/// reqwest-based [`Fetcher`]
+#[cfg(feature = "ai-readiness")]
+pub struct HttpFetcher {
+    client: reqwest::Client,
+    /// Same settings, but redirects are not followed
+    no_redirects: reqwest::Client,
+}
+
+#[cfg(feature = "ai-readiness")]
+impl HttpFetcher {
+    /// Request timeout when `options` sets none
+    const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+    /// Client with the user agent and timeout of `options`
+    pub fn new(options: &FetchOptions) -> Result<Self> {
+        let user_agent = options.user_agent.clone().unwrap_or_else(|| {
+            format!("htmlens-ai-readiness-checker/{}", env!("CARGO_PKG_VERSION"))
+        });
+        let timeout = options
+            .timeout
+            .unwrap_or(std::time::Duration::from_secs(Self::DEFAULT_TIMEOUT_SECS));
+        let builder = || {
+            reqwest::Client::builder()
+                .timeout(timeout)
+                .user_agent(user_agent.as_str())
+        };
+        Ok(Self {
+            client: builder().build()?,
+            no_redirects: builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()?,
+        })
+    }
+}
+
+#[cfg(feature = "ai-readiness")]
+impl Fetcher for HttpFetcher {
+    async fn get(&self, url: &str, follow_redirects: bool) -> Result<FetchResponse> {
+        let client = if follow_redirects {
+            &self.client
+        } else {
+            &self.no_redirects
+        };
+        let response = client.get(url).send().await?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        Ok(FetchResponse {
+            status: response.status().as_u16(),
+            body: response.text().await?,
+            content_type,
+        })
+    }
+
+    async fn head(&self, url: &str) -> Result<u16> {
+        Ok(self.client.head(url).send().await?.status().as_u16())
+    }
+}
@@ -0,0 +1,545 @@
+//! llms.txt parser and validator
+//!
+//! `/llms.txt` is a markdown file that points LLMs at a site's curated
+//! content: an H1 title, an optional blockquote summary, then H2 sections
+//! with lists of `- [name](url): notes` links. `/llms-full.txt` is its
+//! companion with the full content inlined.
+//!
+//! Specification: https://llmstxt.org/
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Results from parsing llms.txt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmsTxtAnalysis {
+    /// URL llms.txt was fetched from
+    pub url: String,
+
+    /// H1 title (required by the spec)
+    pub title: Option<String>,
+
+    /// Blockquote summary following the title
+    pub summary: Option<String>,
+
+    /// H2 sections with their link lists, in file order
+    pub sections: Vec<LlmsTxtSection>,
+
+    /// Companion llms-full.txt (None if not found)
+    pub full: Option<LlmsFullTxt>,
+
+    /// Issues found
+    pub issues: Vec<LlmsTxtIssue>,
+}
+
+/// A problem found in llms.txt or the files it links to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmsTxtIssue {
+    /// What is wrong, for callers that weigh issues
+    pub kind: LlmsTxtIssueKind,
+
+    /// Description of the issue
+    pub message: String,
+}
+
+/// Kind of an [`LlmsTxtIssue`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmsTxtIssueKind {
+    /// No H1 title, which the spec requires
+    MissingTitle,
+
+    /// More than one H1 title
+    MultipleTitles,
+
+    /// Content before the H1 title
+    ContentBeforeTitle,
+
+    /// No blockquote summary after the title
+    MissingSummary,
+
+    /// No H2 sections
+    NoSections,
+
+    /// An H2 section without links
+    EmptySection,
+
+    /// A list item that is not a markdown link
+    InvalidLink,
+
+    /// Links to other origins
+    OffOriginLinks,
+
+    /// A linked URL answered with an error status
+    BrokenLink,
+
+    /// A linked URL did not answer
+    UnreachableLink,
+
+    /// llms-full.txt is empty
+    EmptyFull,
+}
+
+impl LlmsTxtIssue {
+    fn new(kind: LlmsTxtIssueKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for LlmsTxtIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// An H2 section of llms.txt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmsTxtSection {
+    /// Section heading
+    pub title: String,
+
+    /// Whether this is the "Optional" section, which LLMs may skip
+    pub optional: bool,
+
+    /// Links listed in the section
+    pub links: Vec<LlmsTxtLink>,
+}
+
+/// A `- [name](url): notes` entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmsTxtLink {
+    /// Link text
+    pub title: String,
+
+    /// Absolute URL (relative links are resolved against llms.txt)
+    pub url: String,
+
+    /// Notes after the link
+    pub description: Option<String>,
+
+    /// Whether the link points to the same origin as llms.txt
+    pub same_origin: bool,
+
+    /// HTTP status when the link was checked (None if not checked or no
+    /// response)
+    pub status_code: Option<u16>,
+}
+
+/// Summary of llms-full.txt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmsFullTxt {
+    /// URL llms-full.txt was fetched from
+    pub url: String,
+
+    /// Size of the file in bytes
+    pub size_bytes: usize,
+
+    /// H1 title, if any
+    pub title: Option<String>,
+}
+
+impl LlmsTxtAnalysis {
+    /// Total number of links across all sections
+    pub fn link_count(&self) -> usize {
+        self.sections.iter().map(|s| s.links.len()).sum()
+    }
+
+    /// Distinct same-origin link URLs to check for reachability, in file order
+    pub fn links_to_check(&self, limit: usize) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for link in self.sections.iter().flat_map(|s| &s.links) {
+            if link.same_origin && !urls.contains(&link.url) {
+                urls.push(link.url.clone());
+            }
+        }
+        urls.truncate(limit);
+        urls
+    }
+
+    /// Record the result of checking a link (`None` if there was no response)
+    pub fn record_link_status(&mut self, url: &str, status_code: Option<u16>) {
+        for link in self
+            .sections
+            .iter_mut()
+            .flat_map(|s| s.links.iter_mut())
+            .filter(|link| link.url == url)
+        {
+            link.status_code = status_code;
+        }
+
+        match status_code {
+            Some(code) if (200..400).contains(&code) => {}
+            Some(code) => self.issues.push(LlmsTxtIssue::new(
+                LlmsTxtIssueKind::BrokenLink,
+                format!("Linked URL {} returned HTTP {}", url, code),
+            )),
+            None => self.issues.push(LlmsTxtIssue::new(
+                LlmsTxtIssueKind::UnreachableLink,
+                format!("Linked URL {} is not reachable", url),
+            )),
+        }
+    }
+
+    /// Record the companion llms-full.txt
+    pub fn add_full(&mut self, full: LlmsFullTxt) {
+        if full.size_bytes == 0 {
+            self.issues.push(LlmsTxtIssue::new(
+                LlmsTxtIssueKind::EmptyFull,
+                "llms-full.txt is empty",
+            ));
+        }
+        self.full = Some(full);
+    }
+}
+
+/// Parse llms.txt content fetched from `url`
+pub fn parse_llms_txt(content: &str, url: &str) -> LlmsTxtAnalysis {
+    let base = Url::parse(url).ok();
+    let mut analysis = LlmsTxtAnalysis {
+        url: url.to_string(),
+        title: None,
+        summary: None,
+        sections: Vec::new(),
+        full: None,
+        issues: Vec::new(),
+    };
+
+    let mut summary_lines: Vec<&str> = Vec::new();
+    let mut seen_content = false;
+    let mut summary_open = false;
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || line.is_empty() {
+            continue;
+        }
+
+        if let Some(title) = line.strip_prefix("# ") {
+            if analysis.title.is_some() {
+                analysis.issues.push(LlmsTxtIssue::new(
+                    LlmsTxtIssueKind::MultipleTitles,
+                    format!("Multiple H1 titles (extra: '{}')", title.trim()),
+                ));
+            } else {
+                if seen_content {
+                    analysis.issues.push(LlmsTxtIssue::new(
+                        LlmsTxtIssueKind::ContentBeforeTitle,
+                        "Content appears before the H1 title",
+                    ));
+                }
+                analysis.title = Some(title.trim().to_string());
+                summary_open = true;
+            }
+            seen_content = true;
+            continue;
+        }
+
+        if let Some(heading) = line.strip_prefix("## ") {
+            let title = heading.trim().to_string();
+            analysis.sections.push(LlmsTxtSection {
+                optional: title.eq_ignore_ascii_case("optional"),
+                title,
+                links: Vec::new(),
+            });
+            seen_content = true;
+            summary_open = false;
+            continue;
+        }
+
+        // Blockquote summary directly after the title, before any other content
+        if let Some(quote) = line.strip_prefix('>')
+            && summary_open
+        {
+            summary_lines.push(quote.trim());
+            continue;
+        }
+        summary_open = false;
+
+        let list_item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+        if let (Some(item), Some(section)) = (list_item, analysis.sections.last_mut()) {
+            match parse_link_item(item, base.as_ref()) {
+                Some(link) => section.links.push(link),
+                None => analysis.issues.push(LlmsTxtIssue::new(
+                    LlmsTxtIssueKind::InvalidLink,
+                    format!(
+                        "Section '{}' has a list item without a valid markdown link: {}",
+                        section.title, item
+                    ),
+                )),
+            }
+        }
+        seen_content = true;
+    }
+
+    if !summary_lines.is_empty() {
+        analysis.summary = Some(unescape_markdown(&summary_lines.join(" ")));
+    }
+
+    if analysis.title.is_none() {
+        analysis.issues.push(LlmsTxtIssue::new(
+            LlmsTxtIssueKind::MissingTitle,
+            "llms.txt has no H1 title (required)",
+        ));
+    }
+    if analysis.summary.is_none() {
+        analysis.issues.push(LlmsTxtIssue::new(
+            LlmsTxtIssueKind::MissingSummary,
+            "llms.txt has no blockquote summary after the title",
+        ));
+    }
+    if analysis.sections.is_empty() {
+        analysis.issues.push(LlmsTxtIssue::new(
+            LlmsTxtIssueKind::NoSections,
+            "llms.txt has no H2 sections listing links",
+        ));
+    }
+    for section in &analysis.sections {
+        if section.links.is_empty() {
+            analysis.issues.push(LlmsTxtIssue::new(
+                LlmsTxtIssueKind::EmptySection,
+                format!("Section '{}' lists no links", section.title),
+            ));
+        }
+    }
+
+    let off_origin: Vec<String> = analysis
+        .sections
+        .iter()
+        .flat_map(|s| &s.links)
+        .filter(|link| !link.same_origin)
+        .map(|link| link.url.clone())
+        .collect();
+    if let Some(first) = off_origin.first() {
+        analysis.issues.push(LlmsTxtIssue::new(
+            LlmsTxtIssueKind::OffOriginLinks,
+            format!(
+                "{} link(s) point to another origin (e.g. {})",
+                off_origin.len(),
+                first
+            ),
+        ));
+    }
+
+    analysis
+}
+
+/// Summarize llms-full.txt content fetched from `url`
+pub fn analyze_llms_full_txt(content: &str, url: &str) -> LlmsFullTxt {
+    LlmsFullTxt {
+        url: url.to_string(),
+        size_bytes: content.trim().len(),
+        title: content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("# "))
+            .map(|title| title.trim().to_string()),
+    }
+}
+
+/// Parse `[name](url)` with optional `: notes`
+fn parse_link_item(item: &str, base: Option<&Url>) -> Option<LlmsTxtLink> {
+    let rest = item.trim().strip_prefix('[')?;
+    let (title, rest) = split_link_text(rest)?;
+    let (href, rest) = split_link_destination(rest)?;
+
+    let resolved = match base {
+        Some(base) => base.join(href.trim()).ok()?,
+        None => Url::parse(href.trim()).ok()?,
+    };
+    let same_origin = base.is_some_and(|base| base.origin() == resolved.origin());
+
+    let description = rest
+        .trim()
+        .strip_prefix(':')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(unescape_markdown);
+
+    Some(LlmsTxtLink {
+        title: unescape_markdown(title.trim()),
+        url: resolved.to_string(),
+        description,
+        same_origin,
+        status_code: None,
+    })
+}
+
+/// Split `text](href...` at the first unescaped `](`
+fn split_link_text(text: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ']' if text[index + 1..].starts_with('(') => {
+                return Some((&text[..index], &text[index + 2..]));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drop the backslash of markdown escapes (`\[` → `[`)
+fn unescape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && let Some(&next) = chars.peek()
+            && next.is_ascii_punctuation()
+        {
+            out.push(next);
+            chars.next();
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Split `href) rest` at the `)` closing the link, so that balanced
+/// parentheses in the URL (e.g. Wikipedia titles) stay part of it
+fn split_link_destination(text: &str) -> Option<(&str, &str)> {
+    let mut depth = 0usize;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some((&text[..index], &text[index + 1..])),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+
+    const LLMS_TXT: &str = r#"# Example Shop
+
+> Bikes and accessories, with specs and sizing guides.
+
+We ship to the EU only.
+
+## Docs
+
+- [Sizing guide](/docs/sizing.md): How to pick a frame size
+- [API](https://example.com/api.md)
+
+## Optional
+
+- [Blog](https://blog.other.com/index.md): Older posts
+"#;
+
+    #[test]
+    fn test_parse_structure() {
+        let analysis = parse_llms_txt(LLMS_TXT, "https://example.com/llms.txt");
+
+        assert_eq!(analysis.title.as_deref(), Some("Example Shop"));
+        assert_eq!(
+            analysis.summary.as_deref(),
+            Some("Bikes and accessories, with specs and sizing guides.")
+        );
+        assert_eq!(analysis.sections.len(), 2);
+        assert!(analysis.sections[1].optional);
+        assert_eq!(analysis.link_count(), 3);
+
+        let sizing = &analysis.sections[0].links[0];
+        assert_eq!(sizing.url, "https://example.com/docs/sizing.md");
+        assert_eq!(
+            sizing.description.as_deref(),
+            Some("How to pick a frame size")
+        );
+        assert!(sizing.same_origin);
+        assert!(analysis.sections[0].links[1].description.is_none());
+
+        assert!(!analysis.sections[1].links[0].same_origin);
+        assert_eq!(analysis.issues.len(), 1);
+        assert_eq!(analysis.issues[0].kind, LlmsTxtIssueKind::OffOriginLinks);
+        assert!(analysis.issues[0].message.contains("another origin"));
+
+        assert_eq!(
+            analysis.links_to_check(10),
+            vec![
+                "https://example.com/docs/sizing.md",
+                "https://example.com/api.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_structure_issues() {
+        let content = "Intro text\n# Title\n## Empty\n## Docs\n- not a link\n";
+        let analysis = parse_llms_txt(content, "https://example.com/llms.txt");
+
+        let kinds: Vec<LlmsTxtIssueKind> = analysis.issues.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LlmsTxtIssueKind::ContentBeforeTitle,
+                LlmsTxtIssueKind::InvalidLink,
+                LlmsTxtIssueKind::MissingSummary,
+                LlmsTxtIssueKind::EmptySection,
+                LlmsTxtIssueKind::EmptySection,
+            ]
+        );
+        assert!(
+            analysis.issues[3]
+                .message
+                .contains("Section 'Empty' lists no links")
+        );
+
+        let analysis = parse_llms_txt("Just text", "https://example.com/llms.txt");
+        let kinds: Vec<LlmsTxtIssueKind> = analysis.issues.iter().map(|i| i.kind).collect();
+        assert!(kinds.contains(&LlmsTxtIssueKind::MissingTitle));
+        assert!(kinds.contains(&LlmsTxtIssueKind::NoSections));
+    }
+
+    #[test]
+    fn test_link_with_parentheses_in_url() {
+        let content = "# Wiki\n\n> Summary\n\n## Docs\n\n\
+            - [Rust](https://en.wikipedia.org/wiki/Rust_(programming_language)): The (other) Rust\n";
+        let analysis = parse_llms_txt(content, "https://example.com/llms.txt");
+
+        let link = &analysis.sections[0].links[0];
+        assert_eq!(
+            link.url,
+            "https://en.wikipedia.org/wiki/Rust_(programming_language)"
+        );
+        assert_eq!(link.description.as_deref(), Some("The (other) Rust"));
+    }
+
+    #[test]
+    fn test_record_link_status_and_full() {
+        let mut analysis = parse_llms_txt(LLMS_TXT, "https://example.com/llms.txt");
+        analysis.record_link_status("https://example.com/docs/sizing.md", Some(200));
+        analysis.record_link_status("https://example.com/api.md", Some(404));
+
+        assert_eq!(analysis.sections[0].links[0].status_code, Some(200));
+        assert!(
+            analysis
+                .issues
+                .iter()
+                .any(|i| i.kind == LlmsTxtIssueKind::BrokenLink
+                    && i.message == "Linked URL https://example.com/api.md returned HTTP 404")
+        );
+
+        let full = analyze_llms_full_txt(
+            "# Example Shop\n\nFull docs...",
+            "https://example.com/llms-full.txt",
+        );
+        assert_eq!(full.title.as_deref(), Some("Example Shop"));
+        analysis.add_full(full);
+        assert!(analysis.full.is_some());
+    }
+}
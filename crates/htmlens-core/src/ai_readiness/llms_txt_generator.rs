@@ -0,0 +1,337 @@
+//! llms.txt draft generation
+//!
+//! Drafts an `/llms.txt` for sites that lack one from the pages listed in the
+//! sitemap. Each page's JSON-LD `@type`s (falling back to the sitemap's URL
+//! classification) decide its section, and its title and meta description
+//! become the link text and notes. `llms-full.txt` inlines every page as
+//! markdown. The output is meant to be reviewed before publishing.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as FmtWrite;
+
+use super::{sitemap::categorize_url, structured_data::analyze_structured_data};
+use crate::parser::{extract_page_metadata, html_to_markdown};
+
+/// Sections in output order: (content type, H2 heading)
+///
+/// `info` pages (about, contact, privacy) go to the "Optional" section, which
+/// LLMs may skip when context is short.
+const SECTIONS: &[(&str, &str)] = &[
+    ("documentation", "Docs"),
+    ("product", "Products"),
+    ("article", "Articles"),
+    ("blog", "Blog"),
+    ("faq", "FAQ"),
+    ("video", "Videos"),
+    ("image", "Images"),
+    ("page", "Pages"),
+    ("info", "Optional"),
+];
+
+/// Schema.org types that decide a page's content type, checked in order
+const TYPE_CONTENT_TYPES: &[(&str, &str)] = &[
+    ("Product", "product"),
+    ("ProductGroup", "product"),
+    ("FAQPage", "faq"),
+    ("QAPage", "faq"),
+    ("HowTo", "documentation"),
+    ("TechArticle", "documentation"),
+    ("BlogPosting", "blog"),
+    ("NewsArticle", "article"),
+    ("Article", "article"),
+    ("VideoObject", "video"),
+    ("AboutPage", "info"),
+    ("ContactPage", "info"),
+];
+
+/// A page to list in the generated llms.txt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmsTxtPage {
+    /// Page URL
+    pub url: String,
+
+    /// `<title>` of the page
+    pub title: Option<String>,
+
+    /// Meta description of the page
+    pub description: Option<String>,
+
+    /// Schema.org `@type`s declared in the page's JSON-LD, microdata and RDFa
+    pub types: Vec<String>,
+
+    /// Content type used to pick the section ("product", "blog", ...)
+    pub content_type: String,
+
+    /// Page content as markdown (only needed for llms-full.txt)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+}
+
+impl LlmsTxtPage {
+    /// Describe a page from its HTML
+    pub fn from_html(url: &str, html: &str, include_markdown: bool) -> Self {
+        let head = PageHead::parse(html);
+        let types = analyze_structured_data(html, url).types;
+        let content_type = TYPE_CONTENT_TYPES
+            .iter()
+            .find(|(schema_type, _)| types.iter().any(|t| t == schema_type))
+            .map(|(_, content_type)| content_type.to_string())
+            .unwrap_or_else(|| categorize_url(url));
+
+        Self {
+            url: url.to_string(),
+            title: head.title,
+            description: head.description,
+            types,
+            content_type,
+            markdown: include_markdown.then(|| html_to_markdown(html).trim().to_string()),
+        }
+    }
+
+    /// Link text: the page title, or the last path segment of the URL
+    fn link_title(&self) -> String {
+        self.title.clone().unwrap_or_else(|| {
+            self.url
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or(&self.url)
+                .to_string()
+        })
+    }
+}
+
+/// Site name and summary for the llms.txt header, taken from the home page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LlmsTxtSite {
+    /// H1 title
+    pub name: String,
+
+    /// Blockquote summary
+    pub summary: Option<String>,
+}
+
+impl LlmsTxtSite {
+    /// Read the site name (`og:site_name`, else `<title>`) and summary (meta
+    /// description) from the home page, falling back to `fallback_name`
+    pub fn from_html(html: &str, fallback_name: &str) -> Self {
+        let head = PageHead::parse(html);
+        Self {
+            name: head
+                .site_name
+                .or(head.title)
+                .unwrap_or_else(|| fallback_name.to_string()),
+            summary: head.description,
+        }
+    }
+}
+
+/// Generated llms.txt (and optionally llms-full.txt) for a site
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmsTxtDraft {
+    /// Generated llms.txt
+    pub llms_txt: String,
+
+    /// Generated llms-full.txt (None unless requested)
+    pub llms_full_txt: Option<String>,
+
+    /// Pages that were listed
+    pub pages: Vec<LlmsTxtPage>,
+}
+
+/// Generate llms.txt with one H2 section per content type
+pub fn generate_llms_txt(site: &LlmsTxtSite, pages: &[LlmsTxtPage]) -> String {
+    let mut out = header(site);
+
+    for (content_type, heading) in SECTIONS {
+        let section_pages: Vec<&LlmsTxtPage> = pages
+            .iter()
+            .filter(|page| page.content_type == *content_type)
+            .collect();
+        if section_pages.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(out, "## {}\n", heading);
+        for page in section_pages {
+            let link = format!(
+                "[{}]({})",
+                inline_text(&page.link_title()),
+                link_destination(&page.url)
+            );
+            match page.description.as_deref() {
+                Some(description) => {
+                    let _ = writeln!(out, "- {}: {}", link, inline_text(description));
+                }
+                None => {
+                    let _ = writeln!(out, "- {}", link);
+                }
+            }
+        }
+        let _ = writeln!(out);
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// Generate llms-full.txt with the markdown content of every page
+pub fn generate_llms_full_txt(site: &LlmsTxtSite, pages: &[LlmsTxtPage]) -> String {
+    let mut out = header(site);
+
+    for page in pages {
+        let _ = writeln!(out, "## {}\n", collapse_whitespace(&page.link_title()));
+        let _ = writeln!(out, "Source: {}\n", page.url);
+        if let Some(markdown) = page.markdown.as_deref()
+            && !markdown.is_empty()
+        {
+            let _ = writeln!(out, "{}\n", markdown);
+        }
+        let _ = writeln!(out, "---\n");
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+fn header(site: &LlmsTxtSite) -> String {
+    let mut out = format!("# {}\n\n", collapse_whitespace(&site.name));
+    if let Some(summary) = site.summary.as_deref() {
+        let _ = writeln!(out, "> {}\n", inline_text(summary));
+    }
+    out
+}
+
+/// Page text on one line, with the characters of markdown link syntax escaped
+fn inline_text(text: &str) -> String {
+    let mut out = String::new();
+    for c in collapse_whitespace(text).chars() {
+        if matches!(c, '\\' | '[' | ']' | '(' | ')') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Titles and descriptions may span lines in the HTML
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// URL with parentheses percent-encoded, so an unbalanced one cannot end the link
+fn link_destination(url: &str) -> String {
+    url.replace('(', "%28").replace(')', "%29")
+}
+
+/// Title, description and site name from `<head>`; empty values count as missing
+struct PageHead {
+    title: Option<String>,
+    description: Option<String>,
+    site_name: Option<String>,
+}
+
+impl PageHead {
+    fn parse(html: &str) -> Self {
+        let metadata = extract_page_metadata(html, "");
+        let non_empty = |value: Option<String>| value.filter(|value| !value.is_empty());
+
+        Self {
+            title: non_empty(metadata.title),
+            description: non_empty(metadata.description)
+                .or_else(|| non_empty(metadata.open_graph.description)),
+            site_name: non_empty(metadata.open_graph.site_name),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+
+    fn page(url: &str, title: &str, types: &str) -> LlmsTxtPage {
+        let html = format!(
+            r#"<html><head><title>{title}</title>
+<meta name="description" content="About {title}">
+<script type="application/ld+json">{{"@type": "{types}"}}</script>
+</head><body><h1>{title}</h1></body></html>"#
+        );
+        LlmsTxtPage::from_html(url, &html, true)
+    }
+
+    #[test]
+    fn test_pages_are_grouped_by_type() {
+        let pages = vec![
+            // JSON-LD type wins over the URL classification
+            page("https://example.com/shop/bike", "Bike", "Product"),
+            page("https://example.com/help/sizing", "Sizing", "HowTo"),
+            page("https://example.com/about", "About us", "Organization"),
+            LlmsTxtPage::from_html("https://example.com/blog/launch/", "<p>Hi</p>", false),
+        ];
+        assert_eq!(pages[1].content_type, "documentation");
+        assert_eq!(pages[2].content_type, "info");
+
+        let site = LlmsTxtSite {
+            name: "Example".to_string(),
+            summary: Some("Bikes".to_string()),
+        };
+        let llms_txt = generate_llms_txt(&site, &pages);
+
+        assert!(llms_txt.starts_with("# Example\n\n> Bikes\n\n## Docs\n"));
+        assert!(
+            llms_txt
+                .contains("## Products\n\n- [Bike](https://example.com/shop/bike): About Bike\n")
+        );
+        assert!(llms_txt.contains("## Blog\n\n- [launch](https://example.com/blog/launch/)\n"));
+        assert!(
+            llms_txt.ends_with(
+                "## Optional\n\n- [About us](https://example.com/about): About About us\n"
+            )
+        );
+
+        // The draft is valid llms.txt
+        let analysis =
+            super::super::llms_txt::parse_llms_txt(&llms_txt, "https://example.com/llms.txt");
+        assert!(analysis.issues.is_empty(), "{:?}", analysis.issues);
+        assert_eq!(analysis.link_count(), 4);
+
+        // Link syntax and line breaks in page text survive the round trip
+        let mut tricky = page("https://example.com/shop/bike-(2024", "x", "Product");
+        tricky.title = Some("Bike [2024]\n  (red)".to_string());
+        tricky.description = Some("Frame: size](S)\nfits [most] riders".to_string());
+        let site = LlmsTxtSite {
+            name: "Example\nShop".to_string(),
+            summary: Some("Bikes (and\nparts)".to_string()),
+        };
+        let llms_txt = generate_llms_txt(&site, &[tricky]);
+        let analysis =
+            super::super::llms_txt::parse_llms_txt(&llms_txt, "https://example.com/llms.txt");
+        assert!(analysis.issues.is_empty(), "{:?}", analysis.issues);
+        assert_eq!(analysis.title.as_deref(), Some("Example Shop"));
+        assert_eq!(analysis.summary.as_deref(), Some("Bikes (and parts)"));
+        let link = &analysis.sections[0].links[0];
+        assert_eq!(link.title, "Bike [2024] (red)");
+        assert_eq!(
+            link.description.as_deref(),
+            Some("Frame: size](S) fits [most] riders")
+        );
+        assert_eq!(link.url, "https://example.com/shop/bike-%282024");
+    }
+
+    #[test]
+    fn test_site_header_and_full_txt() {
+        let html = r#"<html><head><title>Home | Example</title>
+<meta property="og:site_name" content="Example Shop">
+<meta name="description" content="Bikes and parts"></head></html>"#;
+        let site = LlmsTxtSite::from_html(html, "example.com");
+        assert_eq!(site.name, "Example Shop");
+        assert_eq!(site.summary.as_deref(), Some("Bikes and parts"));
+        assert_eq!(
+            LlmsTxtSite::from_html("", "example.com").name,
+            "example.com"
+        );
+
+        let pages = vec![page("https://example.com/shop/bike", "Bike", "Product")];
+        let full = generate_llms_full_txt(&site, &pages);
+        assert!(full.contains("## Bike\n\nSource: https://example.com/shop/bike\n\nBike\n"));
+        assert!(full.trim_end().ends_with("---"));
+    }
+}
@@ -3,16 +3,39 @@
 //! This module provides tools to check how well a website communicates
 //! with AI agents through standard specifications and files.
 
+pub mod access_matrix;
+pub mod crawlers;
+pub mod fetch;
+pub mod llms_txt;
+pub mod llms_txt_generator;
 pub mod mcp_manifest;
 pub mod openapi;
 pub mod plugin_manifest;
 pub mod robots_txt;
+pub mod scoring;
 pub mod semantic_html;
 pub mod sitemap;
+pub mod structured_data;
 pub mod well_known;
 
+mod checker;
+
+#[cfg(feature = "ai-readiness")]
+pub use checker::check_site;
+pub use checker::{check_crawler_access, check_site_with, draft_llms_txt, sitemap_page_urls};
+
 use serde::{Deserialize, Serialize};
 
+use llms_txt::{LlmsTxtAnalysis, LlmsTxtIssueKind};
+use mcp_manifest::McpManifestValidation;
+use openapi::OpenApiValidation;
+use plugin_manifest::ManifestValidation;
+use robots_txt::{AccessLevel, RobotsTxtAnalysis, RobotsTxtIssueKind};
+use scoring::{ScoreBreakdown, ScoringWeights};
+use semantic_html::SemanticHtmlAnalysis;
+use sitemap::SitemapAnalysis;
+use structured_data::StructuredDataAnalysis;
+
 /// Overall AI readiness assessment for a website
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiReadinessReport {
@@ -22,9 +45,36 @@ pub struct AiReadinessReport {
     /// Overall readiness score (0-100)
     pub score: u8,
 
+    /// Per-category scores behind the overall score
+    pub breakdown: ScoreBreakdown,
+
     /// Results from checking .well-known directory
     pub well_known: well_known::WellKnownChecks,
 
+    /// robots.txt analysis (None if not found)
+    pub robots_txt: Option<RobotsTxtAnalysis>,
+
+    /// XML sitemap analysis (None if not found)
+    pub sitemap: Option<SitemapAnalysis>,
+
+    /// llms.txt analysis (None if not found)
+    pub llms_txt: Option<LlmsTxtAnalysis>,
+
+    /// AI plugin manifest validation (None if not found)
+    pub ai_plugin: Option<ManifestValidation>,
+
+    /// MCP manifest validation (None if not found or unparseable)
+    pub mcp: Option<McpManifestValidation>,
+
+    /// OpenAPI validation for the spec referenced by the AI plugin manifest
+    pub openapi: Option<OpenApiValidation>,
+
+    /// Semantic HTML analysis of the checked page
+    pub semantic_html: Option<SemanticHtmlAnalysis>,
+
+    /// JSON-LD structured data found on the checked page
+    pub structured_data: Option<StructuredDataAnalysis>,
+
     /// Summary of what's working well
     pub strengths: Vec<String>,
 
@@ -42,7 +92,7 @@ pub struct AiReadinessIssue {
     pub severity: IssueSeverity,
 
     /// Category of the issue
-    pub category: String,
+    pub category: IssueCategory,
 
     /// Description of the issue
     pub message: String,
@@ -67,34 +117,609 @@ pub enum IssueSeverity {
     Low,
 }
 
+/// Category of an AI readiness issue, named after the check that raised it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCategory {
+    /// .well-known directory files
+    WellKnown,
+
+    /// robots.txt and AI crawler access
+    RobotsTxt,
+
+    /// XML sitemap
+    Sitemap,
+
+    /// llms.txt and llms-full.txt
+    LlmsTxt,
+
+    /// AI plugin manifest (ai-plugin.json)
+    AiPlugin,
+
+    /// Model Context Protocol manifest (mcp.json)
+    Mcp,
+
+    /// OpenAPI specification
+    OpenApi,
+
+    /// Semantic HTML structure
+    SemanticHtml,
+
+    /// JSON-LD structured data
+    StructuredData,
+}
+
+impl IssueCategory {
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueCategory::WellKnown => ".well-known",
+            IssueCategory::RobotsTxt => "robots.txt",
+            IssueCategory::Sitemap => "Sitemap",
+            IssueCategory::LlmsTxt => "llms.txt",
+            IssueCategory::AiPlugin => "AI Plugin",
+            IssueCategory::Mcp => "MCP",
+            IssueCategory::OpenApi => "OpenAPI",
+            IssueCategory::SemanticHtml => "Semantic HTML",
+            IssueCategory::StructuredData => "Structured Data",
+        }
+    }
+
+    /// Link to the specification or documentation for this category
+    pub fn reference(&self) -> &'static str {
+        match self {
+            IssueCategory::WellKnown => "https://www.rfc-editor.org/rfc/rfc8615",
+            IssueCategory::RobotsTxt => "https://www.rfc-editor.org/rfc/rfc9309",
+            IssueCategory::Sitemap => "https://www.sitemaps.org/protocol.html",
+            IssueCategory::LlmsTxt => "https://llmstxt.org/",
+            IssueCategory::AiPlugin => "https://platform.openai.com/docs/gpts/actions",
+            IssueCategory::Mcp => "https://modelcontextprotocol.io/",
+            IssueCategory::OpenApi => "https://spec.openapis.org/oas/latest.html",
+            IssueCategory::SemanticHtml => {
+                "https://developer.mozilla.org/en-US/docs/Web/HTML/Element#content_sectioning"
+            }
+            IssueCategory::StructuredData => "https://schema.org/docs/gs.html",
+        }
+    }
+}
+
+impl std::fmt::Display for IssueCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 impl AiReadinessReport {
     /// Create a new AI readiness report
     pub fn new(url: String) -> Self {
         Self {
             url,
             score: 0,
+            breakdown: ScoreBreakdown::default(),
             well_known: well_known::WellKnownChecks::default(),
+            robots_txt: None,
+            sitemap: None,
+            llms_txt: None,
+            ai_plugin: None,
+            mcp: None,
+            openapi: None,
+            semantic_html: None,
+            structured_data: None,
             strengths: Vec::new(),
             issues: Vec::new(),
             recommendations: Vec::new(),
         }
     }
 
-    /// Calculate the overall score based on checks
-    pub fn calculate_score(&mut self) {
-        let mut score = 100u32;
-
-        // Deduct points based on issue severity
-        for issue in &self.issues {
-            let deduction = match issue.severity {
-                IssueSeverity::Critical => 20,
-                IssueSeverity::High => 10,
-                IssueSeverity::Medium => 5,
-                IssueSeverity::Low => 2,
+    /// Record an issue, linking it to the category's reference documentation
+    pub fn add_issue(
+        &mut self,
+        severity: IssueSeverity,
+        category: IssueCategory,
+        message: impl Into<String>,
+    ) {
+        self.issues.push(AiReadinessIssue {
+            severity,
+            category,
+            message: message.into(),
+            reference: Some(category.reference().to_string()),
+        });
+    }
+
+    /// Record the .well-known checks, validating the AI plugin and MCP manifests
+    /// from the retrieved file contents
+    pub fn add_well_known(&mut self, checks: well_known::WellKnownChecks) {
+        let ai_plugin = &checks.ai_plugin;
+        match ai_plugin.content.as_deref() {
+            Some(content) if ai_plugin.valid => {
+                self.add_ai_plugin(plugin_manifest::validate_manifest(content));
+            }
+            _ if ai_plugin.found => self.add_issue(
+                IssueSeverity::Medium,
+                IssueCategory::AiPlugin,
+                "ai-plugin.json is served but is not valid JSON",
+            ),
+            _ => self.add_issue(
+                IssueSeverity::Low,
+                IssueCategory::AiPlugin,
+                "No AI plugin manifest at /.well-known/ai-plugin.json",
+            ),
+        }
+
+        let mcp = &checks.mcp;
+        match mcp.content.as_deref() {
+            Some(content) if mcp.valid => match mcp_manifest::validate_manifest(content) {
+                Ok(validation) => self.add_mcp(validation),
+                Err(e) => self.add_issue(
+                    IssueSeverity::High,
+                    IssueCategory::Mcp,
+                    format!("mcp.json could not be parsed: {}", e),
+                ),
+            },
+            _ if mcp.found => self.add_issue(
+                IssueSeverity::Medium,
+                IssueCategory::Mcp,
+                "mcp.json is served but is not valid JSON",
+            ),
+            _ => {
+                self.add_issue(
+                    IssueSeverity::Low,
+                    IssueCategory::Mcp,
+                    "No MCP manifest at /.well-known/mcp.json",
+                );
+                self.recommendations.push(
+                    "Publish /.well-known/mcp.json to let AI agents discover your MCP server"
+                        .to_string(),
+                );
+            }
+        }
+
+        if checks.security_txt.found && checks.security_txt.valid {
+            self.strengths
+                .push("security.txt provides a security contact".to_string());
+        }
+
+        self.well_known = checks;
+    }
+
+    fn add_ai_plugin(&mut self, validation: ManifestValidation) {
+        if validation.valid {
+            self.strengths
+                .push("AI plugin manifest is valid".to_string());
+        }
+        for issue in &validation.issues {
+            self.add_issue(
+                IssueSeverity::Medium,
+                IssueCategory::AiPlugin,
+                format!("{}: {}", issue.field, issue.message),
+            );
+        }
+        for warning in &validation.warnings {
+            self.add_issue(IssueSeverity::Low, IssueCategory::AiPlugin, warning.clone());
+        }
+        self.ai_plugin = Some(validation);
+    }
+
+    fn add_mcp(&mut self, validation: McpManifestValidation) {
+        if validation.valid {
+            self.strengths.push(format!(
+                "MCP manifest is valid ({} tools, {} resources, {} prompts)",
+                validation.tool_count, validation.resource_count, validation.prompt_count
+            ));
+        }
+        for issue in &validation.issues {
+            self.add_issue(IssueSeverity::Medium, IssueCategory::Mcp, issue.clone());
+        }
+        self.mcp = Some(validation);
+    }
+
+    /// URL of the OpenAPI spec referenced by a valid AI plugin manifest
+    pub fn openapi_url(&self) -> Option<&str> {
+        self.ai_plugin
+            .as_ref()
+            .and_then(|validation| validation.manifest.as_ref())
+            .map(|manifest| manifest.api.url.as_str())
+    }
+
+    /// Record the robots.txt analysis (`None` if robots.txt was not found)
+    pub fn add_robots_txt(&mut self, analysis: Option<RobotsTxtAnalysis>) {
+        let Some(analysis) = analysis else {
+            self.add_issue(
+                IssueSeverity::Medium,
+                IssueCategory::RobotsTxt,
+                "robots.txt not found",
+            );
+            self.recommendations.push(
+                "Publish a robots.txt that states which AI crawlers may access the site"
+                    .to_string(),
+            );
+            return;
+        };
+
+        self.strengths.push("robots.txt is published".to_string());
+
+        let total = analysis.ai_crawler_status.len();
+        let blocked: Vec<String> = analysis
+            .ai_crawler_status
+            .iter()
+            .filter(|status| status.access == AccessLevel::Blocked)
+            .map(|status| {
+                format!(
+                    "{} ({} {} crawler)",
+                    status.name, status.vendor, status.purpose
+                )
+            })
+            .collect();
+        if total > blocked.len() {
+            self.strengths.push(format!(
+                "{} of {} known AI crawlers can access the site",
+                total - blocked.len(),
+                total
+            ));
+        }
+        if let Some(summary) = analysis.purpose_summary() {
+            self.strengths.push(summary);
+        }
+        for name in blocked {
+            self.add_issue(
+                IssueSeverity::High,
+                IssueCategory::RobotsTxt,
+                format!("{} is blocked from accessing the site", name),
+            );
+        }
+
+        for issue in &analysis.issues {
+            let severity = match issue.kind {
+                RobotsTxtIssueKind::AllBotsBlocked => IssueSeverity::Critical,
+                RobotsTxtIssueKind::NoSitemap => IssueSeverity::Low,
+            };
+            self.add_issue(severity, IssueCategory::RobotsTxt, issue.message.clone());
+        }
+
+        self.robots_txt = Some(analysis);
+    }
+
+    /// Record the sitemap analysis (`None` if no sitemap was found)
+    pub fn add_sitemap(&mut self, analysis: Option<SitemapAnalysis>) {
+        let Some(analysis) = analysis else {
+            self.add_issue(
+                IssueSeverity::Medium,
+                IssueCategory::Sitemap,
+                "No sitemap found",
+            );
+            self.recommendations.push(
+                "Create a sitemap.xml file to help crawlers discover your content".to_string(),
+            );
+            return;
+        };
+
+        self.strengths
+            .push(format!("XML sitemap found ({} URLs)", analysis.url_count));
+        for issue in &analysis.issues {
+            self.add_issue(IssueSeverity::Low, IssueCategory::Sitemap, issue.clone());
+        }
+        self.recommendations
+            .extend(analysis.recommendations.iter().cloned());
+
+        self.sitemap = Some(analysis);
+    }
+
+    /// Record the llms.txt analysis (`None` if llms.txt was not found)
+    pub fn add_llms_txt(&mut self, analysis: Option<LlmsTxtAnalysis>) {
+        let Some(analysis) = analysis else {
+            self.add_issue(
+                IssueSeverity::Medium,
+                IssueCategory::LlmsTxt,
+                "No llms.txt found at /llms.txt",
+            );
+            self.recommendations
+                .push("Publish /llms.txt to point LLMs at your most useful content".to_string());
+            return;
+        };
+
+        self.strengths.push(format!(
+            "llms.txt found ({} sections, {} links)",
+            analysis.sections.len(),
+            analysis.link_count()
+        ));
+        if let Some(full) = analysis.full.as_ref() {
+            self.strengths.push(format!(
+                "llms-full.txt found ({} KB)",
+                full.size_bytes.div_ceil(1024)
+            ));
+        }
+        for issue in &analysis.issues {
+            let severity = match issue.kind {
+                LlmsTxtIssueKind::MissingTitle
+                | LlmsTxtIssueKind::BrokenLink
+                | LlmsTxtIssueKind::UnreachableLink => IssueSeverity::Medium,
+                LlmsTxtIssueKind::MultipleTitles
+                | LlmsTxtIssueKind::ContentBeforeTitle
+                | LlmsTxtIssueKind::MissingSummary
+                | LlmsTxtIssueKind::NoSections
+                | LlmsTxtIssueKind::EmptySection
+                | LlmsTxtIssueKind::InvalidLink
+                | LlmsTxtIssueKind::OffOriginLinks
+                | LlmsTxtIssueKind::EmptyFull => IssueSeverity::Low,
+            };
+            self.add_issue(severity, IssueCategory::LlmsTxt, issue.message.clone());
+        }
+
+        self.llms_txt = Some(analysis);
+    }
+
+    /// Record the OpenAPI validation for the spec referenced by the AI plugin
+    pub fn add_openapi(&mut self, validation: OpenApiValidation) {
+        if validation.valid {
+            self.strengths.push(format!(
+                "OpenAPI specification is valid ({} operations)",
+                validation.stats.total_operations
+            ));
+        }
+        for issue in &validation.issues {
+            self.add_issue(IssueSeverity::Medium, IssueCategory::OpenApi, issue.clone());
+        }
+        for warning in &validation.warnings {
+            self.add_issue(IssueSeverity::Low, IssueCategory::OpenApi, warning.clone());
+        }
+        self.openapi = Some(validation);
+    }
+
+    /// Record the structured data analysis of the checked page
+    pub fn add_structured_data(&mut self, analysis: StructuredDataAnalysis) {
+        let valid_blocks = analysis.valid_blocks();
+        if valid_blocks > 0 {
+            let mut syntaxes = Vec::new();
+            if analysis.json_ld_blocks > analysis.invalid_blocks {
+                syntaxes.push("JSON-LD");
+            }
+            if analysis.microdata_items > 0 {
+                syntaxes.push("microdata");
+            }
+            if analysis.rdfa_resources > 0 {
+                syntaxes.push("RDFa");
+            }
+            self.strengths.push(format!(
+                "{} structured data block(s) in {} describe the page ({})",
+                valid_blocks,
+                syntaxes.join(", "),
+                if analysis.types.is_empty() {
+                    "no @type declared".to_string()
+                } else {
+                    analysis.types.join(", ")
+                }
+            ));
+        }
+        if analysis.json_ld_blocks == 0 {
+            // Microdata and RDFa carry the same facts, so a page using them
+            // only gets a note rather than an issue
+            self.recommendations.push(if valid_blocks > 0 {
+                "Consider also publishing the page's Schema.org data as JSON-LD, the syntax most AI agents read first".to_string()
+            } else {
+                "Add Schema.org JSON-LD so AI agents can read key facts about the page".to_string()
+            });
+        }
+        for issue in &analysis.issues {
+            let severity = if valid_blocks == 0 && analysis.invalid_blocks == 0 {
+                IssueSeverity::High
+            } else {
+                IssueSeverity::Medium
             };
-            score = score.saturating_sub(deduction);
+            self.add_issue(severity, IssueCategory::StructuredData, issue.clone());
+        }
+
+        self.structured_data = Some(analysis);
+    }
+
+    /// Record the semantic HTML analysis of the checked page
+    pub fn add_semantic_html(&mut self, analysis: SemanticHtmlAnalysis) {
+        if analysis.landmarks.has_main {
+            self.strengths
+                .push("Page marks its primary content with a <main> landmark".to_string());
+        }
+        if analysis.headings.has_single_h1 && analysis.headings.proper_hierarchy {
+            self.strengths
+                .push("Heading structure is clear and sequential".to_string());
+        }
+        for issue in &analysis.issues {
+            self.add_issue(
+                IssueSeverity::Medium,
+                IssueCategory::SemanticHtml,
+                issue.clone(),
+            );
         }
+        self.recommendations
+            .extend(analysis.recommendations.iter().cloned());
+
+        self.semantic_html = Some(analysis);
+    }
+
+    /// Calculate the overall score based on checks
+    ///
+    /// Uses the default category weights; see [`Self::calculate_score_with`].
+    pub fn calculate_score(&mut self) {
+        self.calculate_score_with(&ScoringWeights::default());
+    }
+
+    /// Calculate the overall score and per-category breakdown with custom weights
+    pub fn calculate_score_with(&mut self, weights: &ScoringWeights) {
+        self.breakdown = scoring::score_issues(&self.issues, weights);
+        self.score = self.breakdown.overall;
+    }
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_microdata_only_page_gets_a_note_not_an_issue() {
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_structured_data(structured_data::analyze_structured_data(
+            r#"<div itemscope itemtype="https://schema.org/Product"><span itemprop="name">Bike</span></div>"#,
+            "https://example.com/",
+        ));
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|issue| issue.category == IssueCategory::StructuredData)
+        );
+        assert!(report.strengths.iter().any(|s| s.contains("microdata")));
+        assert!(report.recommendations.iter().any(|r| r.contains("JSON-LD")));
+
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_structured_data(structured_data::analyze_structured_data(
+            "<p>Hi</p>",
+            "https://example.com/",
+        ));
+        assert_eq!(report.issues[0].severity, IssueSeverity::High);
+    }
+
+    #[test]
+    fn test_missing_resources_become_issues() {
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_well_known(well_known::WellKnownChecks::default());
+        report.add_robots_txt(None);
+        report.add_sitemap(None);
+        report.calculate_score();
+
+        assert!(report.robots_txt.is_none());
+        assert!(report.sitemap.is_none());
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|i| i.category == IssueCategory::RobotsTxt
+                    && i.severity == IssueSeverity::Medium)
+        );
+        assert!(
+            report
+                .issues
+                .iter()
+                .all(|i| i.reference.as_deref() == Some(i.category.reference()))
+        );
+        assert!(report.score < 100);
+    }
+
+    #[test]
+    fn test_blocked_crawlers_are_reported() {
+        let robots = robots_txt::parse_robots_txt(
+            "User-agent: GPTBot\nDisallow: /\n\nSitemap: https://example.com/sitemap.xml\n",
+        );
+
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_robots_txt(Some(robots));
+
+        assert!(report.robots_txt.is_some());
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|i| i.category == IssueCategory::RobotsTxt
+                    && i.severity == IssueSeverity::High
+                    && i.message.contains("GPTBot"))
+        );
+        assert!(report.strengths.iter().any(|s| s.contains("robots.txt")));
+    }
+
+    #[test]
+    fn test_well_known_manifests_are_validated() {
+        let checks = well_known::WellKnownChecks {
+            mcp: well_known::FileCheck::from_response(
+                "/.well-known/mcp.json",
+                200,
+                Some(r#"{"name": "incomplete"}"#.to_string()),
+            ),
+            ai_plugin: well_known::FileCheck::from_response(
+                "/.well-known/ai-plugin.json",
+                404,
+                None,
+            ),
+            ..Default::default()
+        };
+
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_well_known(checks);
+
+        assert!(report.mcp.is_none());
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|i| i.category == IssueCategory::Mcp && i.severity == IssueSeverity::High)
+        );
+        assert!(report.ai_plugin.is_none());
+        assert!(report.openapi_url().is_none());
+        assert_eq!(report.well_known.ai_plugin.status_code, Some(404));
+    }
+
+    #[test]
+    fn test_openapi_and_semantic_html_results_are_stored() {
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+
+        let mut validation = openapi::OpenApiValidation::new();
+        validation.add_issue("No operations defined in the API".to_string());
+        report.add_openapi(validation);
+        report.add_semantic_html(semantic_html::analyze_semantic_html(
+            "<html><body><main><h1>Title</h1></main></body></html>",
+        ));
+
+        assert!(report.openapi.is_some());
+        assert!(report.semantic_html.is_some());
+        assert!(
+            report
+                .issues
+                .iter()
+                .any(|i| i.category == IssueCategory::OpenApi)
+        );
+        assert!(report.strengths.iter().any(|s| s.contains("<main>")));
+    }
+
+    #[test]
+    fn test_llms_txt_counts_towards_discovery() {
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_llms_txt(None);
+        report.calculate_score();
+
+        let discovery = report
+            .breakdown
+            .categories
+            .iter()
+            .find(|c| c.category == scoring::ScoreCategory::Discovery)
+            .unwrap();
+        assert_eq!(discovery.issue_count, 1);
+
+        let analysis = llms_txt::parse_llms_txt(
+            "# Example\n\n> Summary\n\n## Docs\n\n- [Guide](/guide.md)\n",
+            "https://example.com/llms.txt",
+        );
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_llms_txt(Some(analysis));
+
+        assert!(report.issues.is_empty());
+        assert!(report.strengths.iter().any(|s| s.contains("1 links")));
+        assert!(report.llms_txt.is_some());
+    }
+
+    #[test]
+    fn test_broken_and_unreachable_links_weigh_the_same() {
+        let mut analysis = llms_txt::parse_llms_txt(
+            "# Example\n\n> Summary\n\n## Docs\n\n- [A](/a.md)\n- [B](/b.md)\n",
+            "https://example.com/llms.txt",
+        );
+        analysis.record_link_status("https://example.com/a.md", Some(404));
+        analysis.record_link_status("https://example.com/b.md", None);
+
+        let mut report = AiReadinessReport::new("https://example.com".to_string());
+        report.add_llms_txt(Some(analysis));
 
-        self.score = score.min(100) as u8;
+        assert_eq!(report.issues.len(), 2);
+        assert!(
+            report
+                .issues
+                .iter()
+                .all(|i| i.severity == IssueSeverity::Medium)
+        );
     }
 }
@@ -2,16 +2,11 @@
 //!
 //! Validates OpenAPI/Swagger specifications for AI agent consumption.
 
-#[cfg(feature = "ai-readiness")]
 use anyhow::{Context, Result};
 use openapiv3::OpenAPI;
 use serde::{Deserialize, Serialize};
-#[cfg(feature = "ai-readiness")]
-use std::time::Duration;
 
-/// HTTP request timeout for fetching OpenAPI specs
-#[cfg(feature = "ai-readiness")]
-const FETCH_TIMEOUT_SECS: u64 = 15;
+use super::fetch::Fetcher;
 
 /// Expected OpenAPI major version
 const EXPECTED_OPENAPI_VERSION_PREFIX: &str = "3.";
@@ -214,34 +209,27 @@ pub fn validate_openapi(content: &str, is_yaml: bool) -> OpenApiValidation {
 }
 
 /// Fetch and validate OpenAPI specification from a URL
-#[cfg(feature = "ai-readiness")]
-pub async fn fetch_and_validate_openapi(url: &str) -> Result<OpenApiValidation> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
-        .user_agent("htmlens-ai-readiness-checker/0.4.2")
-        .build()?;
-
-    let response = client
-        .get(url)
-        .send()
+pub async fn fetch_and_validate_openapi<F: Fetcher>(
+    fetcher: &F,
+    url: &str,
+) -> Result<OpenApiValidation> {
+    let response = fetcher
+        .get(url, true)
         .await
         .context("Failed to fetch OpenAPI spec")?;
 
-    if !response.status().is_success() {
-        anyhow::bail!("Failed to fetch OpenAPI spec: HTTP {}", response.status());
+    if !response.is_success() {
+        anyhow::bail!("Failed to fetch OpenAPI spec: HTTP {}", response.status);
     }
 
-    let content = response.text().await?;
-
     // Determine if it's YAML or JSON based on URL extension or content structure
-    let is_yaml = is_yaml_format(url, &content);
+    let is_yaml = is_yaml_format(url, &response.body);
 
-    Ok(validate_openapi(&content, is_yaml))
+    Ok(validate_openapi(&response.body, is_yaml))
 }
 
 /// Determine if content is YAML or JSON format
-#[cfg(feature = "ai-readiness")]
-fn is_yaml_format(url: &str, content: &str) -> bool {
+pub fn is_yaml_format(url: &str, content: &str) -> bool {
     // Check URL extension first
     if url.ends_with(".yaml") || url.ends_with(".yml") {
         return true;
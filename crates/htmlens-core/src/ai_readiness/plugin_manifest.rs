@@ -3,11 +3,12 @@
 //! Validates ChatGPT plugin manifests according to the specification.
 //! See: https://platform.openai.com/docs/gpts/actions
 
-#[cfg(feature = "ai-readiness")]
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use super::fetch::Fetcher;
+
 /// AI Plugin Manifest structure (schema version v1)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiPluginManifest {
@@ -238,26 +239,22 @@ pub fn validate_manifest(json_content: &str) -> ManifestValidation {
 }
 
 /// Fetch and validate AI plugin manifest from a URL
-#[cfg(feature = "ai-readiness")]
-pub async fn fetch_and_validate_manifest(base_url: &str) -> Result<ManifestValidation> {
+pub async fn fetch_and_validate_manifest<F: Fetcher>(
+    fetcher: &F,
+    base_url: &str,
+) -> Result<ManifestValidation> {
     let url = format!(
         "{}/.well-known/ai-plugin.json",
         base_url.trim_end_matches('/')
     );
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .user_agent("htmlens-ai-readiness-checker/0.4.2")
-        .build()?;
-
-    let response = client.get(&url).send().await?;
+    let response = fetcher.get(&url, true).await?;
 
-    if !response.status().is_success() {
-        anyhow::bail!("Failed to fetch manifest: HTTP {}", response.status());
+    if !response.is_success() {
+        anyhow::bail!("Failed to fetch manifest: HTTP {}", response.status);
     }
 
-    let content = response.text().await?;
-    Ok(validate_manifest(&content))
+    Ok(validate_manifest(&response.body))
 }
 
 #[cfg(all(test, feature = "ai-readiness"))]
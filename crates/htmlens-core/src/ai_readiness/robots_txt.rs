@@ -2,23 +2,18 @@
 //!
 //! Parses robots.txt files and analyzes crawling rules for different user agents,
 //! with special focus on AI crawlers (GPTBot, ClaudeBot, etc.)
+//!
+//! Grouping and matching follow RFC 9309: consecutive `User-agent` lines share
+//! a group, groups for the same agent are merged, crawlers match groups by
+//! product token, and the longest matching `Allow`/`Disallow` pattern wins
+//! (with `Allow` winning ties). Patterns support the `*` and `$` wildcards,
+//! and paths are percent-encoding normalized before comparison.
+//! See: https://www.rfc-editor.org/rfc/rfc9309
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// Known AI crawler user agents
-pub const AI_CRAWLERS: &[&str] = &[
-    "GPTBot",          // OpenAI ChatGPT
-    "ChatGPT-User",    // OpenAI ChatGPT browsing
-    "ClaudeBot",       // Anthropic Claude
-    "Claude-Web",      // Anthropic Claude web
-    "Google-Extended", // Google Bard/Gemini
-    "Bingbot",         // Microsoft Bing
-    "Applebot",        // Apple Siri
-    "Anthropic-AI",    // Anthropic general
-    "PerplexityBot",   // Perplexity AI
-    "YouBot",          // You.com AI
-];
+use super::crawlers::{CrawlerPurpose, CrawlerRegistry};
 
 /// Results from parsing robots.txt
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,14 +31,87 @@ pub struct RobotsTxtAnalysis {
     /// Sitemap URLs found
     pub sitemaps: Vec<String>,
 
-    /// Rules per user agent
+    /// Groups in file order, each with the user agents it applies to
+    pub groups: Vec<RobotsGroup>,
+
+    /// Rules per user agent (groups for the same agent merged)
     pub agent_rules: HashMap<String, AgentRules>,
 
     /// AI crawler analysis
     pub ai_crawler_status: Vec<AiCrawlerStatus>,
 
     /// Parsing issues
-    pub issues: Vec<String>,
+    pub issues: Vec<RobotsTxtIssue>,
+}
+
+/// A problem found in robots.txt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RobotsTxtIssue {
+    /// What is wrong, for callers that weigh issues
+    pub kind: RobotsTxtIssueKind,
+
+    /// Description of the issue
+    pub message: String,
+}
+
+/// Kind of a [`RobotsTxtIssue`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RobotsTxtIssueKind {
+    /// No `Sitemap` line
+    NoSitemap,
+
+    /// The `*` group disallows the whole site
+    AllBotsBlocked,
+}
+
+impl RobotsTxtIssue {
+    fn new(kind: RobotsTxtIssueKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for RobotsTxtIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// A group of rules applying to one or more user agents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RobotsGroup {
+    /// User agents named by the group's `User-agent` lines
+    pub user_agents: Vec<String>,
+
+    /// `Allow` and `Disallow` rules, in file order
+    pub rules: Vec<RobotsRule>,
+
+    /// Crawl delay in seconds (non-standard extension)
+    pub crawl_delay: Option<u32>,
+}
+
+/// A single `Allow` or `Disallow` rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RobotsRule {
+    /// Whether the rule allows or disallows matching paths
+    pub kind: RuleKind,
+
+    /// Path pattern (may contain `*` and a trailing `$`)
+    pub pattern: String,
+
+    /// 1-based line number in robots.txt
+    pub line: usize,
+}
+
+/// Kind of robots.txt rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleKind {
+    Allow,
+    Disallow,
 }
 
 /// Rules for a specific user agent
@@ -71,6 +139,12 @@ pub struct AiCrawlerStatus {
     /// Crawler name
     pub name: String,
 
+    /// Company or project operating the crawler
+    pub vendor: String,
+
+    /// What the crawler fetches pages for
+    pub purpose: CrawlerPurpose,
+
     /// Access level
     pub access: AccessLevel,
 
@@ -95,6 +169,20 @@ pub enum AccessLevel {
     Default,
 }
 
+/// Outcome of evaluating one path for one user agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathDecision {
+    /// Whether the path may be crawled
+    pub allowed: bool,
+
+    /// User agent of the group that was applied (`*` for the wildcard group),
+    /// or `None` if no group applies
+    pub group: Option<String>,
+
+    /// The rule that decided the outcome, or `None` if no rule matched
+    pub rule: Option<RobotsRule>,
+}
+
 impl Default for RobotsTxtAnalysis {
     fn default() -> Self {
         Self {
@@ -102,6 +190,7 @@ impl Default for RobotsTxtAnalysis {
             status_code: 404,
             content: None,
             sitemaps: Vec::new(),
+            groups: Vec::new(),
             agent_rules: HashMap::new(),
             ai_crawler_status: Vec::new(),
             issues: Vec::new(),
@@ -109,8 +198,49 @@ impl Default for RobotsTxtAnalysis {
     }
 }
 
-/// Parse robots.txt content
+impl RobotsTxtAnalysis {
+    /// Summarize the policy by crawler purpose, e.g. "Allows search/retrieval
+    /// crawlers but blocks training crawlers"
+    ///
+    /// Returns `None` unless at least one purpose is fully blocked and another
+    /// is fully open.
+    pub fn purpose_summary(&self) -> Option<String> {
+        let mut allowed = Vec::new();
+        let mut blocked = Vec::new();
+        for purpose in CrawlerPurpose::ALL {
+            let statuses: Vec<&AiCrawlerStatus> = self
+                .ai_crawler_status
+                .iter()
+                .filter(|status| status.purpose == purpose)
+                .collect();
+            if statuses.is_empty() {
+                continue;
+            }
+            if statuses.iter().all(|s| s.access == AccessLevel::Blocked) {
+                blocked.push(purpose.label());
+            } else if statuses.iter().all(|s| s.access != AccessLevel::Blocked) {
+                allowed.push(purpose.label());
+            }
+        }
+
+        if allowed.is_empty() || blocked.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Allows {} crawlers but blocks {} crawlers",
+            allowed.join(" and "),
+            blocked.join(" and ")
+        ))
+    }
+}
+
+/// Parse robots.txt content, analyzing access for the built-in AI crawlers
 pub fn parse_robots_txt(content: &str) -> RobotsTxtAnalysis {
+    parse_robots_txt_with(content, &CrawlerRegistry::default())
+}
+
+/// Parse robots.txt content, analyzing access for the crawlers in `registry`
+pub fn parse_robots_txt_with(content: &str, registry: &CrawlerRegistry) -> RobotsTxtAnalysis {
     let mut analysis = RobotsTxtAnalysis {
         found: true,
         status_code: 200,
@@ -118,75 +248,66 @@ pub fn parse_robots_txt(content: &str) -> RobotsTxtAnalysis {
         ..Default::default()
     };
 
-    let mut current_agents: Vec<String> = Vec::new();
-    let mut current_rules = AgentRules {
-        user_agent: String::new(),
-        disallow: Vec::new(),
-        allow: Vec::new(),
-        crawl_delay: None,
-        blocks_all: false,
-    };
-
-    for line in content.lines() {
-        let line = line.trim();
+    let mut current: Option<RobotsGroup> = None;
+    // Whether the current group has seen a rule; a user-agent line after a
+    // rule starts a new group, consecutive user-agent lines share one
+    let mut in_rules = false;
 
-        // Skip comments and empty lines
-        if line.is_empty() || line.starts_with('#') {
+    for (index, line) in content.lines().enumerate() {
+        // Strip comments and surrounding whitespace
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
             continue;
         }
 
         // Split on first colon
-        let parts: Vec<&str> = line.splitn(2, ':').collect();
-        if parts.len() != 2 {
+        let Some((directive, value)) = line.split_once(':') else {
             continue;
-        }
-
-        let directive = parts[0].trim().to_lowercase();
-        let value = parts[1].trim();
+        };
+        let directive = directive.trim().to_lowercase();
+        let value = value.trim();
 
         match directive.as_str() {
             "user-agent" => {
-                // Save previous agent rules if any
-                if !current_agents.is_empty() {
-                    for agent in &current_agents {
-                        analysis
-                            .agent_rules
-                            .insert(agent.clone(), current_rules.clone());
+                if in_rules || current.is_none() {
+                    if let Some(group) = current.take() {
+                        analysis.groups.push(group);
                     }
+                    current = Some(RobotsGroup::default());
+                    in_rules = false;
                 }
-
-                // Start new agent
-                current_agents = vec![value.to_string()];
-                current_rules = AgentRules {
-                    user_agent: value.to_string(),
-                    disallow: Vec::new(),
-                    allow: Vec::new(),
-                    crawl_delay: None,
-                    blocks_all: false,
-                };
-            }
-            "disallow" => {
-                if !value.is_empty() {
-                    current_rules.disallow.push(value.to_string());
-                    if value == "/" {
-                        current_rules.blocks_all = true;
-                    }
+                if let Some(group) = current.as_mut() {
+                    group.user_agents.push(value.to_string());
                 }
             }
-            "allow" => {
-                if !value.is_empty() {
-                    current_rules.allow.push(value.to_string());
+            "allow" | "disallow" => {
+                // Rules outside of a group are ignored
+                if let Some(group) = current.as_mut() {
+                    in_rules = true;
+                    // An empty pattern matches nothing
+                    if !value.is_empty() {
+                        group.rules.push(RobotsRule {
+                            kind: if directive == "allow" {
+                                RuleKind::Allow
+                            } else {
+                                RuleKind::Disallow
+                            },
+                            pattern: value.to_string(),
+                            line: index + 1,
+                        });
+                    }
                 }
             }
             "crawl-delay" => {
-                if let Ok(delay) = value.parse::<u32>() {
-                    current_rules.crawl_delay = Some(delay);
+                if let Some(group) = current.as_mut() {
+                    in_rules = true;
+                    if let Ok(delay) = value.parse::<u32>() {
+                        group.crawl_delay = Some(delay);
+                    }
                 }
             }
-            "sitemap" => {
-                if !value.is_empty() {
-                    analysis.sitemaps.push(value.to_string());
-                }
+            "sitemap" if !value.is_empty() => {
+                analysis.sitemaps.push(value.to_string());
             }
             _ => {
                 // Unknown directive - could add to issues
@@ -194,136 +315,337 @@ pub fn parse_robots_txt(content: &str) -> RobotsTxtAnalysis {
         }
     }
 
-    // Save last agent rules
-    if !current_agents.is_empty() {
-        for agent in &current_agents {
-            analysis
-                .agent_rules
-                .insert(agent.clone(), current_rules.clone());
-        }
+    if let Some(group) = current.take() {
+        analysis.groups.push(group);
     }
 
+    analysis.agent_rules = build_agent_rules(&analysis.groups);
+
     // Analyze AI crawlers
-    analysis.ai_crawler_status = analyze_ai_crawlers(&analysis.agent_rules);
+    analysis.ai_crawler_status = analyze_ai_crawlers(&analysis, registry);
 
     // Validate and add issues
     if analysis.sitemaps.is_empty() {
-        analysis.issues.push("No sitemap URLs found".to_string());
+        analysis.issues.push(RobotsTxtIssue::new(
+            RobotsTxtIssueKind::NoSitemap,
+            "No sitemap URLs found",
+        ));
     }
 
     if let Some(wildcard) = analysis.agent_rules.get("*")
         && wildcard.blocks_all
     {
-        analysis
-            .issues
-            .push("Warning: All bots blocked with 'Disallow: /'".to_string());
+        analysis.issues.push(RobotsTxtIssue::new(
+            RobotsTxtIssueKind::AllBotsBlocked,
+            "Warning: All bots blocked with 'Disallow: /'",
+        ));
     }
 
     analysis
 }
 
-/// Analyze access for known AI crawlers
-fn analyze_ai_crawlers(agent_rules: &HashMap<String, AgentRules>) -> Vec<AiCrawlerStatus> {
-    let mut statuses = Vec::new();
+/// Build the per-agent view of the groups, merging groups for the same agent
+fn build_agent_rules(groups: &[RobotsGroup]) -> HashMap<String, AgentRules> {
+    let mut agent_rules: HashMap<String, AgentRules> = HashMap::new();
 
-    for crawler in AI_CRAWLERS {
-        let access = determine_access(crawler, agent_rules);
-        let applicable_rules = get_applicable_rules(crawler, agent_rules);
+    for group in groups {
+        for agent in &group.user_agents {
+            let rules = agent_rules
+                .entry(agent.clone())
+                .or_insert_with(|| AgentRules {
+                    user_agent: agent.clone(),
+                    disallow: Vec::new(),
+                    allow: Vec::new(),
+                    crawl_delay: None,
+                    blocks_all: false,
+                });
+            for rule in &group.rules {
+                match rule.kind {
+                    RuleKind::Allow => rules.allow.push(rule.pattern.clone()),
+                    RuleKind::Disallow => rules.disallow.push(rule.pattern.clone()),
+                }
+            }
+            if group.crawl_delay.is_some() {
+                rules.crawl_delay = group.crawl_delay;
+            }
+        }
+    }
 
-        statuses.push(AiCrawlerStatus {
-            name: crawler.to_string(),
-            access,
-            applicable_rules,
-        });
+    // Any non-empty Allow rule lets some path through, however specific
+    for rules in agent_rules.values_mut() {
+        rules.blocks_all = rules
+            .disallow
+            .iter()
+            .any(|p| matches_everything(&normalize_path(p)))
+            && rules.allow.iter().all(|p| p.trim().is_empty());
     }
 
-    statuses
+    agent_rules
 }
 
-/// Determine access level for a specific crawler
-fn determine_access(crawler: &str, agent_rules: &HashMap<String, AgentRules>) -> AccessLevel {
-    // Check for exact match
-    if let Some(rules) = agent_rules.get(crawler) {
-        if rules.blocks_all {
-            return AccessLevel::Blocked;
+/// Extract the product token from a user agent string (e.g. `GPTBot/1.0` -> `gptbot`)
+pub fn product_token(user_agent: &str) -> String {
+    user_agent
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Groups that apply to a user agent: all groups naming its product token,
+/// or else all wildcard groups. Returns the matched group name and the groups.
+fn applicable_groups<'a>(
+    agent: &str,
+    analysis: &'a RobotsTxtAnalysis,
+) -> Option<(String, Vec<&'a RobotsGroup>)> {
+    let token = product_token(agent);
+
+    if !token.is_empty() {
+        let mut name = None;
+        let specific: Vec<&RobotsGroup> = analysis
+            .groups
+            .iter()
+            .filter(|group| {
+                group.user_agents.iter().any(|ua| {
+                    let matched = product_token(ua) == token;
+                    if matched && name.is_none() {
+                        name = Some(ua.clone());
+                    }
+                    matched
+                })
+            })
+            .collect();
+        if let Some(name) = name {
+            return Some((name, specific));
         }
-        if !rules.disallow.is_empty() {
-            return AccessLevel::Partial;
+    }
+
+    let wildcard: Vec<&RobotsGroup> = analysis
+        .groups
+        .iter()
+        .filter(|group| group.user_agents.iter().any(|ua| ua.trim() == "*"))
+        .collect();
+    if wildcard.is_empty() {
+        None
+    } else {
+        Some(("*".to_string(), wildcard))
+    }
+}
+
+/// Evaluate whether `path` may be crawled by `agent`, returning the deciding rule
+pub fn evaluate_path(path: &str, agent: &str, analysis: &RobotsTxtAnalysis) -> PathDecision {
+    let path = normalize_path(path);
+
+    let Some((group_name, groups)) = applicable_groups(agent, analysis) else {
+        return PathDecision {
+            allowed: true,
+            group: None,
+            rule: None,
+        };
+    };
+
+    // The robots.txt file itself is always allowed
+    if path == "/robots.txt" {
+        return PathDecision {
+            allowed: true,
+            group: Some(group_name),
+            rule: None,
+        };
+    }
+
+    let mut best: Option<(usize, &RobotsRule)> = None;
+    for rule in groups.iter().flat_map(|group| group.rules.iter()) {
+        let pattern = normalize_path(&rule.pattern);
+        if !pattern_matches(&pattern, &path) {
+            continue;
+        }
+        let specificity = pattern.len();
+        let better = match best {
+            None => true,
+            Some((best_len, best_rule)) => {
+                specificity > best_len
+                    || (specificity == best_len
+                        && rule.kind == RuleKind::Allow
+                        && best_rule.kind == RuleKind::Disallow)
+            }
+        };
+        if better {
+            best = Some((specificity, rule));
         }
-        return AccessLevel::Allowed;
     }
 
-    // Check for case-insensitive match
-    let crawler_lower = crawler.to_lowercase();
-    for (agent, rules) in agent_rules {
-        if agent.to_lowercase() == crawler_lower {
-            if rules.blocks_all {
-                return AccessLevel::Blocked;
+    PathDecision {
+        allowed: best.is_none_or(|(_, rule)| rule.kind == RuleKind::Allow),
+        group: Some(group_name),
+        rule: best.map(|(_, rule)| rule.clone()),
+    }
+}
+
+/// Match a normalized pattern against a normalized path
+///
+/// `*` matches any sequence of characters and a trailing `$` anchors the
+/// pattern to the end of the path; otherwise patterns match as prefixes.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+
+    let pattern = pattern.as_bytes();
+    let path = path.as_bytes();
+
+    // Iterative wildcard matching with backtracking to the last `*`
+    let (mut p, mut s) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    loop {
+        if p == pattern.len() {
+            if !anchored || s == path.len() {
+                return true;
             }
-            if !rules.disallow.is_empty() {
-                return AccessLevel::Partial;
+        } else if pattern[p] == b'*' {
+            star = Some((p, s));
+            p += 1;
+            continue;
+        } else if s < path.len() && pattern[p] == path[s] {
+            p += 1;
+            s += 1;
+            continue;
+        }
+
+        match star {
+            Some((star_p, star_s)) if star_s < path.len() => {
+                p = star_p + 1;
+                s = star_s + 1;
+                star = Some((star_p, star_s + 1));
             }
-            return AccessLevel::Allowed;
+            _ => return false,
         }
     }
+}
 
-    // Fall back to wildcard rules
-    if let Some(rules) = agent_rules.get("*") {
-        if rules.blocks_all {
-            return AccessLevel::Blocked;
+/// Whether a pattern matches every path (`/`, `/*`, `*`)
+fn matches_everything(pattern: &str) -> bool {
+    let rest = pattern.strip_prefix('/').unwrap_or(pattern);
+    (pattern.starts_with('/') || pattern.starts_with('*')) && rest.chars().all(|c| c == '*')
+}
+
+/// Normalize percent-encoding for comparison (RFC 9309 section 2.2.2)
+///
+/// Percent-encoded unreserved characters are decoded, other percent-encodings
+/// are upper-cased, and non-ASCII or control octets are percent-encoded.
+fn normalize_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut normalized = String::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if byte == b'%'
+            && i + 2 < bytes.len()
+            && let (Some(high), Some(low)) = (hex_value(bytes[i + 1]), hex_value(bytes[i + 2]))
+        {
+            let decoded = high * 16 + low;
+            if decoded.is_ascii_alphanumeric() || matches!(decoded, b'-' | b'.' | b'_' | b'~') {
+                normalized.push(decoded as char);
+            } else {
+                normalized.push_str(&format!("%{:02X}", decoded));
+            }
+            i += 3;
+            continue;
         }
-        if !rules.disallow.is_empty() {
-            return AccessLevel::Partial;
+
+        if byte >= 0x80 || byte.is_ascii_control() || byte == b' ' {
+            normalized.push_str(&format!("%{:02X}", byte));
+        } else {
+            normalized.push(byte as char);
         }
+        i += 1;
     }
 
-    AccessLevel::Default
+    normalized
 }
 
-/// Get description of applicable rules
-fn get_applicable_rules(
-    crawler: &str,
-    agent_rules: &HashMap<String, AgentRules>,
-) -> Option<String> {
-    // Check for exact match
-    if let Some(rules) = agent_rules.get(crawler) {
-        return Some(format_rules(crawler, rules));
-    }
-
-    // Check for case-insensitive match
-    let crawler_lower = crawler.to_lowercase();
-    for (agent, rules) in agent_rules {
-        if agent.to_lowercase() == crawler_lower {
-            return Some(format_rules(agent, rules));
-        }
+fn hex_value(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Analyze access for known AI crawlers
+fn analyze_ai_crawlers(
+    analysis: &RobotsTxtAnalysis,
+    registry: &CrawlerRegistry,
+) -> Vec<AiCrawlerStatus> {
+    registry
+        .crawlers()
+        .iter()
+        .map(|crawler| AiCrawlerStatus {
+            name: crawler.user_agent.clone(),
+            vendor: crawler.vendor.clone(),
+            purpose: crawler.purpose,
+            access: determine_access(&crawler.user_agent, analysis),
+            applicable_rules: get_applicable_rules(&crawler.user_agent, analysis),
+        })
+        .collect()
+}
+
+/// Determine access level for a specific crawler by evaluating its rules
+fn determine_access(crawler: &str, analysis: &RobotsTxtAnalysis) -> AccessLevel {
+    let Some((group_name, groups)) = applicable_groups(crawler, analysis) else {
+        return AccessLevel::Default;
+    };
+    let is_wildcard = group_name == "*";
+    let rules: Vec<&RobotsRule> = groups.iter().flat_map(|g| g.rules.iter()).collect();
+
+    let has_disallow = rules.iter().any(|r| r.kind == RuleKind::Disallow);
+    let root_allowed = evaluate_path("/", crawler, analysis).allowed;
+    let everything_disallowed = rules
+        .iter()
+        .any(|r| r.kind == RuleKind::Disallow && matches_everything(&normalize_path(&r.pattern)));
+
+    if !root_allowed && everything_disallowed {
+        let has_allow = rules.iter().any(|r| r.kind == RuleKind::Allow);
+        return if has_allow {
+            AccessLevel::Partial
+        } else {
+            AccessLevel::Blocked
+        };
     }
 
-    // Fall back to wildcard
-    if let Some(rules) = agent_rules.get("*") {
-        return Some(format_rules("* (all bots)", rules));
+    if has_disallow {
+        AccessLevel::Partial
+    } else if is_wildcard {
+        AccessLevel::Default
+    } else {
+        AccessLevel::Allowed
     }
+}
 
-    None
+/// Get description of applicable rules
+fn get_applicable_rules(crawler: &str, analysis: &RobotsTxtAnalysis) -> Option<String> {
+    let (group_name, _) = applicable_groups(crawler, analysis)?;
+    let rules = analysis.agent_rules.get(&group_name)?;
+
+    if group_name == "*" {
+        Some(format_rules("* (all bots)", rules))
+    } else {
+        Some(format_rules(&group_name, rules))
+    }
 }
 
 /// Format rules into human-readable string
 fn format_rules(agent: &str, rules: &AgentRules) -> String {
     let mut parts = vec![format!("User-agent: {}", agent)];
 
-    if rules.blocks_all {
-        parts.push("Disallow: / (FULL BLOCK)".to_string());
-    } else {
-        if !rules.disallow.is_empty() {
-            for path in &rules.disallow {
-                parts.push(format!("Disallow: {}", path));
-            }
-        }
-        if !rules.allow.is_empty() {
-            for path in &rules.allow {
-                parts.push(format!("Allow: {}", path));
-            }
+    for path in &rules.disallow {
+        if rules.blocks_all && matches_everything(&normalize_path(path)) {
+            parts.push(format!("Disallow: {} (FULL BLOCK)", path));
+        } else {
+            parts.push(format!("Disallow: {}", path));
         }
     }
+    for path in &rules.allow {
+        parts.push(format!("Allow: {}", path));
+    }
 
     if let Some(delay) = rules.crawl_delay {
         parts.push(format!("Crawl-delay: {}s", delay));
@@ -334,31 +656,7 @@ fn format_rules(agent: &str, rules: &AgentRules) -> String {
 
 /// Check if a specific path is allowed for a user agent
 pub fn is_path_allowed(path: &str, agent: &str, analysis: &RobotsTxtAnalysis) -> bool {
-    let rules = analysis
-        .agent_rules
-        .get(agent)
-        .or_else(|| analysis.agent_rules.get("*"));
-
-    if let Some(rules) = rules {
-        // Check allow rules first (they take precedence)
-        for allow_path in &rules.allow {
-            if path.starts_with(allow_path) {
-                return true;
-            }
-        }
-
-        // Check disallow rules
-        for disallow_path in &rules.disallow {
-            if disallow_path == "/" {
-                return false; // Blocked entirely
-            }
-            if path.starts_with(disallow_path) {
-                return false;
-            }
-        }
-    }
-
-    true // Default is allowed
+    evaluate_path(path, agent, analysis).allowed
 }
 
 #[cfg(all(test, feature = "ai-readiness"))]
@@ -385,6 +683,40 @@ Sitemap: https://example.com/sitemap.xml
         assert!(!wildcard.blocks_all);
     }
 
+    #[test]
+    fn test_root_disallow_with_allow_is_not_a_full_block() {
+        let content = r#"
+User-agent: *
+Disallow: /
+Allow: /public
+"#;
+
+        let analysis = parse_robots_txt(content);
+        let wildcard = &analysis.agent_rules["*"];
+        assert!(!wildcard.blocks_all);
+        assert!(
+            !analysis
+                .issues
+                .iter()
+                .any(|issue| issue.kind == RobotsTxtIssueKind::AllBotsBlocked)
+        );
+        assert_eq!(
+            format_rules("*", wildcard),
+            "User-agent: *; Disallow: /; Allow: /public"
+        );
+        assert!(is_path_allowed("/public/page", "GPTBot", &analysis));
+        assert!(!is_path_allowed("/private", "GPTBot", &analysis));
+
+        let analysis = parse_robots_txt("User-agent: *\nDisallow: /\n");
+        assert!(analysis.agent_rules["*"].blocks_all);
+        assert!(
+            analysis
+                .issues
+                .iter()
+                .any(|issue| issue.kind == RobotsTxtIssueKind::AllBotsBlocked)
+        );
+    }
+
     #[test]
     fn test_parse_ai_crawler_block() {
         let content = r#"
@@ -432,4 +764,226 @@ Sitemap: https://example.com/sitemap-videos.xml
         let analysis = parse_robots_txt(content);
         assert_eq!(analysis.sitemaps.len(), 3);
     }
+
+    #[test]
+    fn test_consecutive_user_agents_share_group() {
+        let content = r#"
+User-agent: GPTBot
+User-agent: ClaudeBot
+Disallow: /
+
+User-agent: *
+Allow: /
+"#;
+
+        let analysis = parse_robots_txt(content);
+        assert_eq!(analysis.groups.len(), 2);
+        assert_eq!(analysis.groups[0].user_agents, vec!["GPTBot", "ClaudeBot"]);
+
+        for name in ["GPTBot", "ClaudeBot"] {
+            let status = analysis
+                .ai_crawler_status
+                .iter()
+                .find(|s| s.name == name)
+                .unwrap();
+            assert_eq!(status.access, AccessLevel::Blocked, "{name}");
+        }
+        assert!(is_path_allowed("/page", "PerplexityBot", &analysis));
+    }
+
+    #[test]
+    fn test_groups_for_same_agent_are_merged() {
+        let content = r#"
+User-agent: GPTBot
+Disallow: /private/
+
+User-agent: *
+Disallow: /tmp/
+
+User-agent: gptbot
+Disallow: /drafts/
+"#;
+
+        let analysis = parse_robots_txt(content);
+        assert!(!is_path_allowed("/private/a", "GPTBot", &analysis));
+        assert!(!is_path_allowed("/drafts/a", "GPTBot", &analysis));
+        // The wildcard group does not apply once a specific group matches
+        assert!(is_path_allowed("/tmp/a", "GPTBot", &analysis));
+    }
+
+    #[test]
+    fn test_longest_match_wins() {
+        let content = r#"
+User-agent: *
+Allow: /p
+Disallow: /
+Disallow: /page
+Allow: /page.html
+"#;
+
+        let analysis = parse_robots_txt(content);
+        assert!(is_path_allowed("/page.html", "*", &analysis));
+        assert!(!is_path_allowed("/pages", "*", &analysis));
+        assert!(is_path_allowed("/pa", "*", &analysis));
+        assert!(!is_path_allowed("/other", "*", &analysis));
+
+        let decision = evaluate_path("/pages", "SomeBot", &analysis);
+        assert_eq!(decision.group.as_deref(), Some("*"));
+        assert_eq!(decision.rule.unwrap().pattern, "/page");
+    }
+
+    #[test]
+    fn test_allow_wins_equal_length_tie() {
+        let content = "User-agent: *\nDisallow: /folder\nAllow: /folder\n";
+        let analysis = parse_robots_txt(content);
+        assert!(is_path_allowed("/folder/page", "*", &analysis));
+    }
+
+    #[test]
+    fn test_wildcards() {
+        let content = r#"
+User-agent: *
+Disallow: /*.pdf$
+Disallow: /search*q=
+Disallow: /exact$
+"#;
+
+        let analysis = parse_robots_txt(content);
+        assert!(!is_path_allowed("/docs/report.pdf", "*", &analysis));
+        assert!(is_path_allowed(
+            "/docs/report.pdf?download=1",
+            "*",
+            &analysis
+        ));
+        assert!(!is_path_allowed("/search/results?q=ai", "*", &analysis));
+        assert!(is_path_allowed("/search", "*", &analysis));
+        assert!(!is_path_allowed("/exact", "*", &analysis));
+        assert!(is_path_allowed("/exact/more", "*", &analysis));
+    }
+
+    #[test]
+    fn test_percent_encoding_normalization() {
+        let content = r#"
+User-agent: *
+Disallow: /caf%C3%A9
+Disallow: /%7Euser/
+"#;
+
+        let analysis = parse_robots_txt(content);
+        assert!(!is_path_allowed("/café/menu", "*", &analysis));
+        assert!(!is_path_allowed("/caf%c3%a9/menu", "*", &analysis));
+        assert!(!is_path_allowed("/~user/profile", "*", &analysis));
+    }
+
+    #[test]
+    fn test_product_token_matching() {
+        let content = "User-agent: GPTBot\nDisallow: /\n";
+        let analysis = parse_robots_txt(content);
+
+        assert_eq!(product_token("GPTBot/1.1"), "gptbot");
+        assert!(!is_path_allowed("/", "GPTBot/1.1", &analysis));
+        assert!(!is_path_allowed("/", "gptbot", &analysis));
+        assert!(is_path_allowed("/", "GPTBot-Extended", &analysis));
+        assert!(is_path_allowed("/robots.txt", "GPTBot", &analysis));
+    }
+
+    #[test]
+    fn test_access_level_from_path_evaluation() {
+        let content = r#"
+User-agent: GPTBot
+Disallow: /
+Allow: /public/
+
+User-agent: ClaudeBot
+Disallow: /$
+
+User-agent: PerplexityBot
+Disallow:
+"#;
+
+        let analysis = parse_robots_txt(content);
+        let access = |name: &str| {
+            analysis
+                .ai_crawler_status
+                .iter()
+                .find(|s| s.name == name)
+                .unwrap()
+                .access
+                .clone()
+        };
+
+        assert_eq!(access("GPTBot"), AccessLevel::Partial);
+        assert_eq!(access("ClaudeBot"), AccessLevel::Partial);
+        assert_eq!(access("PerplexityBot"), AccessLevel::Allowed);
+        assert_eq!(access("Amazonbot"), AccessLevel::Default);
+    }
+
+    #[test]
+    fn test_comments_and_rules_before_group_are_ignored() {
+        let content = r#"
+Disallow: /orphan
+User-agent: * # all crawlers
+Disallow: /admin/ # back office
+"#;
+
+        let analysis = parse_robots_txt(content);
+        assert!(is_path_allowed("/orphan", "*", &analysis));
+        assert!(!is_path_allowed("/admin/x", "*", &analysis));
+        assert_eq!(analysis.agent_rules["*"].disallow, vec!["/admin/"]);
+    }
+
+    #[test]
+    fn test_crawler_status_carries_purpose() {
+        let content = r#"
+User-agent: GPTBot
+User-agent: CCBot
+User-agent: Google-Extended
+User-agent: ClaudeBot
+User-agent: Applebot-Extended
+User-agent: Meta-ExternalAgent
+Disallow: /
+"#;
+
+        let analysis = parse_robots_txt(content);
+        let gptbot = analysis
+            .ai_crawler_status
+            .iter()
+            .find(|s| s.name == "GPTBot")
+            .unwrap();
+        assert_eq!(gptbot.vendor, "OpenAI");
+        assert_eq!(gptbot.purpose, CrawlerPurpose::Training);
+        assert_eq!(gptbot.access, AccessLevel::Blocked);
+
+        assert_eq!(
+            analysis.purpose_summary().as_deref(),
+            Some(
+                "Allows search/retrieval and user-triggered fetch crawlers but blocks training crawlers"
+            )
+        );
+        assert!(
+            parse_robots_txt("User-agent: *\nAllow: /\n")
+                .purpose_summary()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_with_custom_registry() {
+        use crate::ai_readiness::crawlers::{CrawlerConfig, CrawlerInfo};
+
+        let registry = CrawlerRegistry::with_config(CrawlerConfig {
+            replace_builtin: true,
+            crawlers: vec![CrawlerInfo {
+                user_agent: "ExampleBot".to_string(),
+                vendor: "Example".to_string(),
+                purpose: CrawlerPurpose::Search,
+                docs_url: None,
+            }],
+        });
+
+        let analysis = parse_robots_txt_with("User-agent: ExampleBot\nDisallow: /\n", &registry);
+        assert_eq!(analysis.ai_crawler_status.len(), 1);
+        assert_eq!(analysis.ai_crawler_status[0].name, "ExampleBot");
+        assert_eq!(analysis.ai_crawler_status[0].access, AccessLevel::Blocked);
+    }
 }
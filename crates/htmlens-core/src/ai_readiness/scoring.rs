@@ -0,0 +1,294 @@
+//! Category-weighted AI readiness scoring
+//!
+//! Each issue is assigned to a [`ScoreCategory`]. Every category starts at 100
+//! and loses points per issue according to its severity, bottoming out at 0.
+//! The overall score is the weighted average of the category scores, so no
+//! single category can cost more than its weight - a pile of low-severity
+//! sitemap nits can never outweigh blocking every AI crawler.
+
+use serde::{Deserialize, Serialize};
+
+use super::{AiReadinessIssue, IssueCategory, IssueSeverity};
+
+/// Scoring category that groups related checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreCategory {
+    /// Sitemaps, llms.txt and .well-known discovery files
+    Discovery,
+
+    /// robots.txt rules for AI crawlers
+    CrawlerAccess,
+
+    /// JSON-LD / Schema.org data on the page
+    StructuredData,
+
+    /// AI plugin, MCP and OpenAPI manifests
+    AgentIntegration,
+
+    /// Semantic HTML structure and accessibility
+    SemanticHtml,
+}
+
+impl ScoreCategory {
+    /// All categories, in display order
+    pub const ALL: [ScoreCategory; 5] = [
+        ScoreCategory::Discovery,
+        ScoreCategory::CrawlerAccess,
+        ScoreCategory::StructuredData,
+        ScoreCategory::AgentIntegration,
+        ScoreCategory::SemanticHtml,
+    ];
+
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScoreCategory::Discovery => "Discovery",
+            ScoreCategory::CrawlerAccess => "Crawler Access",
+            ScoreCategory::StructuredData => "Structured Data",
+            ScoreCategory::AgentIntegration => "Agent Integration",
+            ScoreCategory::SemanticHtml => "Semantic HTML",
+        }
+    }
+}
+
+impl IssueCategory {
+    /// Scoring category this issue category counts towards
+    pub fn score_category(&self) -> ScoreCategory {
+        match self {
+            IssueCategory::WellKnown | IssueCategory::Sitemap | IssueCategory::LlmsTxt => {
+                ScoreCategory::Discovery
+            }
+            IssueCategory::RobotsTxt => ScoreCategory::CrawlerAccess,
+            IssueCategory::StructuredData => ScoreCategory::StructuredData,
+            IssueCategory::AiPlugin | IssueCategory::Mcp | IssueCategory::OpenApi => {
+                ScoreCategory::AgentIntegration
+            }
+            IssueCategory::SemanticHtml => ScoreCategory::SemanticHtml,
+        }
+    }
+}
+
+impl IssueSeverity {
+    /// Points deducted from a category score for one issue of this severity
+    pub fn penalty(&self) -> u32 {
+        match self {
+            IssueSeverity::Critical => 50,
+            IssueSeverity::High => 25,
+            IssueSeverity::Medium => 10,
+            IssueSeverity::Low => 3,
+        }
+    }
+}
+
+/// Relative weight of each category in the overall score
+///
+/// Weights are relative: they are normalized by their sum, so `{2, 1, ...}`
+/// and `{20, 10, ...}` produce the same score. Categories left out when
+/// deserializing keep their default weight.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringWeights {
+    pub discovery: u32,
+    pub crawler_access: u32,
+    pub structured_data: u32,
+    pub agent_integration: u32,
+    pub semantic_html: u32,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            discovery: 20,
+            crawler_access: 25,
+            structured_data: 20,
+            agent_integration: 20,
+            semantic_html: 15,
+        }
+    }
+}
+
+impl ScoringWeights {
+    /// Weight for a single category
+    pub fn weight(&self, category: ScoreCategory) -> u32 {
+        match category {
+            ScoreCategory::Discovery => self.discovery,
+            ScoreCategory::CrawlerAccess => self.crawler_access,
+            ScoreCategory::StructuredData => self.structured_data,
+            ScoreCategory::AgentIntegration => self.agent_integration,
+            ScoreCategory::SemanticHtml => self.semantic_html,
+        }
+    }
+
+    fn total(&self) -> u64 {
+        ScoreCategory::ALL
+            .iter()
+            .map(|c| u64::from(self.weight(*c)))
+            .sum()
+    }
+}
+
+/// Score for one category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryScore {
+    /// Category scored
+    pub category: ScoreCategory,
+
+    /// Category score (0-100)
+    pub score: u8,
+
+    /// Configured weight of the category
+    pub weight: u32,
+
+    /// Points this category contributes to the overall score
+    pub points: f32,
+
+    /// Maximum points this category can contribute
+    pub max_points: f32,
+
+    /// Number of issues counted against this category
+    pub issue_count: usize,
+}
+
+/// Per-category scores plus the weighted overall score
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// Overall score (0-100)
+    pub overall: u8,
+
+    /// Scores per category, in [`ScoreCategory::ALL`] order
+    pub categories: Vec<CategoryScore>,
+}
+
+/// Score a set of issues using the given category weights
+pub fn score_issues(issues: &[AiReadinessIssue], weights: &ScoringWeights) -> ScoreBreakdown {
+    let total_weight = weights.total();
+    let mut overall = 0.0;
+
+    let categories = ScoreCategory::ALL
+        .iter()
+        .map(|category| {
+            let category_issues: Vec<&AiReadinessIssue> = issues
+                .iter()
+                .filter(|issue| issue.category.score_category() == *category)
+                .collect();
+            let deduction: u32 = category_issues
+                .iter()
+                .map(|issue| issue.severity.penalty())
+                .sum();
+            let score = 100u32.saturating_sub(deduction) as u8;
+
+            let weight = weights.weight(*category);
+            let max_points = if total_weight > 0 {
+                weight as f32 * 100.0 / total_weight as f32
+            } else {
+                0.0
+            };
+            let points = max_points * score as f32 / 100.0;
+            overall += points;
+
+            CategoryScore {
+                category: *category,
+                score,
+                weight,
+                points,
+                max_points,
+                issue_count: category_issues.len(),
+            }
+        })
+        .collect();
+
+    ScoreBreakdown {
+        overall: overall.round().clamp(0.0, 100.0) as u8,
+        categories,
+    }
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+
+    fn issue(severity: IssueSeverity, category: IssueCategory) -> AiReadinessIssue {
+        AiReadinessIssue {
+            severity,
+            category,
+            message: String::new(),
+            reference: None,
+        }
+    }
+
+    #[test]
+    fn test_no_issues_scores_full_marks() {
+        let breakdown = score_issues(&[], &ScoringWeights::default());
+        assert_eq!(breakdown.overall, 100);
+        assert_eq!(breakdown.categories.len(), ScoreCategory::ALL.len());
+        assert!(breakdown.categories.iter().all(|c| c.score == 100));
+    }
+
+    #[test]
+    fn test_category_contribution_is_capped() {
+        let weights = ScoringWeights::default();
+
+        // Many low-severity sitemap nits only cost part of the discovery weight
+        let nits: Vec<_> = (0..15)
+            .map(|_| issue(IssueSeverity::Low, IssueCategory::Sitemap))
+            .collect();
+        let nits_score = score_issues(&nits, &weights).overall;
+
+        // Blocking all crawlers wipes out crawler access, and no more
+        let mut blocked = vec![issue(IssueSeverity::Critical, IssueCategory::RobotsTxt)];
+        blocked.extend((0..10).map(|_| issue(IssueSeverity::High, IssueCategory::RobotsTxt)));
+        let breakdown = score_issues(&blocked, &weights);
+
+        let crawler = breakdown
+            .categories
+            .iter()
+            .find(|c| c.category == ScoreCategory::CrawlerAccess)
+            .unwrap();
+        assert_eq!(crawler.score, 0);
+        assert_eq!(crawler.issue_count, 11);
+        assert_eq!(breakdown.overall, 75);
+        assert!(nits_score > breakdown.overall);
+    }
+
+    #[test]
+    fn test_custom_weights() {
+        let weights = ScoringWeights {
+            discovery: 0,
+            crawler_access: 1,
+            structured_data: 0,
+            agent_integration: 0,
+            semantic_html: 0,
+        };
+        let issues = [issue(IssueSeverity::Critical, IssueCategory::RobotsTxt)];
+        assert_eq!(score_issues(&issues, &weights).overall, 50);
+
+        // Issues in zero-weight categories do not affect the score
+        let issues = [issue(IssueSeverity::Critical, IssueCategory::Sitemap)];
+        assert_eq!(score_issues(&issues, &weights).overall, 100);
+    }
+
+    #[test]
+    fn test_huge_weights_do_not_overflow() {
+        let weights = ScoringWeights {
+            discovery: u32::MAX,
+            crawler_access: u32::MAX,
+            structured_data: u32::MAX,
+            agent_integration: u32::MAX,
+            semantic_html: u32::MAX,
+        };
+        let issues = [issue(IssueSeverity::Critical, IssueCategory::RobotsTxt)];
+        assert_eq!(score_issues(&issues, &weights).overall, 90);
+    }
+
+    #[test]
+    fn test_partial_weights_keep_defaults() {
+        let weights: ScoringWeights = serde_json::from_str(r#"{"discovery": 40}"#).unwrap();
+        assert_eq!(weights.discovery, 40);
+        assert_eq!(
+            weights.crawler_access,
+            ScoringWeights::default().crawler_access
+        );
+        assert!(serde_json::from_str::<ScoringWeights>(r#"{"discovry": 40}"#).is_err());
+    }
+}
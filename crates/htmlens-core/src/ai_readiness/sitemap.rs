@@ -80,6 +80,62 @@ pub fn parse_sitemap(content: &str, base_domain: &str) -> Result<SitemapAnalysis
     Ok(analysis)
 }
 
+impl SitemapAnalysis {
+    /// Fold the analyses of a sitemap index's nested sitemaps into this one
+    ///
+    /// URL counts, metadata statistics and content types are summed, URL entries
+    /// are concatenated, and issues/recommendations are recomputed for the totals.
+    pub fn merge_nested(&mut self, nested: Vec<SitemapAnalysis>) {
+        if nested.is_empty() {
+            return;
+        }
+
+        let mut total_urls = 0;
+        let mut urls_with_lastmod = 0;
+        let mut urls_with_priority = 0;
+        let mut priority_sum = 0.0;
+        let mut content_types: HashMap<String, usize> = HashMap::new();
+        let mut url_entries = Vec::new();
+        let mut nested_issues = Vec::new();
+
+        for analysis in nested {
+            total_urls += analysis.url_count;
+            urls_with_lastmod += analysis.statistics.urls_with_lastmod;
+            urls_with_priority += analysis.statistics.urls_with_priority;
+            priority_sum +=
+                analysis.statistics.avg_priority * analysis.statistics.urls_with_priority as f32;
+            for (content_type, count) in analysis.statistics.content_types {
+                *content_types.entry(content_type).or_insert(0) += count;
+            }
+            url_entries.extend(analysis.url_entries);
+            nested_issues.extend(analysis.issues);
+        }
+
+        self.url_count = total_urls;
+        self.statistics = SitemapStatistics {
+            total_urls,
+            urls_with_lastmod,
+            urls_with_priority,
+            avg_priority: if urls_with_priority > 0 {
+                priority_sum / urls_with_priority as f32
+            } else {
+                0.0
+            },
+            content_types,
+        };
+        self.url_entries = url_entries;
+
+        self.issues.clear();
+        self.recommendations.clear();
+        validate_sitemap(self, "");
+        for issue in nested_issues {
+            if !self.issues.contains(&issue) {
+                self.issues.push(issue);
+            }
+        }
+    }
+}
+
 fn parse_sitemap_index(content: &str, analysis: &mut SitemapAnalysis) -> Result<()> {
     // Extract sitemap locations from index - use DOTALL flag for multiline matching
     let sitemap_pattern = regex::Regex::new(r"(?s)<sitemap>.*?<loc>(.*?)</loc>.*?</sitemap>")
@@ -230,7 +286,9 @@ fn validate_sitemap(analysis: &mut SitemapAnalysis, _base_domain: &str) {
     }
 }
 
-fn categorize_url(url: &str) -> String {
+/// Classify a URL into a content type ("product", "blog", "documentation",
+/// ...) by its path
+pub fn categorize_url(url: &str) -> String {
     let url_lower = url.to_lowercase();
 
     // Common patterns for content types
@@ -366,6 +424,39 @@ mod tests {
         assert!(result.issues.iter().any(|i| i.contains("Invalid priority")));
     }
 
+    #[test]
+    fn test_merge_nested_sitemaps() {
+        let index = r#"<?xml version="1.0" encoding="UTF-8"?>
+<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/sitemap-products.xml</loc></sitemap>
+  <sitemap><loc>https://example.com/sitemap-blog.xml</loc></sitemap>
+</sitemapindex>"#;
+        let products = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/product/1</loc><lastmod>2024-01-01</lastmod><priority>0.8</priority></url>
+  <url><loc>https://example.com/product/2</loc><priority>0.4</priority></url>
+</urlset>"#;
+        let blog = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url><loc>https://example.com/blog/hello</loc><lastmod>2024-02-01</lastmod></url>
+</urlset>"#;
+
+        let mut analysis = parse_sitemap(index, "https://example.com").unwrap();
+        analysis.merge_nested(vec![
+            parse_sitemap(products, "https://example.com").unwrap(),
+            parse_sitemap(blog, "https://example.com").unwrap(),
+        ]);
+
+        assert_eq!(analysis.sitemap_type, SitemapType::Index);
+        assert_eq!(analysis.url_count, 3);
+        assert_eq!(analysis.statistics.total_urls, 3);
+        assert_eq!(analysis.statistics.urls_with_lastmod, 2);
+        assert_eq!(analysis.statistics.urls_with_priority, 2);
+        assert!((analysis.statistics.avg_priority - 0.6).abs() < 0.001);
+        assert_eq!(analysis.url_entries.len(), 3);
+        assert_eq!(analysis.statistics.content_types.get("product"), Some(&2));
+    }
+
     #[test]
     fn test_url_limit_warning() {
         let mut analysis = SitemapAnalysis {
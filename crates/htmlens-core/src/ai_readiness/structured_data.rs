@@ -0,0 +1,159 @@
+//! Structured data presence check
+//!
+//! Counts the JSON-LD blocks, microdata items and RDFa resources on a page and
+//! the Schema.org types they declare, so the readiness report can tell whether
+//! AI agents get machine-readable facts about the page.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::parser::{StructuredDataSource, extract_structured_data};
+
+/// Results from analyzing the structured data on a page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructuredDataAnalysis {
+    /// Number of JSON-LD script blocks found
+    pub json_ld_blocks: usize,
+
+    /// Number of JSON-LD blocks that are not valid JSON
+    pub invalid_blocks: usize,
+
+    /// Number of top-level microdata items
+    #[serde(default)]
+    pub microdata_items: usize,
+
+    /// Number of top-level RDFa resources
+    #[serde(default)]
+    pub rdfa_resources: usize,
+
+    /// Distinct `@type` values declared (sorted)
+    pub types: Vec<String>,
+
+    /// Issues found
+    pub issues: Vec<String>,
+}
+
+impl StructuredDataAnalysis {
+    /// Number of blocks that parsed, in any syntax
+    pub fn valid_blocks(&self) -> usize {
+        self.json_ld_blocks - self.invalid_blocks + self.microdata_items + self.rdfa_resources
+    }
+}
+
+/// Analyze the JSON-LD, microdata and RDFa structured data embedded in an
+/// HTML page; `base_url` resolves relative IRIs in microdata and RDFa
+pub fn analyze_structured_data(html: &str, base_url: &str) -> StructuredDataAnalysis {
+    let mut analysis = StructuredDataAnalysis::default();
+    let data = extract_structured_data(html, base_url).unwrap_or_default();
+
+    for block in &data.blocks {
+        match block.source {
+            StructuredDataSource::JsonLd => analysis.json_ld_blocks += 1,
+            StructuredDataSource::Microdata => analysis.microdata_items += 1,
+            StructuredDataSource::Rdfa => analysis.rdfa_resources += 1,
+        }
+        if let Ok(value) = serde_json::from_str::<JsonValue>(&block.json) {
+            collect_types(&value, &mut analysis.types);
+        }
+    }
+    // Only JSON-LD scripts can fail to parse
+    analysis.invalid_blocks = data.diagnostics.len();
+    analysis.json_ld_blocks += analysis.invalid_blocks;
+    analysis.types.sort();
+    analysis.types.dedup();
+
+    if analysis.json_ld_blocks + analysis.microdata_items + analysis.rdfa_resources == 0 {
+        analysis
+            .issues
+            .push("No structured data (JSON-LD, microdata or RDFa) found on the page".to_string());
+    } else if analysis.invalid_blocks > 0 {
+        analysis.issues.push(format!(
+            "{} of {} JSON-LD blocks are not valid JSON",
+            analysis.invalid_blocks, analysis.json_ld_blocks
+        ));
+    }
+
+    analysis
+}
+
+/// Collect `@type` values from a JSON-LD value, descending into nested objects
+fn collect_types(value: &JsonValue, types: &mut Vec<String>) {
+    match value {
+        JsonValue::Object(map) => {
+            match map.get("@type") {
+                Some(JsonValue::String(t)) => types.push(t.clone()),
+                Some(JsonValue::Array(items)) => {
+                    types.extend(items.iter().filter_map(|t| t.as_str()).map(str::to_string))
+                }
+                _ => {}
+            }
+            for (key, nested) in map {
+                if key != "@type" && key != "@context" {
+                    collect_types(nested, types);
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                collect_types(item, types);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(all(test, feature = "ai-readiness"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collects_nested_types() {
+        let html = r#"<html><head>
+<script type="application/ld+json">
+{"@context": "https://schema.org", "@graph": [
+  {"@type": "Product", "offers": {"@type": "Offer"}},
+  {"@type": ["Organization", "Brand"]}
+]}
+</script>
+</head></html>"#;
+
+        let analysis = analyze_structured_data(html, "https://example.com/");
+        assert_eq!(analysis.json_ld_blocks, 1);
+        assert_eq!(
+            analysis.types,
+            vec!["Brand", "Offer", "Organization", "Product"]
+        );
+        assert!(analysis.issues.is_empty());
+    }
+
+    #[test]
+    fn test_missing_and_invalid_blocks() {
+        let analysis = analyze_structured_data(
+            "<html><body><p>Hi</p></body></html>",
+            "https://example.com/",
+        );
+        assert_eq!(analysis.json_ld_blocks, 0);
+        assert_eq!(analysis.issues.len(), 1);
+
+        let html = r#"<script type="application/ld+json">{"@type": "Product"</script>"#;
+        let analysis = analyze_structured_data(html, "https://example.com/");
+        assert_eq!(analysis.invalid_blocks, 1);
+        assert!(analysis.issues[0].contains("not valid JSON"));
+    }
+
+    #[test]
+    fn test_counts_microdata_and_rdfa() {
+        let html = r#"<html><body>
+<div itemscope itemtype="https://schema.org/Product"><span itemprop="name">Bike</span></div>
+<div vocab="https://schema.org/" typeof="Organization"><span property="name">Acme</span></div>
+</body></html>"#;
+
+        let analysis = analyze_structured_data(html, "https://example.com/");
+        assert_eq!(analysis.json_ld_blocks, 0);
+        assert_eq!(analysis.microdata_items, 1);
+        assert_eq!(analysis.rdfa_resources, 1);
+        assert_eq!(analysis.valid_blocks(), 2);
+        assert_eq!(analysis.types, vec!["Organization", "Product"]);
+        assert!(analysis.issues.is_empty());
+    }
+}
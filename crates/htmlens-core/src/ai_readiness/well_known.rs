@@ -3,13 +3,10 @@
 //! The .well-known directory (RFC 8615) is a standard location for
 //! site-wide metadata and configuration files.
 
-#[cfg(feature = "ai-readiness")]
-use crate::url_utils::normalize_origin;
-#[cfg(feature = "ai-readiness")]
-use anyhow::Result;
 use serde::{Deserialize, Serialize};
-#[cfg(feature = "ai-readiness")]
-use std::time::Duration;
+
+use super::fetch::Fetcher;
+use crate::url_utils::normalize_origin;
 
 /// Results from checking .well-known directory
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -64,123 +61,94 @@ impl FileCheck {
             ..Default::default()
         }
     }
+
+    /// Build a check result from an HTTP status code and response body
+    ///
+    /// `security.txt` is validated as text, every other file as JSON.
+    pub fn from_response(path: &str, status_code: u16, body: Option<String>) -> Self {
+        let mut check = Self::new(path.to_string());
+        check.status_code = Some(status_code);
+        check.found = (200..300).contains(&status_code);
+
+        if !check.found {
+            check.error = Some(if status_code == 404 {
+                "File not found (404)".to_string()
+            } else {
+                format!("HTTP error: {}", status_code)
+            });
+            return check;
+        }
+
+        let Some(content) = body else {
+            check.error = Some("Failed to read response".to_string());
+            return check;
+        };
+
+        let expects_json = !path.ends_with("security.txt");
+        check.valid = if expects_json {
+            validate_json(&content)
+        } else {
+            validate_text(&content)
+        };
+
+        if !check.valid {
+            check.error = Some(if expects_json {
+                "Invalid JSON format".to_string()
+            } else {
+                "Invalid or empty content".to_string()
+            });
+        }
+
+        check.content = Some(content);
+        check
+    }
 }
 
 /// Check all relevant .well-known files for a domain
-#[cfg(feature = "ai-readiness")]
-pub async fn check_well_known_files(base_url: &str) -> Result<WellKnownChecks> {
+///
+/// Redirects are not followed: a file that is only reachable through a
+/// redirect is not considered published.
+pub async fn check_well_known_files<F: Fetcher>(fetcher: &F, base_url: &str) -> WellKnownChecks {
     let normalized_base = normalize_origin(base_url);
     let base_url = normalized_base.trim_end_matches('/');
 
-    let mut checks = WellKnownChecks {
-        ai_plugin: FileCheck::new("/.well-known/ai-plugin.json".to_string()),
-        mcp: FileCheck::new("/.well-known/mcp.json".to_string()),
-        openid_config: FileCheck::new("/.well-known/openid-configuration".to_string()),
-        security_txt: FileCheck::new("/.well-known/security.txt".to_string()),
-        apple_app_site_association: FileCheck::new(
-            "/.well-known/apple-app-site-association".to_string(),
-        ),
-        assetlinks: FileCheck::new("/.well-known/assetlinks.json".to_string()),
-    };
-
-    // Check each file
-    checks.ai_plugin = check_file(base_url, &checks.ai_plugin.path, FileType::Json).await;
-    checks.mcp = check_file(base_url, &checks.mcp.path, FileType::Json).await;
-    checks.openid_config = check_file(base_url, &checks.openid_config.path, FileType::Json).await;
-    checks.security_txt = check_file(base_url, &checks.security_txt.path, FileType::Text).await;
-    checks.apple_app_site_association = check_file(
-        base_url,
-        &checks.apple_app_site_association.path,
-        FileType::Json,
-    )
-    .await;
-    checks.assetlinks = check_file(base_url, &checks.assetlinks.path, FileType::Json).await;
-
-    Ok(checks)
-}
-
-/// Type of file expected
-#[cfg(feature = "ai-readiness")]
-#[derive(Debug, Clone, Copy)]
-enum FileType {
-    Json,
-    Text,
+    WellKnownChecks {
+        ai_plugin: check_file(fetcher, base_url, "/.well-known/ai-plugin.json").await,
+        mcp: check_file(fetcher, base_url, "/.well-known/mcp.json").await,
+        openid_config: check_file(fetcher, base_url, "/.well-known/openid-configuration").await,
+        security_txt: check_file(fetcher, base_url, "/.well-known/security.txt").await,
+        apple_app_site_association: check_file(
+            fetcher,
+            base_url,
+            "/.well-known/apple-app-site-association",
+        )
+        .await,
+        assetlinks: check_file(fetcher, base_url, "/.well-known/assetlinks.json").await,
+    }
 }
 
 /// Check a specific .well-known file
-#[cfg(feature = "ai-readiness")]
-async fn check_file(base_url: &str, path: &str, file_type: FileType) -> FileCheck {
+async fn check_file<F: Fetcher>(fetcher: &F, base_url: &str, path: &str) -> FileCheck {
     let url = format!("{}{}", base_url, path);
-    let mut check = FileCheck::new(path.to_string());
-
-    // Create HTTP client with timeout
-    let client = match reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
-        .user_agent("htmlens-ai-readiness-checker/0.4.2")
-        .build()
-    {
-        Ok(client) => client,
-        Err(e) => {
-            check.error = Some(format!("Failed to create HTTP client: {}", e));
-            return check;
+    match fetcher.get(&url, false).await {
+        Ok(response) => {
+            let body = response.is_success().then_some(response.body);
+            FileCheck::from_response(path, response.status, body)
         }
-    };
-
-    // Make request
-    let response = match client.get(&url).send().await {
-        Ok(resp) => resp,
         Err(e) => {
-            check.error = Some(format!("Request failed: {}", e));
-            return check;
+            let mut check = FileCheck::new(path.to_string());
+            check.error = Some(format!("Request failed: {:#}", e));
+            check
         }
-    };
-
-    check.status_code = Some(response.status().as_u16());
-    check.found = response.status().is_success();
-
-    if !check.found {
-        if response.status().as_u16() == 404 {
-            check.error = Some("File not found (404)".to_string());
-        } else {
-            check.error = Some(format!("HTTP error: {}", response.status()));
-        }
-        return check;
     }
-
-    // Get content
-    let content = match response.text().await {
-        Ok(text) => text,
-        Err(e) => {
-            check.error = Some(format!("Failed to read response: {}", e));
-            return check;
-        }
-    };
-
-    // Validate content based on type
-    check.valid = match file_type {
-        FileType::Json => validate_json(&content),
-        FileType::Text => validate_text(&content),
-    };
-
-    if !check.valid {
-        check.error = Some(match file_type {
-            FileType::Json => "Invalid JSON format".to_string(),
-            FileType::Text => "Invalid or empty content".to_string(),
-        });
-    }
-
-    check.content = Some(content);
-    check
 }
 
 /// Validate JSON content
-#[cfg(feature = "ai-readiness")]
 fn validate_json(content: &str) -> bool {
     serde_json::from_str::<serde_json::Value>(content).is_ok()
 }
 
 /// Validate text content (just check it's not empty)
-#[cfg(feature = "ai-readiness")]
 fn validate_text(content: &str) -> bool {
     !content.trim().is_empty()
 }
@@ -0,0 +1,163 @@
+//! JSON-LD compacted against the schema.org context
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value as JsonValue, json};
+
+use super::{Object, SCHEMA_NS, describe};
+use crate::types::KnowledgeGraph;
+
+/// The schema.org context URL written to `@context`
+pub const SCHEMA_ORG_CONTEXT_URL: &str = "https://schema.org";
+
+/// Term definitions of the bundled schema.org context: term -> (IRI, whether
+/// values are coerced to `@id`)
+static SCHEMA_TERMS: Lazy<HashMap<String, (String, bool)>> = Lazy::new(|| {
+    let context: JsonValue = serde_json::from_str(include_str!("../../contexts/schema.org.jsonld"))
+        .expect("bundled schema.org context is valid JSON");
+    let Some(terms) = context.get("@context").and_then(JsonValue::as_object) else {
+        return HashMap::new();
+    };
+
+    terms
+        .iter()
+        .filter_map(|(term, definition)| {
+            let (iri, coerced) = match definition {
+                JsonValue::String(iri) => (iri.as_str(), false),
+                JsonValue::Object(obj) => (
+                    obj.get("@id").and_then(JsonValue::as_str)?,
+                    obj.get("@type").and_then(JsonValue::as_str) == Some("@id"),
+                ),
+                _ => return None,
+            };
+            let iri = match iri.strip_prefix("schema:") {
+                Some(local) => format!("{SCHEMA_NS}{local}"),
+                None => iri.to_string(),
+            };
+            Some((term.clone(), (iri, coerced)))
+        })
+        .collect()
+});
+
+/// The graph as a compacted JSON-LD document using the schema.org context
+///
+/// Each subject is one node object in `@graph`. Schema.org types and
+/// properties are written as terms, other IRIs in full; edges are
+/// `{"@id": ...}` references.
+pub fn to_json_ld(graph: &KnowledgeGraph) -> JsonValue {
+    let nodes: Vec<JsonValue> = describe(graph)
+        .into_iter()
+        .map(|description| {
+            let mut node = Map::new();
+            node.insert("@id".to_string(), json!(description.subject));
+            match description.types.as_slice() {
+                [] => {}
+                [ty] => {
+                    node.insert("@type".to_string(), json!(compact_iri(ty)));
+                }
+                types => {
+                    let types: Vec<String> = types.iter().map(|ty| compact_iri(ty)).collect();
+                    node.insert("@type".to_string(), json!(types));
+                }
+            }
+
+            for (predicate, objects) in &description.predicates {
+                let key = compact_iri(predicate);
+                let coerced = SCHEMA_TERMS.get(&key).is_some_and(|(_, coerced)| *coerced);
+                let mut values = Vec::new();
+                for object in objects {
+                    match object {
+                        Object::Node(id) => values.push(json!({ "@id": id })),
+                        Object::Literal(JsonValue::Array(items)) => {
+                            values.extend(items.iter().map(|item| compact_literal(item, coerced)))
+                        }
+                        Object::Literal(value) => values.push(compact_literal(value, coerced)),
+                    }
+                }
+                let value = if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    JsonValue::Array(values)
+                };
+                node.insert(key, value);
+            }
+            JsonValue::Object(node)
+        })
+        .collect();
+
+    json!({
+        "@context": SCHEMA_ORG_CONTEXT_URL,
+        "@graph": nodes,
+    })
+}
+
+/// A term for schema.org IRIs the context maps back to the same IRI
+fn compact_iri(iri: &str) -> String {
+    let Some(local) = iri.strip_prefix(SCHEMA_NS) else {
+        return iri.to_string();
+    };
+    let maps_back = match SCHEMA_TERMS.get(local) {
+        Some((term_iri, _)) => term_iri == iri,
+        // Undefined terms expand through `@vocab`
+        None => !local.is_empty() && !local.contains([':', '/', '#']),
+    };
+    if maps_back {
+        local.to_string()
+    } else {
+        iri.to_string()
+    }
+}
+
+/// Strings of `@id`-coerced properties (e.g. `url`) would be read back as
+/// IRIs, so they are written as value objects
+fn compact_literal(value: &JsonValue, coerced: bool) -> JsonValue {
+    match value {
+        JsonValue::String(text) if coerced => json!({ "@value": text }),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::product_graph;
+
+    #[test]
+    fn test_compacted_json_ld() {
+        let document = to_json_ld(&product_graph());
+
+        assert_eq!(document["@context"], "https://schema.org");
+        let graph = document["@graph"].as_array().unwrap();
+        assert_eq!(graph.len(), 2);
+
+        let offer = &graph[0];
+        assert_eq!(offer["@id"], "_:b1");
+        assert_eq!(offer["@type"], "Offer");
+        assert_eq!(offer["price"], 1299.5);
+
+        let bike = &graph[1];
+        assert_eq!(bike["@type"], "Product");
+        assert_eq!(bike["offers"], json!({"@id": "_:b1"}));
+        assert_eq!(bike["description"]["@language"], "nl");
+        assert!(bike.get("@included").is_none());
+    }
+
+    #[test]
+    fn test_compact_iri_respects_context_terms() {
+        assert_eq!(compact_iri("http://schema.org/name"), "name");
+        assert_eq!(
+            compact_iri("https://vocab.example/name"),
+            "https://vocab.example/name"
+        );
+        // `type` is an alias of `@type` in the schema.org context
+        assert_eq!(
+            compact_iri("http://schema.org/type"),
+            "http://schema.org/type"
+        );
+        assert_eq!(
+            compact_literal(&json!("https://x"), true)["@value"],
+            "https://x"
+        );
+    }
+}
@@ -0,0 +1,645 @@
+//! Mermaid flowchart rendering
+//!
+//! A flat diagram of a ProductGroup with hundreds of variants is more than
+//! GitHub (or anyone) will render, so the diagram is condensed:
+//!
+//! - Runs of sibling nodes of the same type (targets of the same predicate
+//!   from one node, or sources of the same predicate into one node) beyond
+//!   [`MermaidOptions::sibling_limit`] are replaced by a single
+//!   "+187 more Product variants" node. Nodes only reachable through hidden
+//!   nodes (e.g. the offers of hidden variants) are hidden too.
+//! - At most [`MermaidOptions::max_nodes`] nodes are drawn, nearest to the
+//!   graph's roots first.
+//! - Nodes are grouped into `subgraph`s by owning ProductGroup or by type
+//!   and styled per Schema.org type with `classDef`.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+use super::visual::{node_classes, node_label, shorten_iri, type_color};
+use crate::types::{GraphNode, KnowledgeGraph};
+
+/// Flowchart direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MermaidDirection {
+    /// Top to bottom
+    #[default]
+    TopDown,
+    LeftRight,
+    BottomTop,
+    RightLeft,
+}
+
+impl fmt::Display for MermaidDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::TopDown => "TD",
+            Self::LeftRight => "LR",
+            Self::BottomTop => "BT",
+            Self::RightLeft => "RL",
+        })
+    }
+}
+
+impl FromStr for MermaidDirection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_uppercase().as_str() {
+            "TD" | "TB" => Ok(Self::TopDown),
+            "LR" => Ok(Self::LeftRight),
+            "BT" => Ok(Self::BottomTop),
+            "RL" => Ok(Self::RightLeft),
+            other => Err(format!(
+                "unknown Mermaid direction '{other}' (expected TD, LR, BT or RL)"
+            )),
+        }
+    }
+}
+
+/// How nodes are grouped into subgraphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MermaidGrouping {
+    /// A ProductGroup with its variants and the nodes only they refer to
+    /// (offers, specs) form one subgraph; other nodes are grouped by type
+    #[default]
+    ProductGroup,
+    /// One subgraph per type
+    Type,
+    /// No subgraphs
+    None,
+}
+
+impl FromStr for MermaidGrouping {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "product-group" | "productgroup" => Ok(Self::ProductGroup),
+            "type" => Ok(Self::Type),
+            "none" => Ok(Self::None),
+            other => Err(format!(
+                "unknown Mermaid grouping '{other}' (expected product-group, type or none)"
+            )),
+        }
+    }
+}
+
+/// Options for [`to_mermaid`]
+#[derive(Debug, Clone)]
+pub struct MermaidOptions {
+    pub direction: MermaidDirection,
+    /// Maximum number of graph nodes drawn
+    pub max_nodes: usize,
+    /// Siblings of one type shown before the rest are collapsed
+    pub sibling_limit: usize,
+    pub grouping: MermaidGrouping,
+}
+
+impl Default for MermaidOptions {
+    fn default() -> Self {
+        Self {
+            direction: MermaidDirection::default(),
+            max_nodes: 100,
+            sibling_limit: 5,
+            grouping: MermaidGrouping::default(),
+        }
+    }
+}
+
+/// Class for the "+N more" and "not shown" nodes
+const COLLAPSED_CLASS: &str = "collapsed";
+
+/// Render the graph as a Mermaid flowchart
+pub fn to_mermaid(graph: &KnowledgeGraph, options: &MermaidOptions) -> String {
+    let direction = options.direction;
+    if graph.nodes.is_empty() {
+        return format!("graph {direction}\n  Empty[\"No data\"]");
+    }
+
+    let layout = Layout::new(graph, options);
+    let mut lines = vec![format!("graph {direction}")];
+
+    // Subgraphs in order of their first member; single-member groups are
+    // drawn without one
+    let mut groups: Vec<(&Group, Vec<String>)> = Vec::new();
+    let mut ungrouped = Vec::new();
+    let mut members = layout
+        .shown
+        .iter()
+        .map(|&index| {
+            let line = format!("N{index}[{}]", node_text(&graph.nodes[index]));
+            (layout.groups[index].as_ref(), line)
+        })
+        .collect::<Vec<_>>();
+    members.extend(
+        layout
+            .placeholders
+            .iter()
+            .enumerate()
+            .map(|(position, placeholder)| {
+                let line = format!("C{position}[\"{}\"]", escape(&placeholder.label()));
+                (layout.groups[placeholder.first_hidden].as_ref(), line)
+            }),
+    );
+    for (group, line) in members {
+        match group {
+            Some(group) => match groups.iter_mut().find(|(g, _)| g.key == group.key) {
+                Some((_, lines)) => lines.push(line),
+                None => groups.push((group, vec![line])),
+            },
+            None => ungrouped.push(line),
+        }
+    }
+
+    for (position, (group, members)) in groups.iter().enumerate() {
+        if members.len() < 2 {
+            lines.extend(members.iter().map(|line| format!("  {line}")));
+            continue;
+        }
+        lines.push(format!(
+            "  subgraph G{position} [\"{}\"]",
+            escape(&group.title)
+        ));
+        lines.extend(members.iter().map(|line| format!("    {line}")));
+        lines.push("  end".to_string());
+    }
+    lines.extend(ungrouped.iter().map(|line| format!("  {line}")));
+    if layout.truncated > 0 {
+        lines.push(format!(
+            "  More[\"… {} more nodes not shown (limit {})\"]",
+            layout.truncated, options.max_nodes
+        ));
+    }
+
+    for &(from, to, predicate) in &layout.edges {
+        if layout.is_shown[from] && layout.is_shown[to] {
+            lines.push(format!(
+                "  N{from} -->|{}| N{to}",
+                escape(&shorten_iri(predicate))
+            ));
+        }
+    }
+    for (position, placeholder) in layout.placeholders.iter().enumerate() {
+        let predicate = escape(&shorten_iri(placeholder.predicate));
+        let anchor = placeholder.anchor;
+        lines.push(if placeholder.outgoing {
+            format!("  N{anchor} -.->|{predicate}| C{position}")
+        } else {
+            format!("  C{position} -.->|{predicate}| N{anchor}")
+        });
+    }
+
+    // One class per type, in order of first appearance
+    let mut classes: Vec<(String, Vec<usize>)> = Vec::new();
+    for &index in &layout.shown {
+        let Some(ty) = node_classes(&graph.nodes[index]).into_iter().next() else {
+            continue;
+        };
+        let class = class_name(&ty);
+        match classes.iter_mut().find(|(name, _)| *name == class) {
+            Some((_, members)) => members.push(index),
+            None => classes.push((class, vec![index])),
+        }
+    }
+    for (class, members) in &classes {
+        let members: Vec<String> = members.iter().map(|index| format!("N{index}")).collect();
+        lines.push(format!(
+            "  classDef {class} fill:{},stroke:#333,color:#000",
+            type_color(class)
+        ));
+        lines.push(format!("  class {} {class}", members.join(",")));
+    }
+    let mut collapsed: Vec<String> = (0..layout.placeholders.len())
+        .map(|position| format!("C{position}"))
+        .collect();
+    if layout.truncated > 0 {
+        collapsed.push("More".to_string());
+    }
+    if !collapsed.is_empty() {
+        lines.push(format!(
+            "  classDef {COLLAPSED_CLASS} fill:#fff,stroke:#999,stroke-dasharray:5 5,color:#555"
+        ));
+        lines.push(format!("  class {} {COLLAPSED_CLASS}", collapsed.join(",")));
+    }
+
+    lines.join("\n")
+}
+
+/// A subgraph
+struct Group {
+    key: String,
+    title: String,
+}
+
+/// Siblings collapsed into one node
+struct Placeholder<'a> {
+    /// The node the siblings are attached to
+    anchor: usize,
+    predicate: &'a str,
+    /// Whether the edges point from the anchor to the siblings
+    outgoing: bool,
+    ty: String,
+    hidden: usize,
+    first_hidden: usize,
+}
+
+impl Placeholder<'_> {
+    fn label(&self) -> String {
+        let predicate = shorten_iri(self.predicate);
+        if matches!(predicate.as_str(), "hasVariant" | "isVariantOf") {
+            format!("+{} more {} variants", self.hidden, self.ty)
+        } else {
+            format!("+{} more {} ({predicate})", self.hidden, self.ty)
+        }
+    }
+}
+
+/// Which nodes are drawn, and how
+struct Layout<'a> {
+    /// Drawn nodes, in drawing order
+    shown: Vec<usize>,
+    is_shown: Vec<bool>,
+    /// Visible nodes left out because of the node budget
+    truncated: usize,
+    edges: Vec<(usize, usize, &'a str)>,
+    placeholders: Vec<Placeholder<'a>>,
+    groups: Vec<Option<Group>>,
+}
+
+impl<'a> Layout<'a> {
+    fn new(graph: &'a KnowledgeGraph, options: &MermaidOptions) -> Self {
+        let count = graph.nodes.len();
+        let position: HashMap<&str, usize> = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id.as_str(), index))
+            .collect();
+        let edges: Vec<(usize, usize, &str)> = graph
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                Some((
+                    *position.get(edge.from.as_str())?,
+                    *position.get(edge.to.as_str())?,
+                    edge.predicate.as_str(),
+                ))
+            })
+            .filter(|(from, to, _)| from != to)
+            .collect();
+        let primary_type = |index: usize| {
+            node_classes(&graph.nodes[index])
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| "Thing".to_string())
+        };
+
+        // Sibling runs: (anchor, predicate, outgoing, sibling type) -> siblings
+        let mut runs: BTreeMap<(usize, &str, bool, String), Vec<usize>> = BTreeMap::new();
+        for &(from, to, predicate) in &edges {
+            runs.entry((from, predicate, true, primary_type(to)))
+                .or_default()
+                .push(to);
+            runs.entry((to, predicate, false, primary_type(from)))
+                .or_default()
+                .push(from);
+        }
+        let mut collapsed = vec![false; count];
+        for siblings in runs.values() {
+            for &index in &siblings[options.sibling_limit.min(siblings.len())..] {
+                collapsed[index] = true;
+            }
+        }
+        // A node that is one of the first targets of a visible node stays
+        // visible (e.g. an offer of a shown variant, even when it is one of
+        // hundreds of offers by the same seller)
+        let mut kept = vec![false; count];
+        for ((anchor, _, outgoing, _), siblings) in &runs {
+            if *outgoing && !collapsed[*anchor] {
+                for &index in &siblings[..options.sibling_limit.min(siblings.len())] {
+                    kept[index] = true;
+                }
+            }
+        }
+        let mut hidden: Vec<bool> = (0..count)
+            .map(|index| collapsed[index] && !kept[index])
+            .collect();
+
+        let mut placeholders = Vec::new();
+        for ((anchor, predicate, outgoing, ty), siblings) in &runs {
+            let hidden_siblings: Vec<usize> = siblings[options.sibling_limit.min(siblings.len())..]
+                .iter()
+                .copied()
+                .filter(|&index| hidden[index])
+                .collect();
+            if let Some(&first_hidden) = hidden_siblings.first() {
+                placeholders.push(Placeholder {
+                    anchor: *anchor,
+                    predicate,
+                    outgoing: *outgoing,
+                    ty: ty.clone(),
+                    hidden: hidden_siblings.len(),
+                    first_hidden,
+                });
+            }
+        }
+
+        // Nodes only referenced by hidden nodes are hidden too
+        let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for &(from, to, _) in &edges {
+            incoming[to].push(from);
+        }
+        loop {
+            let newly_hidden: Vec<usize> = (0..count)
+                .filter(|&index| {
+                    !hidden[index]
+                        && !incoming[index].is_empty()
+                        && incoming[index].iter().all(|&source| hidden[source])
+                })
+                .collect();
+            if newly_hidden.is_empty() {
+                break;
+            }
+            for index in newly_hidden {
+                hidden[index] = true;
+            }
+        }
+
+        // Breadth-first from the roots, so the budget keeps connected parts
+        let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for &(from, to, _) in &edges {
+            neighbours[from].push(to);
+            neighbours[to].push(from);
+        }
+        let roots =
+            (0..count).filter(|&index| incoming[index].iter().all(|&source| hidden[source]));
+        let mut seen = hidden.clone();
+        let mut order = Vec::new();
+        for seed in roots.chain(0..count) {
+            if seen[seed] {
+                continue;
+            }
+            seen[seed] = true;
+            let mut queue = VecDeque::from([seed]);
+            while let Some(index) = queue.pop_front() {
+                order.push(index);
+                for &next in &neighbours[index] {
+                    if !seen[next] {
+                        seen[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        let truncated = order.len().saturating_sub(options.max_nodes);
+        order.truncate(options.max_nodes);
+
+        let mut is_shown = vec![false; count];
+        for &index in &order {
+            is_shown[index] = true;
+        }
+        placeholders.retain(|placeholder| is_shown[placeholder.anchor]);
+
+        Self {
+            groups: groups(graph, &edges, options.grouping),
+            shown: order,
+            is_shown,
+            truncated,
+            edges,
+            placeholders,
+        }
+    }
+}
+
+/// The subgraph each node belongs to
+fn groups(
+    graph: &KnowledgeGraph,
+    edges: &[(usize, usize, &str)],
+    grouping: MermaidGrouping,
+) -> Vec<Option<Group>> {
+    let by_type = |node: &GraphNode| {
+        node_classes(node).into_iter().next().map(|ty| Group {
+            key: format!("type:{ty}"),
+            title: ty,
+        })
+    };
+
+    match grouping {
+        MermaidGrouping::None => graph.nodes.iter().map(|_| None).collect(),
+        MermaidGrouping::Type => graph.nodes.iter().map(by_type).collect(),
+        MermaidGrouping::ProductGroup => {
+            let owners = product_group_owners(graph, edges);
+            graph
+                .nodes
+                .iter()
+                .zip(owners)
+                .map(|(node, owner)| match owner {
+                    Some(owner) => Some(Group {
+                        key: format!("group:{owner}"),
+                        title: format!("ProductGroup: {}", node_label(&graph.nodes[owner])),
+                    }),
+                    None => by_type(node),
+                })
+                .collect()
+        }
+    }
+}
+
+/// The ProductGroup each node belongs to: the group itself, its variants
+/// (`hasVariant` / `isVariantOf`) and nodes only referenced from those
+fn product_group_owners(
+    graph: &KnowledgeGraph,
+    edges: &[(usize, usize, &str)],
+) -> Vec<Option<usize>> {
+    let is_group = |index: usize| {
+        node_classes(&graph.nodes[index])
+            .iter()
+            .any(|ty| ty == "ProductGroup")
+    };
+
+    let mut owners: Vec<Option<usize>> = (0..graph.nodes.len())
+        .map(|index| is_group(index).then_some(index))
+        .collect();
+    for &(from, to, predicate) in edges {
+        match shorten_iri(predicate).as_str() {
+            "hasVariant" if is_group(from) && owners[to].is_none() => owners[to] = Some(from),
+            "isVariantOf" if is_group(to) && owners[from].is_none() => owners[from] = Some(to),
+            _ => {}
+        }
+    }
+
+    let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); graph.nodes.len()];
+    for &(from, to, _) in edges {
+        incoming[to].push(from);
+    }
+    loop {
+        let mut changed = false;
+        for index in 0..graph.nodes.len() {
+            if owners[index].is_some() || incoming[index].is_empty() {
+                continue;
+            }
+            let owner = owners[incoming[index][0]];
+            if owner.is_some()
+                && incoming[index]
+                    .iter()
+                    .all(|&source| owners[source] == owner)
+            {
+                owners[index] = owner;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    owners
+}
+
+/// Node text: a link for IRI nodes, a quoted label otherwise
+fn node_text(node: &GraphNode) -> String {
+    let label = node_label(node);
+    if node.id.starts_with("http://") || node.id.starts_with("https://") {
+        format!(
+            "\"<a href='{}'>{}</a>\"",
+            escape(&node.id).replace('\'', "#39;"),
+            escape(&label)
+        )
+    } else {
+        format!("\"{}\"", escape(&label))
+    }
+}
+
+/// Escape text for a quoted Mermaid label
+fn escape(text: &str) -> String {
+    text.replace('&', "#amp;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+/// A `classDef` name for a type
+fn class_name(ty: &str) -> String {
+    ty.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GraphEdge;
+    use serde_json::json;
+
+    const SCHEMA: &str = "http://schema.org/";
+
+    fn node(id: &str, ty: &str, name: &str) -> GraphNode {
+        let mut node = GraphNode::new(id.to_string());
+        node.types.push(format!("{SCHEMA}{ty}"));
+        node.properties.insert(format!("{SCHEMA}name"), json!(name));
+        node
+    }
+
+    fn edge(from: &str, predicate: &str, to: &str) -> GraphEdge {
+        GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            predicate: format!("{SCHEMA}{predicate}"),
+        }
+    }
+
+    /// A ProductGroup with `variants` variants, each with an offer
+    fn product_group(variants: usize) -> KnowledgeGraph {
+        let mut graph = KnowledgeGraph {
+            nodes: vec![
+                node("https://example.com/group", "ProductGroup", "Bike"),
+                node("https://example.com/#brand", "Brand", "Pon"),
+            ],
+            edges: vec![edge(
+                "https://example.com/group",
+                "brand",
+                "https://example.com/#brand",
+            )],
+        };
+        for index in 0..variants {
+            let variant = format!("https://example.com/v{index}");
+            let offer = format!("_:o{index}");
+            graph
+                .nodes
+                .push(node(&variant, "Product", &format!("Variant {index}")));
+            graph.nodes.push(node(&offer, "Offer", "Offer"));
+            graph
+                .edges
+                .push(edge(&variant, "isVariantOf", "https://example.com/group"));
+            graph.edges.push(edge(&variant, "offers", &offer));
+        }
+        graph
+    }
+
+    #[test]
+    fn test_collapses_siblings_and_their_children() {
+        let mermaid = to_mermaid(&product_group(200), &MermaidOptions::default());
+
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("[\"+195 more Product variants\"]"));
+        assert!(mermaid.contains("-.->|isVariantOf| N0"));
+        // The group, the brand and five variants with their offers
+        assert_eq!(mermaid.matches("-->|offers|").count(), 5);
+        assert_eq!(mermaid.matches("-->|isVariantOf|").count(), 5);
+        assert!(!mermaid.contains("Variant 5"));
+    }
+
+    #[test]
+    fn test_groups_and_styles_nodes() {
+        let mermaid = to_mermaid(&product_group(2), &MermaidOptions::default());
+
+        assert!(mermaid.contains("  subgraph G0 [\"ProductGroup: Bike\"]\n"));
+        assert!(mermaid.contains("    N2[\"<a href='https://example.com/v0'>Variant 0</a>\"]"));
+        assert!(mermaid.contains("    N3[\"Offer\"]"));
+        assert!(mermaid.contains("classDef ProductGroup fill:#2f4b7c"));
+        assert!(mermaid.contains("class N2,N4 Product"));
+        assert!(!mermaid.contains("collapsed"));
+
+        let by_type = to_mermaid(
+            &product_group(2),
+            &MermaidOptions {
+                grouping: MermaidGrouping::Type,
+                direction: MermaidDirection::LeftRight,
+                ..MermaidOptions::default()
+            },
+        );
+        assert!(by_type.starts_with("graph LR\n"));
+        assert!(by_type.contains("subgraph G0 [\"Product\"]"));
+    }
+
+    #[test]
+    fn test_node_budget() {
+        let options = MermaidOptions {
+            max_nodes: 4,
+            sibling_limit: 10,
+            ..MermaidOptions::default()
+        };
+        let mermaid = to_mermaid(&product_group(5), &options);
+
+        assert_eq!(
+            mermaid.matches("[\"<a href=").count() + mermaid.matches("[\"Offer\"]").count(),
+            4
+        );
+        assert!(mermaid.contains("More[\"… 8 more nodes not shown (limit 4)\"]"));
+        assert!(mermaid.contains("class More collapsed"));
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = KnowledgeGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+        assert_eq!(
+            to_mermaid(&graph, &MermaidOptions::default()),
+            "graph TD\n  Empty[\"No data\"]"
+        );
+        assert_eq!("lr".parse(), Ok(MermaidDirection::LeftRight));
+    }
+}
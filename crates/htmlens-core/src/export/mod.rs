@@ -0,0 +1,324 @@
+//! Knowledge graph serializers
+//!
+//! [`KnowledgeGraph`] serializes to htmlens's own `{nodes, edges}` JSON via
+//! serde. This module writes it in standard RDF syntaxes instead, for loading
+//! into a triple store:
+//!
+//! - N-Triples and N-Quads ([`to_n_triples`], [`to_n_quads`])
+//! - Turtle with the `schema:` prefix ([`to_turtle`])
+//! - JSON-LD compacted against the schema.org context ([`to_json_ld`])
+//!
+//! and, for visualization, as Graphviz DOT, GraphML and Cytoscape.js JSON
+//! (see [`visual`]) or a condensed Mermaid flowchart ([`to_mermaid`]).
+//!
+//! In the RDF formats, node IDs become IRIs or blank nodes, `@type` becomes
+//! `rdf:type`, edges become IRI-valued statements and property values become
+//! literals. Strings with a language or datatype (`{"@value", "@language"}` /
+//! `{"@value", "@type"}`) keep it; JSON numbers and booleans are typed as
+//! `xsd:integer`, `xsd:double` and `xsd:boolean`.
+
+pub mod json_ld;
+pub mod mermaid;
+pub mod rdf;
+pub mod visual;
+
+pub use json_ld::to_json_ld;
+pub use mermaid::{MermaidDirection, MermaidGrouping, MermaidOptions, to_mermaid};
+pub use rdf::{to_n_quads, to_n_triples, to_turtle};
+pub use visual::{GraphFormat, shorten_iri, to_cytoscape, to_dot, to_graphml};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value as JsonValue;
+
+use crate::identity::canonical_json;
+use crate::types::KnowledgeGraph;
+
+pub(crate) const SCHEMA_NS: &str = "http://schema.org/";
+pub(crate) const XSD_NS: &str = "http://www.w3.org/2001/XMLSchema#";
+pub(crate) const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+
+/// RDF serialization formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RdfFormat {
+    NTriples,
+    NQuads,
+    Turtle,
+    JsonLd,
+}
+
+impl RdfFormat {
+    /// Conventional file extension
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::NTriples => "nt",
+            Self::NQuads => "nq",
+            Self::Turtle => "ttl",
+            Self::JsonLd => "jsonld",
+        }
+    }
+
+    /// Serialize `graph`; N-Quads statements are placed in `graph_name`
+    /// (the default graph when `None`)
+    pub fn serialize(self, graph: &KnowledgeGraph, graph_name: Option<&str>) -> String {
+        match self {
+            Self::NTriples => to_n_triples(graph),
+            Self::NQuads => to_n_quads(graph, graph_name),
+            Self::Turtle => to_turtle(graph),
+            Self::JsonLd => {
+                let mut text = serde_json::to_string_pretty(&to_json_ld(graph))
+                    .expect("JSON values always serialize");
+                text.push('\n');
+                text
+            }
+        }
+    }
+}
+
+impl FromStr for RdfFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "ntriples" | "n-triples" | "nt" => Ok(Self::NTriples),
+            "nquads" | "n-quads" | "nq" => Ok(Self::NQuads),
+            "turtle" | "ttl" => Ok(Self::Turtle),
+            "jsonld" | "json-ld" => Ok(Self::JsonLd),
+            other => Err(format!(
+                "unknown RDF format '{other}' (expected ntriples, nquads, turtle or jsonld)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RdfFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NTriples => "ntriples",
+            Self::NQuads => "nquads",
+            Self::Turtle => "turtle",
+            Self::JsonLd => "jsonld",
+        })
+    }
+}
+
+/// The value of a statement
+pub(crate) enum Object<'a> {
+    /// Another node, by ID
+    Node(&'a str),
+    /// A property value as stored on [`GraphNode`](crate::GraphNode)
+    Literal(&'a JsonValue),
+}
+
+/// Everything the graph states about one subject
+pub(crate) struct Description<'a> {
+    pub subject: &'a str,
+    pub types: Vec<&'a str>,
+    /// Objects keyed by predicate IRI
+    pub predicates: BTreeMap<&'a str, Vec<Object<'a>>>,
+}
+
+/// Group the graph's types, properties and edges by subject, in ID order
+///
+/// Predicates that are not IRIs (e.g. `@included` edges) have no RDF
+/// equivalent and are left out.
+pub(crate) fn describe(graph: &KnowledgeGraph) -> Vec<Description<'_>> {
+    let mut descriptions: BTreeMap<&str, Description> = BTreeMap::new();
+
+    for node in &graph.nodes {
+        let description = descriptions
+            .entry(node.id.as_str())
+            .or_insert_with(|| Description::new(&node.id));
+        description.types.extend(
+            node.types
+                .iter()
+                .map(String::as_str)
+                .filter(|ty| is_iri(ty)),
+        );
+        for (predicate, value) in &node.properties {
+            if is_iri(predicate) && !value.is_null() {
+                description
+                    .predicates
+                    .entry(predicate)
+                    .or_default()
+                    .push(Object::Literal(value));
+            }
+        }
+    }
+    for edge in &graph.edges {
+        if is_iri(&edge.predicate) {
+            descriptions
+                .entry(edge.from.as_str())
+                .or_insert_with(|| Description::new(&edge.from))
+                .predicates
+                .entry(&edge.predicate)
+                .or_default()
+                .push(Object::Node(&edge.to));
+        }
+    }
+
+    descriptions
+        .into_values()
+        .filter(|description| !description.types.is_empty() || !description.predicates.is_empty())
+        .collect()
+}
+
+impl<'a> Description<'a> {
+    fn new(subject: &'a str) -> Self {
+        Self {
+            subject,
+            types: Vec::new(),
+            predicates: BTreeMap::new(),
+        }
+    }
+}
+
+/// An RDF literal
+pub(crate) struct Literal {
+    pub value: String,
+    /// Datatype IRI; `None` for plain strings
+    pub datatype: Option<String>,
+    pub language: Option<String>,
+}
+
+/// The RDF literals of a property value; arrays give one literal per item
+pub(crate) fn literals(value: &JsonValue) -> Vec<Literal> {
+    let typed = |value: String, datatype: &str| Literal {
+        value,
+        datatype: Some(datatype.to_string()),
+        language: None,
+    };
+
+    match value {
+        JsonValue::Null => Vec::new(),
+        JsonValue::String(text) => vec![Literal {
+            value: text.clone(),
+            datatype: None,
+            language: None,
+        }],
+        JsonValue::Bool(flag) => vec![typed(flag.to_string(), &format!("{XSD_NS}boolean"))],
+        JsonValue::Number(number) if number.is_f64() => {
+            let value = number.as_f64().unwrap_or_default();
+            vec![typed(canonical_double(value), &format!("{XSD_NS}double"))]
+        }
+        JsonValue::Number(number) => {
+            vec![typed(number.to_string(), &format!("{XSD_NS}integer"))]
+        }
+        JsonValue::Array(values) => values.iter().flat_map(literals).collect(),
+        JsonValue::Object(obj) => match obj.get("@value") {
+            Some(inner) => {
+                let mut items = literals(inner);
+                for literal in &mut items {
+                    if let Some(language) = obj.get("@language").and_then(JsonValue::as_str) {
+                        literal.language = Some(language.to_string());
+                        literal.datatype = None;
+                    } else if let Some(datatype) = obj.get("@type").and_then(JsonValue::as_str) {
+                        literal.datatype = Some(datatype.to_string());
+                    }
+                }
+                items
+            }
+            None => vec![typed(canonical_json(value), RDF_JSON)],
+        },
+    }
+}
+
+/// `xsd:double` in the canonical form JSON-LD uses (e.g. `1.5E1`)
+fn canonical_double(value: f64) -> String {
+    let text = format!("{value:E}");
+    match text.split_once('E') {
+        Some((mantissa, exponent)) if !mantissa.contains('.') => {
+            format!("{mantissa}.0E{exponent}")
+        }
+        _ => text,
+    }
+}
+
+/// Absolute IRIs and blank node labels; keywords and bare terms are not
+fn is_iri(value: &str) -> bool {
+    !value.starts_with('@') && !value.starts_with("_:") && value.contains(':')
+}
+
+pub(crate) fn is_blank(id: &str) -> bool {
+    id.starts_with("_:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GraphEdge, GraphNode};
+    use serde_json::json;
+
+    pub(super) fn product_graph() -> KnowledgeGraph {
+        let mut product = GraphNode::new("https://example.com/bike".to_string());
+        product.types.push(format!("{SCHEMA_NS}Product"));
+        product
+            .properties
+            .insert(format!("{SCHEMA_NS}name"), json!("Bike \"Pro\""));
+        product.properties.insert(
+            format!("{SCHEMA_NS}description"),
+            json!({"@value": "Fiets", "@language": "nl"}),
+        );
+        product.properties.insert(
+            format!("{SCHEMA_NS}releaseDate"),
+            json!({"@value": "2024-03-01", "@type": format!("{XSD_NS}date")}),
+        );
+        let mut offer = GraphNode::new("_:b1".to_string());
+        offer.types.push(format!("{SCHEMA_NS}Offer"));
+        offer
+            .properties
+            .insert(format!("{SCHEMA_NS}price"), json!(1299.5));
+        offer
+            .properties
+            .insert(format!("{SCHEMA_NS}inventoryLevel"), json!(3));
+
+        KnowledgeGraph {
+            nodes: vec![offer, product],
+            edges: vec![
+                GraphEdge {
+                    from: "https://example.com/bike".to_string(),
+                    to: "_:b1".to_string(),
+                    predicate: format!("{SCHEMA_NS}offers"),
+                },
+                GraphEdge {
+                    from: "https://example.com/bike".to_string(),
+                    to: "https://example.com/page".to_string(),
+                    predicate: "@included".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_describe_groups_statements_by_subject() {
+        let graph = product_graph();
+        let descriptions = describe(&graph);
+
+        let subjects: Vec<&str> = descriptions.iter().map(|d| d.subject).collect();
+        assert_eq!(subjects, vec!["_:b1", "https://example.com/bike"]);
+        // `@included` is not an RDF predicate
+        assert_eq!(descriptions[1].predicates.len(), 4);
+    }
+
+    #[test]
+    fn test_literals_keep_types_and_languages() {
+        let literal = &literals(&json!({"@value": "Fiets", "@language": "nl"}))[0];
+        assert_eq!(literal.language.as_deref(), Some("nl"));
+        assert_eq!(literal.datatype, None);
+
+        let literal = &literals(&json!(1299.5))[0];
+        assert_eq!(literal.value, "1.2995E3");
+        assert_eq!(
+            literal.datatype.as_deref(),
+            Some(&*format!("{XSD_NS}double"))
+        );
+
+        assert_eq!(literals(&json!(10.0))[0].value, "1.0E1");
+        assert_eq!(literals(&json!(["a", "b"])).len(), 2);
+        assert!(literals(&JsonValue::Null).is_empty());
+        assert_eq!("ttl".parse::<RdfFormat>(), Ok(RdfFormat::Turtle));
+    }
+}
@@ -0,0 +1,212 @@
+//! N-Triples, N-Quads and Turtle serialization
+
+use std::fmt::Write as _;
+
+use super::{
+    Description, Literal, Object, RDF_TYPE, SCHEMA_NS, XSD_NS, describe, is_blank, literals,
+};
+use crate::types::KnowledgeGraph;
+
+/// Serialize the graph as N-Triples
+pub fn to_n_triples(graph: &KnowledgeGraph) -> String {
+    to_n_quads(graph, None)
+}
+
+/// Serialize the graph as N-Quads, with every statement in `graph_name`
+///
+/// With no graph name the statements are in the default graph, which makes
+/// the output N-Triples.
+pub fn to_n_quads(graph: &KnowledgeGraph, graph_name: Option<&str>) -> String {
+    let suffix = graph_name
+        .map(|name| format!(" {}", node_term(name)))
+        .unwrap_or_default();
+    let mut out = String::new();
+
+    for description in describe(graph) {
+        let subject = node_term(description.subject);
+        for ty in &description.types {
+            let _ = writeln!(out, "{subject} <{RDF_TYPE}> {}{suffix} .", iri_term(ty));
+        }
+        for (predicate, objects) in &description.predicates {
+            for object in object_terms(objects, |literal| literal_term(literal, iri_term)) {
+                let _ = writeln!(out, "{subject} {} {object}{suffix} .", iri_term(predicate));
+            }
+        }
+    }
+    out
+}
+
+/// Serialize the graph as Turtle, abbreviating schema.org and XSD IRIs
+pub fn to_turtle(graph: &KnowledgeGraph) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "@prefix schema: <{SCHEMA_NS}> .");
+    let _ = writeln!(out, "@prefix xsd: <{XSD_NS}> .");
+
+    for description in describe(graph) {
+        out.push('\n');
+        out.push_str(&turtle_description(&description));
+    }
+    out
+}
+
+fn turtle_description(description: &Description) -> String {
+    let mut lines = Vec::new();
+    if !description.types.is_empty() {
+        let types: Vec<String> = description.types.iter().map(|ty| prefixed(ty)).collect();
+        lines.push(format!("a {}", types.join(", ")));
+    }
+    for (predicate, objects) in &description.predicates {
+        let objects = object_terms(objects, |literal| literal_term(literal, prefixed));
+        lines.push(format!("{} {}", prefixed(predicate), objects.join(", ")));
+    }
+
+    format!(
+        "{} {} .\n",
+        node_term(description.subject),
+        lines.join(" ;\n    ")
+    )
+}
+
+fn object_terms(objects: &[Object], literal: impl Fn(&Literal) -> String) -> Vec<String> {
+    let mut terms = Vec::new();
+    for object in objects {
+        match object {
+            Object::Node(id) => terms.push(node_term(id)),
+            Object::Literal(value) => terms.extend(literals(value).iter().map(&literal)),
+        }
+    }
+    terms
+}
+
+/// A node ID as an IRI or blank node label
+fn node_term(id: &str) -> String {
+    match id.strip_prefix("_:") {
+        Some(label) if is_blank(id) => {
+            let label: String = label
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || matches!(c, '_' | '-') {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("_:{label}")
+        }
+        _ => iri_term(id),
+    }
+}
+
+/// `<iri>`, with characters IRIREF does not allow escaped
+fn iri_term(iri: &str) -> String {
+    let mut out = String::with_capacity(iri.len() + 2);
+    out.push('<');
+    for c in iri.chars() {
+        if c <= ' ' || matches!(c, '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\') {
+            let _ = write!(out, "\\u{:04X}", c as u32);
+        } else {
+            out.push(c);
+        }
+    }
+    out.push('>');
+    out
+}
+
+/// `schema:Name` / `xsd:name` where the local name allows it, else `<iri>`
+fn prefixed(iri: &str) -> String {
+    [("schema", SCHEMA_NS), ("xsd", XSD_NS)]
+        .into_iter()
+        .find_map(|(prefix, namespace)| {
+            let local = iri.strip_prefix(namespace)?;
+            let valid = local
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic())
+                && local.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            valid.then(|| format!("{prefix}:{local}"))
+        })
+        .unwrap_or_else(|| iri_term(iri))
+}
+
+fn literal_term(literal: &Literal, datatype_term: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(literal.value.len() + 2);
+    out.push('"');
+    for c in literal.value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+
+    if let Some(language) = &literal.language {
+        let _ = write!(out, "@{language}");
+    } else if let Some(datatype) = &literal.datatype {
+        let _ = write!(out, "^^{}", datatype_term(datatype));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::product_graph;
+
+    #[test]
+    fn test_n_quads_output() {
+        let quads = to_n_quads(&product_graph(), Some("https://example.com/page"));
+        let lines: Vec<&str> = quads.lines().collect();
+
+        assert_eq!(lines.len(), 8);
+        assert!(lines.contains(
+            &"_:b1 <http://schema.org/price> \"1.2995E3\"^^<http://www.w3.org/2001/XMLSchema#double> <https://example.com/page> ."
+        ));
+        assert!(lines.contains(
+            &"<https://example.com/bike> <http://schema.org/name> \"Bike \\\"Pro\\\"\" <https://example.com/page> ."
+        ));
+        assert!(lines.contains(
+            &"<https://example.com/bike> <http://schema.org/description> \"Fiets\"@nl <https://example.com/page> ."
+        ));
+        assert!(lines.contains(
+            &"<https://example.com/bike> <http://schema.org/offers> _:b1 <https://example.com/page> ."
+        ));
+
+        let triples = to_n_triples(&product_graph());
+        assert!(
+            triples
+                .lines()
+                .all(|line| !line.contains("example.com/page"))
+        );
+    }
+
+    #[test]
+    fn test_turtle_output() {
+        let turtle = to_turtle(&product_graph());
+
+        assert!(turtle.starts_with("@prefix schema: <http://schema.org/> .\n"));
+        assert!(turtle.contains(
+            "<https://example.com/bike> a schema:Product ;\n    \
+             schema:description \"Fiets\"@nl ;\n    \
+             schema:name \"Bike \\\"Pro\\\"\" ;\n    \
+             schema:offers _:b1 ;\n    \
+             schema:releaseDate \"2024-03-01\"^^xsd:date .\n"
+        ));
+        assert!(turtle.contains("schema:inventoryLevel \"3\"^^xsd:integer"));
+    }
+
+    #[test]
+    fn test_iri_escaping() {
+        assert_eq!(
+            iri_term("https://example.com/a b"),
+            "<https://example.com/a\\u0020b>"
+        );
+        assert_eq!(
+            prefixed("http://schema.org/3DModel"),
+            "<http://schema.org/3DModel>"
+        );
+    }
+}
@@ -0,0 +1,488 @@
+//! Graphviz DOT, GraphML and Cytoscape.js serialization
+//!
+//! Unlike the RDF formats these are for looking at the graph: every node gets
+//! a label (its name, else its type), a class and color per type, and a few
+//! key properties (SKU, GTIN, price, ...) as attributes; edges are labeled
+//! with the shortened predicate. Large graphs with hundreds of variants stay
+//! usable in Graphviz, yEd/Gephi and Cytoscape, where Mermaid gives up.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use serde_json::{Map, Value as JsonValue, json};
+
+use super::SCHEMA_NS;
+use crate::types::{GraphNode, KnowledgeGraph};
+
+/// Properties copied onto nodes as attributes, by schema.org name
+pub const KEY_PROPERTIES: &[&str] = &[
+    "name",
+    "sku",
+    "gtin",
+    "gtin13",
+    "mpn",
+    "price",
+    "priceCurrency",
+    "url",
+];
+
+/// Colors for common types; other types get one from [`PALETTE`]
+const TYPE_COLORS: &[(&str, &str)] = &[
+    ("Product", "#4e79a7"),
+    ("ProductGroup", "#2f4b7c"),
+    ("Offer", "#59a14f"),
+    ("AggregateOffer", "#8cd17d"),
+    ("Organization", "#f28e2b"),
+    ("Brand", "#ffbe7d"),
+    ("WebPage", "#76b7b2"),
+    ("WebSite", "#499894"),
+    ("BreadcrumbList", "#b6992d"),
+    ("ListItem", "#f1ce63"),
+    ("PropertyValue", "#bab0ac"),
+    ("ImageObject", "#d37295"),
+];
+
+const PALETTE: &[&str] = &[
+    "#e15759", "#ff9d9a", "#79706e", "#d4a6c8", "#9d7660", "#a0cbe8", "#86bcb6", "#fabfd2",
+];
+
+/// Color for nodes without a type
+const UNTYPED_COLOR: &str = "#dddddd";
+
+/// Visual graph formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    GraphMl,
+    Cytoscape,
+}
+
+impl GraphFormat {
+    /// Conventional file extension
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::GraphMl => "graphml",
+            Self::Cytoscape => "json",
+        }
+    }
+
+    pub fn serialize(self, graph: &KnowledgeGraph) -> String {
+        match self {
+            Self::Dot => to_dot(graph),
+            Self::GraphMl => to_graphml(graph),
+            Self::Cytoscape => {
+                let mut text = serde_json::to_string_pretty(&to_cytoscape(graph))
+                    .expect("JSON values always serialize");
+                text.push('\n');
+                text
+            }
+        }
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "graphml" => Ok(Self::GraphMl),
+            "cytoscape" | "cytoscape-json" => Ok(Self::Cytoscape),
+            other => Err(format!(
+                "unknown graph format '{other}' (expected dot, graphml or cytoscape)"
+            )),
+        }
+    }
+}
+
+/// The last segment of an IRI (`http://schema.org/offers` -> `offers`)
+pub fn shorten_iri(iri: &str) -> String {
+    iri.rsplit(&['/', '#'][..])
+        .next()
+        .unwrap_or(iri)
+        .to_string()
+}
+
+/// Serialize the graph as a Graphviz `digraph`
+pub fn to_dot(graph: &KnowledgeGraph) -> String {
+    let ids = short_ids(graph);
+    let mut out = String::new();
+    out.push_str("digraph knowledge_graph {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+    out.push_str("  edge [fontname=\"Helvetica\", fontsize=10];\n");
+
+    for (node, id) in graph.nodes.iter().zip(&ids) {
+        let mut attributes = vec![
+            ("label".to_string(), node_label(node)),
+            ("tooltip".to_string(), node.id.clone()),
+            ("fillcolor".to_string(), node_color(node).to_string()),
+        ];
+        if !node.types.is_empty() {
+            attributes.push(("class".to_string(), node_classes(node).join(" ")));
+        }
+        if node.id.starts_with("http://") || node.id.starts_with("https://") {
+            attributes.push(("URL".to_string(), node.id.clone()));
+        }
+        attributes.extend(key_properties(node));
+
+        let attributes: Vec<String> = attributes
+            .iter()
+            .map(|(key, value)| format!("{}={}", dot_id(key), dot_string(value)))
+            .collect();
+        let _ = writeln!(out, "  {id} [{}];", attributes.join(", "));
+    }
+
+    for edge in visible_edges(graph, &ids) {
+        let _ = writeln!(
+            out,
+            "  {} -> {} [label={}];",
+            edge.from,
+            edge.to,
+            dot_string(&shorten_iri(edge.predicate))
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Serialize the graph as GraphML
+///
+/// Node and edge IDs are the graph's own IDs; labels, types, colors, key
+/// properties and predicates are `<data>` attributes.
+pub fn to_graphml(graph: &KnowledgeGraph) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for (id, domain, name) in [
+        ("label", "node", "label"),
+        ("type", "node", "type"),
+        ("color", "node", "color"),
+    ] {
+        let _ = writeln!(
+            out,
+            "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{name}\" attr.type=\"string\"/>"
+        );
+    }
+    for property in KEY_PROPERTIES {
+        let _ = writeln!(
+            out,
+            "  <key id=\"p_{property}\" for=\"node\" attr.name=\"{property}\" attr.type=\"string\"/>"
+        );
+    }
+    out.push_str(
+        "  <key id=\"predicate\" for=\"edge\" attr.name=\"predicate\" attr.type=\"string\"/>\n",
+    );
+    out.push_str("  <key id=\"iri\" for=\"edge\" attr.name=\"iri\" attr.type=\"string\"/>\n");
+    out.push_str("  <graph id=\"knowledge_graph\" edgedefault=\"directed\">\n");
+
+    for node in &graph.nodes {
+        let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id));
+        let mut data = vec![
+            ("label".to_string(), node_label(node)),
+            ("color".to_string(), node_color(node).to_string()),
+        ];
+        if !node.types.is_empty() {
+            data.push(("type".to_string(), node_classes(node).join(" ")));
+        }
+        data.extend(
+            key_properties(node)
+                .into_iter()
+                .map(|(name, value)| (format!("p_{name}"), value)),
+        );
+        for (key, value) in data {
+            let _ = writeln!(
+                out,
+                "      <data key=\"{key}\">{}</data>",
+                xml_escape(&value)
+            );
+        }
+        out.push_str("    </node>\n");
+    }
+
+    let ids: Vec<&str> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+    for (index, edge) in visible_edges(graph, &ids).enumerate() {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"e{index}\" source=\"{}\" target=\"{}\">",
+            xml_escape(edge.from),
+            xml_escape(edge.to)
+        );
+        let _ = writeln!(
+            out,
+            "      <data key=\"predicate\">{}</data>",
+            xml_escape(&shorten_iri(edge.predicate))
+        );
+        let _ = writeln!(
+            out,
+            "      <data key=\"iri\">{}</data>",
+            xml_escape(edge.predicate)
+        );
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// The graph as Cytoscape.js elements (`cy.json()` / `cy.add()` input)
+///
+/// Node types are Cytoscape classes, so stylesheets can select e.g.
+/// `node.Product`; `data.color` holds the same color as the other exports.
+pub fn to_cytoscape(graph: &KnowledgeGraph) -> JsonValue {
+    let nodes: Vec<JsonValue> = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let mut data = Map::new();
+            data.insert("id".to_string(), json!(node.id));
+            data.insert("label".to_string(), json!(node_label(node)));
+            data.insert("types".to_string(), json!(node_classes(node)));
+            data.insert("color".to_string(), json!(node_color(node)));
+            for (name, value) in key_properties(node) {
+                data.insert(name, json!(value));
+            }
+            json!({ "data": data, "classes": node_classes(node).join(" ") })
+        })
+        .collect();
+
+    let ids: Vec<&str> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+    let edges: Vec<JsonValue> = visible_edges(graph, &ids)
+        .enumerate()
+        .map(|(index, edge)| {
+            json!({
+                "data": {
+                    "id": format!("e{index}"),
+                    "source": edge.from,
+                    "target": edge.to,
+                    "label": shorten_iri(edge.predicate),
+                    "predicate": edge.predicate,
+                }
+            })
+        })
+        .collect();
+
+    json!({ "elements": { "nodes": nodes, "edges": edges } })
+}
+
+/// An edge between exported node IDs
+struct VisibleEdge<'a> {
+    from: &'a str,
+    to: &'a str,
+    predicate: &'a str,
+}
+
+/// Edges between nodes of the graph, with endpoints mapped to `ids` (which
+/// are parallel to `graph.nodes`)
+fn visible_edges<'a, S: AsRef<str>>(
+    graph: &'a KnowledgeGraph,
+    ids: &'a [S],
+) -> impl Iterator<Item = VisibleEdge<'a>> {
+    let index: HashMap<&str, &str> = graph
+        .nodes
+        .iter()
+        .zip(ids)
+        .map(|(node, id)| (node.id.as_str(), id.as_ref()))
+        .collect();
+
+    graph.edges.iter().filter_map(move |edge| {
+        Some(VisibleEdge {
+            from: index.get(edge.from.as_str())?,
+            to: index.get(edge.to.as_str())?,
+            predicate: &edge.predicate,
+        })
+    })
+}
+
+/// `n0`, `n1`, ... in node order
+fn short_ids(graph: &KnowledgeGraph) -> Vec<String> {
+    (0..graph.nodes.len())
+        .map(|index| format!("n{index}"))
+        .collect()
+}
+
+/// The node's name (`propertyID: value unit` for PropertyValues), else its
+/// type or ID
+pub(super) fn node_label(node: &GraphNode) -> String {
+    if node_classes(node).iter().any(|ty| ty == "PropertyValue") {
+        let mut label = schema_property(node, "propertyID")
+            .or_else(|| schema_property(node, "name"))
+            .unwrap_or_else(|| "PropertyValue".to_string());
+        if let Some(value) =
+            schema_property(node, "value").or_else(|| schema_property(node, "valueReference"))
+        {
+            label.push_str(": ");
+            label.push_str(&value);
+            if let Some(unit) =
+                schema_property(node, "unitText").or_else(|| schema_property(node, "unitCode"))
+            {
+                label.push(' ');
+                label.push_str(&unit);
+            }
+        }
+        return label;
+    }
+
+    schema_property(node, "name")
+        .or_else(|| node.types.first().map(|ty| shorten_iri(ty)))
+        .unwrap_or_else(|| shorten_iri(&node.id))
+}
+
+pub(super) fn node_classes(node: &GraphNode) -> Vec<String> {
+    node.types.iter().map(|ty| shorten_iri(ty)).collect()
+}
+
+/// Color of the node's first type
+fn node_color(node: &GraphNode) -> &'static str {
+    match node.types.first() {
+        Some(ty) => type_color(&shorten_iri(ty)),
+        None => UNTYPED_COLOR,
+    }
+}
+
+/// Color for a (shortened) type name
+pub(super) fn type_color(ty: &str) -> &'static str {
+    TYPE_COLORS
+        .iter()
+        .find(|(name, _)| *name == ty)
+        .map(|(_, color)| *color)
+        .unwrap_or_else(|| {
+            let hash = ty.bytes().fold(0usize, |hash, byte| {
+                hash.wrapping_mul(31).wrapping_add(byte as usize)
+            });
+            PALETTE[hash % PALETTE.len()]
+        })
+}
+
+fn key_properties(node: &GraphNode) -> Vec<(String, String)> {
+    KEY_PROPERTIES
+        .iter()
+        .filter_map(|name| Some((name.to_string(), schema_property(node, name)?)))
+        .collect()
+}
+
+/// Display text of a schema.org property (`http` or `https` namespace)
+fn schema_property(node: &GraphNode, name: &str) -> Option<String> {
+    [SCHEMA_NS, "https://schema.org/"]
+        .iter()
+        .find_map(|namespace| node.properties.get(&format!("{namespace}{name}")))
+        .and_then(display_text)
+}
+
+fn display_text(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(text) => Some(text.clone()),
+        JsonValue::Number(number) => Some(number.to_string()),
+        JsonValue::Bool(flag) => Some(flag.to_string()),
+        JsonValue::Array(values) => values.iter().find_map(display_text),
+        JsonValue::Object(obj) => obj.get("@value").and_then(display_text),
+        JsonValue::Null => None,
+    }
+}
+
+/// A DOT ID: bare when it is a plain identifier, quoted otherwise
+fn dot_id(value: &str) -> String {
+    let plain = value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        value.to_string()
+    } else {
+        dot_string(value)
+    }
+}
+
+fn dot_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::product_graph;
+
+    #[test]
+    fn test_dot_output() {
+        let dot = to_dot(&product_graph());
+
+        assert!(dot.starts_with("digraph knowledge_graph {\n"));
+        assert!(dot.contains(
+            "  n1 [label=\"Bike \\\"Pro\\\"\", tooltip=\"https://example.com/bike\", \
+             fillcolor=\"#4e79a7\", class=\"Product\", URL=\"https://example.com/bike\", \
+             name=\"Bike \\\"Pro\\\"\"];"
+        ));
+        assert!(dot.contains("price=\"1299.5\""));
+        assert!(dot.contains("  n1 -> n0 [label=\"offers\"];"));
+        // Edges to nodes outside the graph are left out
+        assert!(!dot.contains("included"));
+    }
+
+    #[test]
+    fn test_graphml_output() {
+        let graphml = to_graphml(&product_graph());
+
+        assert!(graphml.contains("<graph id=\"knowledge_graph\" edgedefault=\"directed\">"));
+        assert!(graphml.contains("<node id=\"_:b1\">"));
+        assert!(graphml.contains("<data key=\"label\">Bike &quot;Pro&quot;</data>"));
+        assert!(graphml.contains("<data key=\"type\">Offer</data>"));
+        assert!(graphml.contains("<data key=\"p_price\">1299.5</data>"));
+        assert!(
+            graphml
+                .contains("<edge id=\"e0\" source=\"https://example.com/bike\" target=\"_:b1\">")
+        );
+        assert!(graphml.contains("<data key=\"predicate\">offers</data>"));
+    }
+
+    #[test]
+    fn test_cytoscape_output() {
+        let elements = to_cytoscape(&product_graph());
+
+        let nodes = elements["elements"]["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1]["classes"], "Product");
+        assert_eq!(nodes[1]["data"]["label"], "Bike \"Pro\"");
+        assert_eq!(nodes[1]["data"]["color"], "#4e79a7");
+        assert_eq!(nodes[0]["data"]["price"], "1299.5");
+
+        let edges = elements["elements"]["edges"].as_array().unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0]["data"]["source"], "https://example.com/bike");
+        assert_eq!(edges[0]["data"]["label"], "offers");
+    }
+
+    #[test]
+    fn test_shorten_iri_and_colors() {
+        assert_eq!(shorten_iri("http://schema.org/offers"), "offers");
+        assert_eq!(shorten_iri("http://example.com#property"), "property");
+        assert_eq!(shorten_iri("simple"), "simple");
+
+        let mut node = GraphNode::new("_:x".to_string());
+        assert_eq!(node_color(&node), UNTYPED_COLOR);
+        node.types.push("http://schema.org/Vehicle".to_string());
+        assert_eq!(node_color(&node), node_color(&node.clone()));
+        assert!(PALETTE.contains(&node_color(&node)));
+    }
+}
@@ -4,22 +4,32 @@ use anyhow::{Result, anyhow};
 use iref::IriBuf;
 use json_ld::object::Literal;
 use json_ld::syntax::{Parse, Value};
-use json_ld::{JsonLdProcessor, RemoteDocument, ReqwestLoader};
+use json_ld::{JsonLdProcessor, Loader, RemoteDocument};
 use json_syntax::Value as SyntaxValue;
 use serde_json::{Map, Value as JsonValue};
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use uuid::Uuid;
+
+use crate::identity::label_blank_nodes;
+use crate::parser::{BlockDiagnostic, DiagnosticStage, SOURCE_PROPERTY, StructuredDataBlock};
 
 // Re-export types from the types module
 pub use crate::types::{GraphEdge, GraphNode, KnowledgeGraph};
 
 /// Expand a JSON-LD block into an expanded document
-pub async fn expand_json_ld(
+///
+/// Remote contexts are resolved with `loader`; use
+/// [`ContextLoader`](crate::loader::ContextLoader) to resolve schema.org
+/// without the network.
+pub async fn expand_json_ld<L>(
     base_url: &str,
     raw_json_ld: &str,
-    loader: &mut ReqwestLoader,
-) -> Result<json_ld::ExpandedDocument> {
+    loader: &mut L,
+) -> Result<json_ld::ExpandedDocument>
+where
+    L: Loader<IriBuf>,
+    L::Error: std::fmt::Display,
+{
     let (value, _) = Value::parse_str(raw_json_ld)
         .map_err(|err| anyhow!("failed to parse JSON-LD block: {err}"))?;
 
@@ -38,11 +48,74 @@ pub async fn expand_json_ld(
         .map_err(|err| anyhow!("JSON-LD expansion failed: {err}"))
 }
 
+/// Expand structured data blocks independently
+///
+/// Each block is expanded with its own `@context` (schema.org when it has
+/// none), so blocks using different vocabularies do not affect each other and
+/// a broken block does not empty the whole graph. Ingest the documents into a
+/// single [`GraphBuilder`] to merge nodes that share an `@id`; the blocks that
+/// fail are returned as diagnostics.
+pub async fn expand_structured_data<L>(
+    base_url: &str,
+    blocks: &[StructuredDataBlock],
+    loader: &mut L,
+) -> (Vec<json_ld::ExpandedDocument>, Vec<BlockDiagnostic>)
+where
+    L: Loader<IriBuf>,
+    L::Error: std::fmt::Display,
+{
+    let mut documents = Vec::new();
+    let mut diagnostics = Vec::new();
+    for block in blocks {
+        let expanded = match with_default_context(&block.json) {
+            Ok(document) => expand_json_ld(base_url, &document, loader).await,
+            Err(err) => Err(err),
+        };
+        match expanded {
+            Ok(document) => documents.push(document),
+            Err(err) => {
+                diagnostics.push(block.diagnostic(DiagnosticStage::Expansion, format!("{err:#}")))
+            }
+        }
+    }
+    (documents, diagnostics)
+}
+
+/// Add the schema.org context to a block (or its top-level items) without one
+fn with_default_context(json: &str) -> Result<String> {
+    fn add_context(item: &mut JsonValue) {
+        if let JsonValue::Object(obj) = item
+            && !obj.contains_key("@context")
+        {
+            obj.insert(
+                "@context".to_string(),
+                JsonValue::String("https://schema.org".to_string()),
+            );
+        }
+    }
+
+    let mut value: JsonValue = serde_json::from_str(json)
+        .map_err(|err| anyhow!("failed to parse JSON-LD block: {err}"))?;
+    match &mut value {
+        JsonValue::Array(items) => items.iter_mut().for_each(add_context),
+        item => add_context(item),
+    }
+    Ok(value.to_string())
+}
+
 /// Build a knowledge graph from expanded JSON-LD documents
+///
+/// Several documents can be ingested into one builder: nodes with the same
+/// `@id` are merged, and repeated property values and edges are kept once.
 pub struct GraphBuilder {
     nodes: HashMap<String, GraphNode>,
     edges: Vec<GraphEdge>,
+    seen_edges: HashSet<GraphEdge>,
     processing: HashSet<String>,
+    /// Documents ingested so far; blank node labels are scoped per document
+    documents: usize,
+    /// Unlabeled nodes seen so far
+    unlabeled: usize,
 }
 
 impl GraphBuilder {
@@ -50,11 +123,15 @@ impl GraphBuilder {
         Self {
             nodes: HashMap::new(),
             edges: Vec::new(),
+            seen_edges: HashSet::new(),
             processing: HashSet::new(),
+            documents: 0,
+            unlabeled: 0,
         }
     }
 
     pub fn ingest_document(&mut self, doc: &json_ld::ExpandedDocument) {
+        self.documents += 1;
         for object in doc.iter() {
             self.process_indexed_object(object);
         }
@@ -80,7 +157,7 @@ impl GraphBuilder {
         &mut self,
         node: &json_ld::Node<iref::IriBuf, rdf_types::BlankIdBuf>,
     ) -> String {
-        let node_id = node_identifier(node.id.as_ref());
+        let node_id = self.node_identifier(node.id.as_ref());
         self.nodes
             .entry(node_id.clone())
             .or_insert_with(|| GraphNode::new(node_id.clone()));
@@ -105,7 +182,7 @@ impl GraphBuilder {
         if let Some(graph) = &node.graph {
             for object in graph.iter() {
                 if let Some(target_id) = self.process_indexed_object(object) {
-                    self.edges.push(GraphEdge {
+                    self.add_edge(GraphEdge {
                         from: node_id.clone(),
                         to: target_id,
                         predicate: "@graph".to_string(),
@@ -117,7 +194,7 @@ impl GraphBuilder {
         if let Some(included) = &node.included {
             for indexed_node in included.iter() {
                 let target_id = self.process_node(indexed_node.as_ref());
-                self.edges.push(GraphEdge {
+                self.add_edge(GraphEdge {
                     from: node_id.clone(),
                     to: target_id,
                     predicate: "@included".to_string(),
@@ -130,7 +207,7 @@ impl GraphBuilder {
                 let predicate_str = id_to_string(predicate);
                 for reverse_node in nodes.iter() {
                     let source_id = self.process_node(reverse_node.as_ref());
-                    self.edges.push(GraphEdge {
+                    self.add_edge(GraphEdge {
                         from: source_id,
                         to: node_id.clone(),
                         predicate: predicate_str.clone(),
@@ -141,6 +218,10 @@ impl GraphBuilder {
 
         for (predicate, values) in node.properties.iter() {
             let predicate_str = id_to_string(predicate);
+            if predicate_str == SOURCE_PROPERTY {
+                self.add_sources(&node_id, values);
+                continue;
+            }
             let collected = self.collect_property_values(&node_id, &predicate_str, values);
             if !collected.is_empty() {
                 for value in collected {
@@ -183,7 +264,7 @@ impl GraphBuilder {
             }
             json_ld::Object::Node(node) => {
                 let target_id = self.process_node(node);
-                self.edges.push(GraphEdge {
+                self.add_edge(GraphEdge {
                     from: source_id.to_string(),
                     to: target_id,
                     predicate: predicate.to_string(),
@@ -201,12 +282,43 @@ impl GraphBuilder {
         }
     }
 
+    fn add_sources(
+        &mut self,
+        node_id: &str,
+        values: &[json_ld::IndexedObject<iref::IriBuf, rdf_types::BlankIdBuf>],
+    ) {
+        let entry = self
+            .nodes
+            .get_mut(node_id)
+            .expect("node must exist before adding sources");
+        for value in values {
+            if let json_ld::Object::Value(v) = value.as_ref()
+                && let Some(JsonValue::String(source)) = value_object_to_json(v)
+                && !entry.sources.contains(&source)
+            {
+                entry.sources.push(source);
+            }
+        }
+    }
+
+    fn add_edge(&mut self, edge: GraphEdge) {
+        if self.seen_edges.insert(edge.clone()) {
+            self.edges.push(edge);
+        }
+    }
+
     fn add_property_value(&mut self, node_id: &str, predicate: &str, value: JsonValue) {
         let entry = self
             .nodes
             .get_mut(node_id)
             .expect("node must exist before adding properties");
         match entry.properties.get_mut(predicate) {
+            // The same statement made again, e.g. by another block
+            Some(existing)
+                if *existing == value
+                    || existing
+                        .as_array()
+                        .is_some_and(|values| values.contains(&value)) => {}
             Some(existing) => {
                 if existing.is_array() {
                     if let Some(arr) = existing.as_array_mut() {
@@ -223,18 +335,44 @@ impl GraphBuilder {
         }
     }
 
+    /// Node ID; blank and unlabeled nodes get temporary labels that
+    /// [`into_graph`](Self::into_graph) replaces with content-based ones
+    fn node_identifier(
+        &mut self,
+        id: Option<&json_ld::Id<iref::IriBuf, rdf_types::BlankIdBuf>>,
+    ) -> String {
+        match id {
+            Some(json_ld::Id::Valid(json_ld::ValidId::Iri(iri))) => iri.as_str().to_string(),
+            Some(json_ld::Id::Valid(json_ld::ValidId::Blank(blank))) => {
+                let label = blank.as_str().trim_start_matches("_:");
+                format!("_:d{}-{label}", self.documents)
+            }
+            Some(json_ld::Id::Invalid(raw)) => raw.clone(),
+            None => {
+                self.unlabeled += 1;
+                format!("_:n{}", self.unlabeled)
+            }
+        }
+    }
+
+    /// The built graph, with blank nodes labeled by their content
+    ///
+    /// Identical blank nodes (e.g. the same Organization inlined in several
+    /// blocks) become one node; see [`label_blank_nodes`]. The graph is in
+    /// canonical order (see [`KnowledgeGraph::canonicalize`]), so the same
+    /// page always serializes to the same JSON.
     pub fn into_graph(mut self) -> KnowledgeGraph {
         for node in self.nodes.values_mut() {
             node.types.sort();
             node.types.dedup();
+            node.sources.sort();
         }
-        let mut nodes: Vec<GraphNode> = self.nodes.into_values().collect();
-        nodes.sort_by(|a, b| a.id.cmp(&b.id));
-
-        KnowledgeGraph {
-            nodes,
+        let mut graph = KnowledgeGraph {
+            nodes: self.nodes.into_values().collect(),
             edges: self.edges,
-        }
+        };
+        label_blank_nodes(&mut graph);
+        graph
     }
 }
 
@@ -244,8 +382,19 @@ impl Default for GraphBuilder {
     }
 }
 
+/// JSON for a value object; typed string literals (e.g. `xsd:date`) keep their
+/// datatype as `{"@value", "@type"}` so exports can write typed RDF literals
 fn value_object_to_json(value: &json_ld::Value<iref::IriBuf>) -> Option<JsonValue> {
     match value {
+        json_ld::Value::Literal(Literal::String(s), Some(datatype)) => {
+            let mut obj = Map::new();
+            obj.insert("@value".to_string(), JsonValue::String(s.to_string()));
+            obj.insert(
+                "@type".to_string(),
+                JsonValue::String(datatype.as_str().to_string()),
+            );
+            Some(JsonValue::Object(obj))
+        }
         json_ld::Value::Literal(lit, _) => match lit {
             Literal::Null => Some(JsonValue::Null),
             Literal::Boolean(b) => Some(JsonValue::Bool(*b)),
@@ -298,17 +447,6 @@ fn json_syntax_value_to_json(value: &SyntaxValue) -> JsonValue {
     }
 }
 
-fn node_identifier(id: Option<&json_ld::Id<iref::IriBuf, rdf_types::BlankIdBuf>>) -> String {
-    match id {
-        Some(json_ld::Id::Valid(json_ld::ValidId::Iri(iri))) => iri.as_str().to_string(),
-        Some(json_ld::Id::Valid(json_ld::ValidId::Blank(blank))) => {
-            format!("_:{}", blank.as_str())
-        }
-        Some(json_ld::Id::Invalid(raw)) => raw.clone(),
-        None => format!("_:{}", Uuid::new_v4()),
-    }
-}
-
 fn id_to_string(id: &json_ld::Id<iref::IriBuf, rdf_types::BlankIdBuf>) -> String {
     match id {
         json_ld::Id::Valid(json_ld::ValidId::Iri(iri)) => iri.as_str().to_string(),
@@ -318,3 +456,173 @@ fn id_to_string(id: &json_ld::Id<iref::IriBuf, rdf_types::BlankIdBuf>) -> String
         json_ld::Id::Invalid(raw) => raw.clone(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::ContextLoader;
+    use crate::parser::{StructuredData, combine_json_ld_blocks};
+
+    #[tokio::test]
+    async fn test_expansion_errors_are_isolated_per_block() {
+        let html = r#"
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "Organization", "name": "Pon"}
+            </script>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "Product", "@id": 5}
+            </script>
+        "#;
+        let data = crate::parser::extract_structured_data(html, "https://example.com/").unwrap();
+        assert_eq!(data.blocks.len(), 2);
+
+        let mut loader = ContextLoader::offline();
+        let (documents, diagnostics) =
+            expand_structured_data("https://example.com/", &data.blocks, &mut loader).await;
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].index, 1);
+        assert_eq!(diagnostics[0].stage, DiagnosticStage::Expansion);
+        assert!(diagnostics[0].offset.is_some());
+
+        let mut builder = GraphBuilder::new();
+        builder.ingest_document(&documents[0]);
+        let graph = builder.into_graph();
+        assert_eq!(graph.nodes.len(), 1);
+        assert_eq!(graph.nodes[0].types, vec!["http://schema.org/Organization"]);
+
+        let data =
+            StructuredData::from_json_ld(r#"{"@context": "https://schema.org", "@type": "Thing"}"#);
+        let (documents, diagnostics) =
+            expand_structured_data("https://example.com/", &data.blocks, &mut loader).await;
+        assert_eq!((documents.len(), diagnostics.len()), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_blocks_expand_with_their_own_context_and_merge_by_id() {
+        let html = r#"
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "Product", "@id": "https://example.com/bike",
+             "name": "Bike", "brand": {"@id": "https://example.com/#brand"}}
+            </script>
+            <script type="application/ld+json">
+            {"@context": {"@vocab": "https://vocab.example/"}, "@type": "Thing",
+             "@id": "https://example.com/bike", "name": "Internal name"}
+            </script>
+            <script type="application/ld+json">
+            [{"@type": "Product", "@id": "https://example.com/bike", "name": "Bike",
+              "brand": {"@id": "https://example.com/#brand"}}]
+            </script>
+        "#;
+        let data = crate::parser::extract_structured_data(html, "https://example.com/").unwrap();
+
+        let mut loader = ContextLoader::offline();
+        let (documents, diagnostics) =
+            expand_structured_data("https://example.com/", &data.blocks, &mut loader).await;
+        assert_eq!((documents.len(), diagnostics.len()), (3, 0));
+
+        let mut builder = GraphBuilder::new();
+        for document in &documents {
+            builder.ingest_document(document);
+        }
+        let graph = builder.into_graph();
+
+        let bike = graph
+            .nodes
+            .iter()
+            .find(|node| node.id == "https://example.com/bike")
+            .unwrap();
+        assert_eq!(
+            bike.types,
+            vec!["http://schema.org/Product", "https://vocab.example/Thing"]
+        );
+        // The custom vocabulary does not leak into schema.org terms
+        assert_eq!(bike.properties["http://schema.org/name"], "Bike");
+        assert_eq!(
+            bike.properties["https://vocab.example/name"],
+            "Internal name"
+        );
+        // Repeated statements are kept once
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .filter(|edge| edge.predicate == "http://schema.org/brand")
+                .count(),
+            1
+        );
+
+        // The combined @graph document keeps each block's context
+        let combined = combine_json_ld_blocks(&data.json_blocks()).unwrap();
+        let expanded = expand_json_ld("https://example.com/", &combined, &mut loader)
+            .await
+            .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder.ingest_document(&expanded);
+        let graph = builder.into_graph();
+        let bike = graph
+            .nodes
+            .iter()
+            .find(|node| node.id == "https://example.com/bike")
+            .unwrap();
+        assert_eq!(bike.properties["http://schema.org/name"], "Bike");
+        assert_eq!(
+            bike.properties["https://vocab.example/name"],
+            "Internal name"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inline_blank_nodes_are_merged_with_stable_labels() {
+        let publisher = r#""publisher": {"@type": "Organization", "name": "Pon"}"#;
+        let html = format!(
+            r#"
+            <script type="application/ld+json">
+            {{"@context": "https://schema.org", "@type": "Article", "@id": "https://example.com/a", {publisher}}}
+            </script>
+            <script type="application/ld+json">
+            {{"@context": "https://schema.org", "@type": "Article", "@id": "https://example.com/b", {publisher}}}
+            </script>
+            <script type="application/ld+json">
+            {{"@context": "https://schema.org", "@type": "WebPage", {publisher}}}
+            </script>
+            "#
+        );
+        let data = crate::parser::extract_structured_data(&html, "https://example.com/").unwrap();
+
+        let mut loader = ContextLoader::offline();
+        let mut build = async || {
+            let (documents, _) =
+                expand_structured_data("https://example.com/", &data.blocks, &mut loader).await;
+            let mut builder = GraphBuilder::new();
+            for document in &documents {
+                builder.ingest_document(document);
+            }
+            builder.into_graph()
+        };
+        let graph = build().await;
+
+        let publishers: Vec<&GraphNode> = graph
+            .nodes
+            .iter()
+            .filter(|node| node.types == vec!["http://schema.org/Organization"])
+            .collect();
+        assert_eq!(publishers.len(), 1);
+        assert!(publishers[0].id.starts_with("_:b"));
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .filter(|edge| edge.to == publishers[0].id)
+                .count(),
+            3
+        );
+
+        let again = build().await;
+        let ids = |graph: &KnowledgeGraph| -> Vec<String> {
+            graph.nodes.iter().map(|node| node.id.clone()).collect()
+        };
+        assert_eq!(ids(&graph), ids(&again));
+    }
+}
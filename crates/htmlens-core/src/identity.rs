@@ -0,0 +1,619 @@
+//! Node identity in the knowledge graph
+//!
+//! Pages repeat the same entity all the time: the publisher Organization
+//! inlined in every block, a Product described once in JSON-LD and once in
+//! microdata, a brand referenced by URL. Two passes turn those copies into one
+//! node:
+//!
+//! - [`label_blank_nodes`] gives every blank node a label derived from its
+//!   content (types, properties and outgoing edges), so labels are stable
+//!   between runs and identical blank nodes collapse into one.
+//! - [`resolve_identities`] additionally merges nodes that share a `url`,
+//!   `sameAs`, GTIN or SKU, as long as they do not contradict each other.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+
+use crate::types::{GraphEdge, GraphNode, KnowledgeGraph};
+
+const SCHEMA_PREFIXES: &[&str] = &["http://schema.org/", "https://schema.org/"];
+
+/// Properties whose values are URLs identifying the node itself
+const IRI_PROPERTIES: &[&str] = &["url", "sameAs"];
+
+/// Product identifiers, all compared as GTINs
+const GTIN_PROPERTIES: &[&str] = &["gtin", "gtin8", "gtin12", "gtin13", "gtin14"];
+
+/// Replace blank node IDs with labels derived from the node's content
+///
+/// The label is a hash of the node's types, properties and outgoing edges
+/// (with blank targets represented by their own labels). Blank nodes with the
+/// same content get the same label and are merged. The graph is left in
+/// canonical order (see [`KnowledgeGraph::canonicalize`]).
+pub fn label_blank_nodes(graph: &mut KnowledgeGraph) {
+    let mut labeler = BlankLabeler::new(graph);
+    let blank_ids: Vec<String> = graph
+        .nodes
+        .iter()
+        .filter(|node| is_blank(&node.id))
+        .map(|node| node.id.clone())
+        .collect();
+
+    let mapping: HashMap<String, String> = blank_ids
+        .into_iter()
+        .map(|id| {
+            let label = labeler.label(&id);
+            (id, label)
+        })
+        .collect();
+    rename_nodes(graph, &mapping);
+    graph.canonicalize();
+}
+
+/// Merge nodes that describe the same entity
+///
+/// Nodes are candidates when they share a `url`, `sameAs` or `@id` IRI, a
+/// GTIN (leading zeros ignored) or a SKU. They are only merged when their
+/// types overlap (or one of them is untyped) and no property they both have
+/// takes different values, so e.g. two variants or offers that share the
+/// page URL stay apart. IRIs are preferred over blank nodes as the merged ID.
+/// Blank nodes are relabeled afterwards (see [`label_blank_nodes`]).
+///
+/// Returns the number of nodes merged into another one.
+pub fn resolve_identities(graph: &mut KnowledgeGraph) -> usize {
+    let count = graph.nodes.len();
+    let position: HashMap<&str, usize> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.id.as_str(), index))
+        .collect();
+
+    // Identifier key -> nodes carrying it, in a deterministic order
+    let mut candidates: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, node) in graph.nodes.iter().enumerate() {
+        if !candidates_from(node) {
+            continue;
+        }
+        if !is_blank(&node.id) {
+            candidates
+                .entry(format!("iri:{}", node.id))
+                .or_default()
+                .push(index);
+        }
+        for (predicate, value) in &node.properties {
+            for key in identifier_keys(predicate, value) {
+                candidates.entry(key).or_default().push(index);
+            }
+        }
+    }
+    for edge in &graph.edges {
+        if let Some(&index) = position.get(edge.from.as_str())
+            && candidates_from(&graph.nodes[index])
+            && let Some(key) = edge_identifier_key(edge)
+        {
+            candidates.entry(key).or_default().push(index);
+        }
+    }
+
+    let mut groups = Groups::new(graph.nodes.clone());
+    for indices in candidates.values() {
+        for (offset, &a) in indices.iter().enumerate() {
+            for &b in &indices[offset + 1..] {
+                groups.union(a, b);
+            }
+        }
+    }
+
+    let mapping: HashMap<String, String> = (0..count)
+        .map(|index| {
+            let root = groups.find(index);
+            (
+                graph.nodes[index].id.clone(),
+                groups.merged[root].id.clone(),
+            )
+        })
+        .filter(|(from, to)| from != to)
+        .collect();
+    let merged = mapping.len();
+
+    let roots: Vec<usize> = (0..count)
+        .filter(|&index| groups.find(index) == index)
+        .collect();
+    graph.nodes = roots
+        .into_iter()
+        .map(|index| std::mem::replace(&mut groups.merged[index], GraphNode::new(String::new())))
+        .collect();
+    rename_nodes(graph, &mapping);
+    label_blank_nodes(graph);
+    merged
+}
+
+/// Identifier keys of `node`: its `url` and `sameAs` IRIs (also those given
+/// as references in `edges`), GTINs and SKUs
+///
+/// These are the keys [`resolve_identities`] merges on, so two nodes sharing
+/// one most likely describe the same entity.
+pub fn node_identifier_keys(node: &GraphNode, edges: &[GraphEdge]) -> BTreeSet<String> {
+    let properties = node
+        .properties
+        .iter()
+        .flat_map(|(predicate, value)| identifier_keys(predicate, value));
+    let references = edges
+        .iter()
+        .filter(|edge| edge.from == node.id)
+        .filter_map(edge_identifier_key);
+    properties.chain(references).collect()
+}
+
+/// Bare references (e.g. the target of a `url` edge) describe nothing to merge
+fn candidates_from(node: &GraphNode) -> bool {
+    !node.types.is_empty() || !node.properties.is_empty()
+}
+
+fn is_blank(id: &str) -> bool {
+    id.starts_with("_:")
+}
+
+fn schema_local_name(predicate: &str) -> Option<&str> {
+    SCHEMA_PREFIXES
+        .iter()
+        .find_map(|prefix| predicate.strip_prefix(prefix))
+}
+
+fn identifier_keys(predicate: &str, value: &JsonValue) -> Vec<String> {
+    let Some(name) = schema_local_name(predicate) else {
+        return Vec::new();
+    };
+
+    literal_strings(value)
+        .into_iter()
+        .filter_map(|text| {
+            let text = text.trim();
+            if text.is_empty() {
+                None
+            } else if IRI_PROPERTIES.contains(&name) {
+                Some(format!("iri:{text}"))
+            } else if GTIN_PROPERTIES.contains(&name) {
+                let digits: String = text.chars().filter(char::is_ascii_digit).collect();
+                let gtin = digits.trim_start_matches('0');
+                (!gtin.is_empty()).then(|| format!("gtin:{gtin}"))
+            } else if name == "sku" {
+                Some(format!("sku:{text}"))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn edge_identifier_key(edge: &GraphEdge) -> Option<String> {
+    let name = schema_local_name(&edge.predicate)?;
+    IRI_PROPERTIES
+        .contains(&name)
+        .then(|| format!("iri:{}", edge.to))
+}
+
+/// Plain string (or `@value`) values of a property
+fn literal_strings(value: &JsonValue) -> Vec<&str> {
+    match value {
+        JsonValue::String(text) => vec![text.as_str()],
+        JsonValue::Array(values) => values.iter().flat_map(literal_strings).collect(),
+        JsonValue::Object(obj) => obj
+            .get("@value")
+            .and_then(JsonValue::as_str)
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Union-find over the graph's nodes, keeping the merged node at each root
+struct Groups {
+    parent: Vec<usize>,
+    merged: Vec<GraphNode>,
+}
+
+impl Groups {
+    fn new(nodes: Vec<GraphNode>) -> Self {
+        Self {
+            parent: (0..nodes.len()).collect(),
+            merged: nodes,
+        }
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        let parent = self.parent[index];
+        if parent == index {
+            return index;
+        }
+        let root = self.find(parent);
+        self.parent[index] = root;
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b || !compatible(&self.merged[a], &self.merged[b]) {
+            return;
+        }
+
+        // IRIs win over blank nodes, then the smallest ID
+        let key = |node: &GraphNode| (is_blank(&node.id), node.id.clone());
+        let (root, child) = if key(&self.merged[a]) <= key(&self.merged[b]) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let other = std::mem::replace(&mut self.merged[child], GraphNode::new(String::new()));
+        merge_node(&mut self.merged[root], other);
+        self.parent[child] = root;
+    }
+}
+
+fn compatible(a: &GraphNode, b: &GraphNode) -> bool {
+    let types_overlap =
+        a.types.is_empty() || b.types.is_empty() || a.types.iter().any(|ty| b.types.contains(ty));
+
+    types_overlap
+        && a.properties.iter().all(|(predicate, value)| {
+            b.properties.get(predicate).is_none_or(|other| {
+                let values = values_of(value);
+                values_of(other).iter().any(|v| values.contains(v))
+            })
+        })
+}
+
+fn values_of(value: &JsonValue) -> Vec<&JsonValue> {
+    match value {
+        JsonValue::Array(values) => values.iter().collect(),
+        value => vec![value],
+    }
+}
+
+/// Merge `other`'s types, sources and property values into `node`
+fn merge_node(node: &mut GraphNode, other: GraphNode) {
+    for ty in other.types {
+        if !node.types.contains(&ty) {
+            node.types.push(ty);
+        }
+    }
+    node.types.sort();
+
+    for source in other.sources {
+        if !node.sources.contains(&source) {
+            node.sources.push(source);
+        }
+    }
+    node.sources.sort();
+
+    for (predicate, value) in other.properties {
+        match node.properties.get_mut(&predicate) {
+            Some(existing) => {
+                let mut values: Vec<JsonValue> = values_of(existing).into_iter().cloned().collect();
+                for value in values_of(&value) {
+                    if !values.contains(value) {
+                        values.push(value.clone());
+                    }
+                }
+                *existing = if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    JsonValue::Array(values)
+                };
+            }
+            None => {
+                node.properties.insert(predicate, value);
+            }
+        }
+    }
+}
+
+/// Rename node IDs, merging nodes that end up with the same ID and rewriting
+/// edges; edges that become duplicates or self-loops are dropped
+fn rename_nodes(graph: &mut KnowledgeGraph, mapping: &HashMap<String, String>) {
+    if mapping.is_empty() {
+        return;
+    }
+    let rename = |id: &str| mapping.get(id).cloned().unwrap_or_else(|| id.to_string());
+
+    let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
+    for mut node in std::mem::take(&mut graph.nodes) {
+        node.id = rename(&node.id);
+        match nodes.get_mut(&node.id) {
+            Some(existing) => merge_node(existing, node),
+            None => {
+                nodes.insert(node.id.clone(), node);
+            }
+        }
+    }
+    graph.nodes = nodes.into_values().collect();
+
+    let mut seen = HashSet::new();
+    graph.edges = std::mem::take(&mut graph.edges)
+        .into_iter()
+        .filter_map(|edge| {
+            let renamed = GraphEdge {
+                from: rename(&edge.from),
+                to: rename(&edge.to),
+                predicate: edge.predicate.clone(),
+            };
+            let collapsed = renamed.from == renamed.to && edge.from != edge.to;
+            (!collapsed && seen.insert(renamed.clone())).then_some(renamed)
+        })
+        .collect();
+}
+
+/// Computes content labels for blank nodes, following blank edge targets
+///
+/// A blank node reached again while its own label is being computed is
+/// hashed as `_:cycle`. Labels computed under such a cut depend on where the
+/// walk started, so they are not cached: every node in a cycle is labeled by
+/// a walk starting from itself, whatever order the nodes are labeled in.
+struct BlankLabeler<'a> {
+    nodes: HashMap<&'a str, &'a GraphNode>,
+    outgoing: HashMap<&'a str, Vec<&'a GraphEdge>>,
+    labels: HashMap<String, String>,
+    visiting: HashSet<String>,
+}
+
+impl<'a> BlankLabeler<'a> {
+    fn new(graph: &'a KnowledgeGraph) -> Self {
+        let mut outgoing: HashMap<&str, Vec<&GraphEdge>> = HashMap::new();
+        for edge in &graph.edges {
+            outgoing.entry(edge.from.as_str()).or_default().push(edge);
+        }
+        Self {
+            nodes: graph
+                .nodes
+                .iter()
+                .map(|node| (node.id.as_str(), node))
+                .collect(),
+            outgoing,
+            labels: HashMap::new(),
+            visiting: HashSet::new(),
+        }
+    }
+
+    fn label(&mut self, id: &str) -> String {
+        self.walk(id).0
+    }
+
+    /// The label of `id`, and whether the walk cut a cycle
+    fn walk(&mut self, id: &str) -> (String, bool) {
+        if let Some(label) = self.labels.get(id) {
+            return (label.clone(), false);
+        }
+        // Cyclic blank references are hashed as a placeholder
+        if !self.visiting.insert(id.to_string()) {
+            return ("_:cycle".to_string(), true);
+        }
+
+        let mut hasher = Sha256::new();
+        if let Some(node) = self.nodes.get(id).copied() {
+            let mut types = node.types.clone();
+            types.sort();
+            for ty in types {
+                hasher.update(format!("type\t{ty}\n"));
+            }
+            let properties: BTreeMap<&String, &JsonValue> = node.properties.iter().collect();
+            for (predicate, value) in properties {
+                hasher.update(format!(
+                    "property\t{predicate}\t{}\n",
+                    canonical_json(value)
+                ));
+            }
+        }
+
+        let edges: Vec<(String, String)> = self
+            .outgoing
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|edge| (edge.predicate.clone(), edge.to.clone()))
+            .collect();
+        let mut cyclic = false;
+        let mut edges: Vec<(String, String)> = edges
+            .into_iter()
+            .map(|(predicate, to)| {
+                let target = if is_blank(&to) {
+                    let (label, cut) = self.walk(&to);
+                    cyclic |= cut;
+                    label
+                } else {
+                    to
+                };
+                (predicate, target)
+            })
+            .collect();
+        edges.sort();
+        edges.dedup();
+        for (predicate, target) in edges {
+            hasher.update(format!("edge\t{predicate}\t{target}\n"));
+        }
+
+        let digest = hasher.finalize();
+        let hex: String = digest[..8]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let label = format!("_:b{hex}");
+
+        self.visiting.remove(id);
+        if !cyclic {
+            self.labels.insert(id.to_string(), label.clone());
+        }
+        (label, cyclic)
+    }
+}
+
+/// JSON with object keys sorted at every level
+pub(crate) fn canonical_json(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Object(obj) => {
+            let entries: BTreeMap<&String, &JsonValue> = obj.iter().collect();
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    format!(
+                        "{}:{}",
+                        JsonValue::from(key.as_str()),
+                        canonical_json(value)
+                    )
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        JsonValue::Array(values) => {
+            let items: Vec<String> = values.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, ty: &str, properties: &[(&str, JsonValue)]) -> GraphNode {
+        let mut node = GraphNode::new(id.to_string());
+        node.types.push(format!("http://schema.org/{ty}"));
+        for (name, value) in properties {
+            node.properties
+                .insert(format!("http://schema.org/{name}"), value.clone());
+        }
+        node
+    }
+
+    fn edge(from: &str, predicate: &str, to: &str) -> GraphEdge {
+        GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            predicate: format!("http://schema.org/{predicate}"),
+        }
+    }
+
+    fn graph_with_inline_publishers(first: &str, second: &str) -> KnowledgeGraph {
+        let publisher = |id: &str| node(id, "Organization", &[("name", "Pon".into())]);
+        KnowledgeGraph {
+            nodes: vec![
+                node("https://example.com/a", "Article", &[]),
+                node("https://example.com/b", "Article", &[]),
+                publisher(first),
+                publisher(second),
+            ],
+            edges: vec![
+                edge("https://example.com/a", "publisher", first),
+                edge("https://example.com/b", "publisher", second),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_identical_blank_nodes_get_one_stable_label() {
+        let mut graph = graph_with_inline_publishers("_:x1", "_:x2");
+        label_blank_nodes(&mut graph);
+
+        assert_eq!(graph.nodes.len(), 3);
+        let label = graph.edges[0].to.clone();
+        assert!(label.starts_with("_:b"), "{label}");
+        assert_eq!(graph.edges[1].to, label);
+
+        // Labels do not depend on the IDs the builder happened to assign
+        let mut other = graph_with_inline_publishers("_:n7", "_:n9");
+        label_blank_nodes(&mut other);
+        assert_eq!(other.edges[0].to, label);
+    }
+
+    #[test]
+    fn test_cyclic_blank_nodes_get_order_independent_labels() {
+        let labels = |reverse: bool| {
+            let mut nodes = vec![
+                node("_:a", "Person", &[("name", "Ann".into())]),
+                node("_:b", "Person", &[("name", "Bob".into())]),
+            ];
+            if reverse {
+                nodes.reverse();
+            }
+            let mut graph = KnowledgeGraph {
+                nodes,
+                edges: vec![edge("_:a", "knows", "_:b"), edge("_:b", "knows", "_:a")],
+            };
+            label_blank_nodes(&mut graph);
+            graph
+                .edges
+                .iter()
+                .map(|edge| (edge.from.clone(), edge.to.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let forward = labels(false);
+        assert_eq!(forward, labels(true));
+        assert_eq!(forward.len(), 2);
+        assert_ne!(forward[0].0, forward[0].1);
+        assert!(forward.iter().all(|(from, _)| from.starts_with("_:b")));
+    }
+
+    #[test]
+    fn test_resolve_identities_merges_shared_identifiers() {
+        let mut graph = KnowledgeGraph {
+            nodes: vec![
+                node(
+                    "https://example.com/bike",
+                    "Product",
+                    &[("name", "Bike".into()), ("sku", "B-1".into())],
+                ),
+                node(
+                    "_:m1",
+                    "Product",
+                    &[("sku", "B-1".into()), ("gtin13", "0871234567890".into())],
+                ),
+                node(
+                    "_:r1",
+                    "Product",
+                    &[("gtin", "871234567890".into()), ("color", "Red".into())],
+                ),
+                node(
+                    "_:o1",
+                    "Offer",
+                    &[
+                        ("url", "https://example.com/bike".into()),
+                        ("price", "10".into()),
+                    ],
+                ),
+                node(
+                    "_:o2",
+                    "Offer",
+                    &[
+                        ("url", "https://example.com/bike".into()),
+                        ("price", "12".into()),
+                    ],
+                ),
+            ],
+            edges: vec![edge("_:m1", "brand", "https://example.com/#brand")],
+        };
+
+        let merged = resolve_identities(&mut graph);
+        assert_eq!(merged, 2);
+
+        // The SKU and GTIN copies merge into the IRI node
+        let bike = graph
+            .nodes
+            .iter()
+            .find(|node| node.id == "https://example.com/bike")
+            .unwrap();
+        assert_eq!(bike.properties["http://schema.org/color"], "Red");
+        assert_eq!(graph.edges[0].from, bike.id);
+
+        // Offers sharing the page URL but not the price stay apart
+        let offers = graph
+            .nodes
+            .iter()
+            .filter(|node| node.types == vec!["http://schema.org/Offer"])
+            .count();
+        assert_eq!(offers, 2);
+    }
+}
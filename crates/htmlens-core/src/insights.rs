@@ -0,0 +1,1658 @@
+//! Product and entity summaries of a knowledge graph
+//!
+//! [`GraphInsights`] condenses a [`KnowledgeGraph`] into the summaries the CLI
+//! and worker report on: product groups with their variants, prices and
+//! availability, organizations, web pages, breadcrumbs and other entities.
+//! Graphs from full expansion use schema.org IRIs; without expansion,
+//! [`GraphInsights::from_json_ld`] reads compact schema.org JSON-LD directly.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::export::{SCHEMA_NS, shorten_iri};
+use crate::types::{GraphEdge, GraphNode, KnowledgeGraph};
+
+/// Summaries of the products and entities in a graph
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GraphInsights {
+    pub product_groups: Vec<ProductGroupSummary>,
+    pub organizations: Vec<OrganizationSummary>,
+    pub web_pages: Vec<WebPageSummary>,
+    pub breadcrumbs: Vec<BreadcrumbSummary>,
+    pub other_entities: Vec<EntitySummary>,
+    /// One line per relationship or entity type found, e.g. "Product → Offer (3)"
+    pub graph_summary: Vec<String>,
+    pub data_downloads: Vec<DataDownloadEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OrganizationSummary {
+    pub name: Option<String>,
+    pub logo: Option<String>,
+    pub telephone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<AddressSummary>,
+    pub rating: Option<RatingSummary>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AddressSummary {
+    pub street: Option<String>,
+    pub locality: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RatingSummary {
+    pub rating_value: Option<String>,
+    pub review_count: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebPageSummary {
+    pub url: Option<String>,
+    pub speakable: Option<String>,
+}
+
+/// An entity of a type without a dedicated summary, with its literal
+/// properties keyed by short name
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EntitySummary {
+    pub id: String,
+    pub entity_type: String,
+    pub properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BreadcrumbSummary {
+    pub items: Vec<BreadcrumbItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BreadcrumbItem {
+    pub position: usize,
+    pub name: String,
+    pub url: String,
+}
+
+/// A `ProductGroup` and its variants, or a standalone `Product` as a group
+/// of one
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProductGroupSummary {
+    pub name: Option<String>,
+    pub product_group_id: Option<String>,
+    pub brand: Option<String>,
+    pub varies_by: Vec<String>,
+    /// Properties that appear directly on Product nodes
+    pub direct_properties: Vec<String>,
+    /// Properties shared by all variants (not in variesBy)
+    pub common_properties: BTreeMap<String, String>,
+    /// Variants, sorted by SKU
+    pub variants: Vec<VariantSummary>,
+    pub total_variants: usize,
+    pub price_stats: Option<PriceStats>,
+    /// Number of variants per availability (e.g. "InStock")
+    pub availability_counts: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PriceStats {
+    pub min: f64,
+    pub max: f64,
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VariantSummary {
+    pub name: Option<String>,
+    pub sku: Option<String>,
+    pub gtin: Option<String>,
+    pub mpn: Option<String>,
+    pub color: Option<String>,
+    pub size: Option<String>,
+    pub weight: Option<String>,
+    pub material: Option<String>,
+    pub model: Option<String>,
+    pub category: Option<String>,
+    pub width: Option<String>,
+    pub height: Option<String>,
+    pub depth: Option<String>,
+    pub frame_shape: Option<String>,
+    pub battery: Option<String>,
+    /// Price formatted with [`format_price`], or as written when not numeric
+    pub price_display: Option<String>,
+    pub price_numeric: Option<f64>,
+    pub price_currency: Option<String>,
+    /// Availability without the schema.org prefix, e.g. "InStock"
+    pub availability: Option<String>,
+    /// Other literal properties and `additionalProperty` values
+    pub additional: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataDownloadEntry {
+    pub content_url: String,
+    pub encoding_format: Option<String>,
+    pub license: Option<String>,
+}
+
+type OfferInfo = (Option<String>, Option<f64>, Option<String>, Option<String>);
+
+fn predicate_matches(predicate: &str, name: &str) -> bool {
+    predicate.ends_with(name)
+}
+
+fn build_adjacency<'a>(graph: &'a KnowledgeGraph) -> HashMap<&'a str, Vec<&'a GraphEdge>> {
+    let mut map: HashMap<&'a str, Vec<&'a GraphEdge>> = HashMap::new();
+    for edge in &graph.edges {
+        map.entry(edge.from.as_str()).or_default().push(edge);
+    }
+    map
+}
+
+fn property_list(node: &GraphNode, keys: &[&str]) -> Vec<String> {
+    for key in keys {
+        if let Some(value) = resolve_node_property(&node.properties, key) {
+            return match value {
+                JsonValue::Array(items) => items.iter().map(json_value_display).collect(),
+                _ => vec![json_value_display(value)],
+            };
+        }
+    }
+    Vec::new()
+}
+
+impl From<&KnowledgeGraph> for GraphInsights {
+    fn from(graph: &KnowledgeGraph) -> Self {
+        let nodes_map: HashMap<&str, &GraphNode> = graph
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node))
+            .collect();
+        let adjacency = build_adjacency(graph);
+
+        let mut insights = GraphInsights::default();
+        let mut property_names = BTreeSet::new();
+        let mut direct_properties = BTreeSet::new();
+        let mut offer_count = 0usize;
+
+        // Process ALL ProductGroups (not just the first one)
+        for product_group in graph
+            .nodes
+            .iter()
+            .filter(|node| has_schema_type(node, "ProductGroup"))
+        {
+            let mut pg_summary = ProductGroupSummary {
+                name: property_text(product_group, &["https://schema.org/name", "name"]),
+                product_group_id: property_text(
+                    product_group,
+                    &[
+                        "https://schema.org/productGroupID",
+                        "http://schema.org/productGroupID",
+                        "productGroupID",
+                    ],
+                ),
+                varies_by: property_list(
+                    product_group,
+                    &[
+                        "https://schema.org/variesBy",
+                        "http://schema.org/variesBy",
+                        "variesBy",
+                    ],
+                ),
+                ..Default::default()
+            };
+
+            let mut brand_added = false;
+
+            if let Some(edges) = adjacency.get(product_group.id.as_str()) {
+                for edge in edges {
+                    let target_id = edge.to.as_str();
+                    if predicate_matches(&edge.predicate, "brand") {
+                        if let Some(brand_node) = nodes_map.get(target_id) {
+                            pg_summary.brand =
+                                property_text(brand_node, &["https://schema.org/name", "name"]);
+                            if !brand_added {
+                                if let Some(brand_name) = pg_summary.brand.as_ref() {
+                                    insights
+                                        .graph_summary
+                                        .push(format!("ProductGroup → Brand ({brand_name})"));
+                                } else {
+                                    insights
+                                        .graph_summary
+                                        .push("ProductGroup → Brand".to_string());
+                                }
+                                brand_added = true;
+                            }
+                        }
+                    } else if predicate_matches(&edge.predicate, "hasVariant")
+                        && let Some(product_node) = nodes_map.get(target_id)
+                    {
+                        let variant = summarize_variant(
+                            product_node,
+                            &adjacency,
+                            &nodes_map,
+                            &mut property_names,
+                            &mut direct_properties,
+                            &mut offer_count,
+                        );
+                        if let Some(status) = variant.availability.as_ref() {
+                            *pg_summary
+                                .availability_counts
+                                .entry(status.clone())
+                                .or_insert(0) += 1;
+                        }
+                        if let Some(price) = variant.price_numeric {
+                            pg_summary.price_stats = match pg_summary.price_stats.take() {
+                                Some(mut stats) => {
+                                    if price < stats.min {
+                                        stats.min = price;
+                                    }
+                                    if price > stats.max {
+                                        stats.max = price;
+                                    }
+                                    if stats.currency.is_none() {
+                                        stats.currency = variant.price_currency.clone();
+                                    }
+                                    Some(stats)
+                                }
+                                None => Some(PriceStats {
+                                    min: price,
+                                    max: price,
+                                    currency: variant.price_currency.clone(),
+                                }),
+                            };
+                        }
+                        pg_summary.total_variants += 1;
+                        pg_summary.variants.push(variant);
+                    }
+                }
+            }
+
+            if pg_summary.total_variants > 0 {
+                insights.graph_summary.push(format!(
+                    "ProductGroup → Product ({})",
+                    pg_summary.total_variants
+                ));
+            }
+
+            // Sort variants within this product group
+            pg_summary.variants.sort_by(|a, b| a.sku.cmp(&b.sku));
+
+            // Extract common properties (properties not in variesBy) from the first variant
+            if let Some(first_variant) = pg_summary.variants.first() {
+                // Get the first variant's product node to extract direct properties
+                if let Some(edges) = adjacency.get(product_group.id.as_str()) {
+                    for edge in edges {
+                        if predicate_matches(&edge.predicate, "hasVariant")
+                            && let Some(variant_node) = nodes_map.get(edge.to.as_str())
+                        {
+                            // Check if this is the first variant by SKU
+                            let variant_sku = property_text(
+                                variant_node,
+                                &["https://schema.org/sku", "http://schema.org/sku", "sku"],
+                            );
+                            if variant_sku == first_variant.sku {
+                                pg_summary.common_properties = extract_common_properties(
+                                    variant_node,
+                                    &adjacency,
+                                    &nodes_map,
+                                    &pg_summary.varies_by,
+                                );
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Store direct_properties from variants
+            pg_summary.direct_properties = direct_properties.iter().cloned().collect();
+
+            insights.product_groups.push(pg_summary);
+        }
+
+        // If no ProductGroups found, check for standalone Products
+        if insights.product_groups.is_empty()
+            && let Some(product_node) = graph
+                .nodes
+                .iter()
+                .find(|node| has_schema_type(node, "Product"))
+        {
+            let mut pg_summary = ProductGroupSummary {
+                name: property_text(
+                    product_node,
+                    &["https://schema.org/name", "http://schema.org/name", "name"],
+                ),
+                product_group_id: property_text(
+                    product_node,
+                    &[
+                        "https://schema.org/productID",
+                        "http://schema.org/productID",
+                        "productID",
+                        "https://schema.org/sku",
+                        "http://schema.org/sku",
+                        "sku",
+                    ],
+                ),
+                ..Default::default()
+            };
+
+            if let Some(edges) = adjacency.get(product_node.id.as_str()) {
+                for edge in edges {
+                    let target_id = edge.to.as_str();
+                    if predicate_matches(&edge.predicate, "brand")
+                        && let Some(brand_node) = nodes_map.get(target_id)
+                    {
+                        pg_summary.brand = property_text(
+                            brand_node,
+                            &["https://schema.org/name", "http://schema.org/name", "name"],
+                        );
+                        if let Some(brand_name) = pg_summary.brand.as_ref() {
+                            insights
+                                .graph_summary
+                                .push(format!("Product → Brand ({brand_name})"));
+                        } else {
+                            insights.graph_summary.push("Product → Brand".to_string());
+                        }
+                    }
+                }
+            }
+
+            let variant = summarize_variant(
+                product_node,
+                &adjacency,
+                &nodes_map,
+                &mut property_names,
+                &mut direct_properties,
+                &mut offer_count,
+            );
+
+            if let Some(status) = variant.availability.as_ref() {
+                *pg_summary
+                    .availability_counts
+                    .entry(status.clone())
+                    .or_insert(0) += 1;
+            }
+
+            if let Some(price) = variant.price_numeric {
+                pg_summary.price_stats = Some(PriceStats {
+                    min: price,
+                    max: price,
+                    currency: variant.price_currency.clone(),
+                });
+            }
+
+            pg_summary.total_variants = 1;
+            pg_summary.direct_properties = direct_properties.iter().cloned().collect();
+            pg_summary.variants.push(variant);
+            insights.product_groups.push(pg_summary);
+        }
+
+        if offer_count > 0 {
+            insights
+                .graph_summary
+                .push(format!("Product → Offer ({offer_count})"));
+        }
+
+        if !property_names.is_empty() {
+            let joined = property_names.into_iter().collect::<Vec<_>>().join(", ");
+            insights
+                .graph_summary
+                .push(format!("Product → PropertyValue ({joined})"));
+        }
+
+        if !direct_properties.is_empty() {
+            for prop in direct_properties {
+                insights
+                    .graph_summary
+                    .push(format!("Product → {}", title_case(&prop)));
+            }
+        }
+
+        // Extract Organization entities
+        for node in &graph.nodes {
+            if has_schema_type(node, "Organization") {
+                insights
+                    .organizations
+                    .push(extract_organization(node, &adjacency, &nodes_map));
+                insights.graph_summary.push("Organization".to_string());
+            }
+        }
+
+        // Extract WebPage entities
+        for node in &graph.nodes {
+            if has_schema_type(node, "WebPage") {
+                insights.web_pages.push(extract_webpage(node));
+                insights.graph_summary.push("WebPage".to_string());
+            }
+        }
+
+        // Extract BreadcrumbList
+        for node in &graph.nodes {
+            if has_schema_type(node, "BreadcrumbList") {
+                insights
+                    .breadcrumbs
+                    .push(extract_breadcrumb(node, &adjacency, &nodes_map));
+                insights.graph_summary.push("BreadcrumbList".to_string());
+            }
+        }
+
+        // Extract other interesting entities
+        for node in &graph.nodes {
+            // Skip entities we already process elsewhere or are sub-entities
+            if has_schema_type(node, "Product")
+                || has_schema_type(node, "ProductGroup")
+                || has_schema_type(node, "Organization")
+                || has_schema_type(node, "WebPage")
+                || has_schema_type(node, "Offer")
+                || has_schema_type(node, "Brand")
+                || has_schema_type(node, "PropertyValue")
+                || has_schema_type(node, "PostalAddress")
+                || has_schema_type(node, "AggregateRating")
+                || has_schema_type(node, "SpeakableSpecification")
+                || has_schema_type(node, "BreadcrumbList")
+                || has_schema_type(node, "ListItem")
+            {
+                continue;
+            }
+
+            // Collect interesting types
+            for node_type in &node.types {
+                let short_type = shorten_iri(node_type);
+                if !short_type.starts_with('_') && short_type != "@graph" {
+                    insights.other_entities.push(extract_generic_entity(node));
+                    insights.graph_summary.push(short_type);
+                    break; // Only add once per node
+                }
+            }
+        }
+
+        // Only worth a line when some data did not come from JSON-LD
+        let mut source_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for node in &graph.nodes {
+            for source in &node.sources {
+                *source_counts.entry(source.as_str()).or_default() += 1;
+            }
+        }
+        if source_counts.keys().any(|source| *source != "json-ld") {
+            let counts = source_counts
+                .iter()
+                .map(|(source, count)| format!("{source} ({count})"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            insights
+                .graph_summary
+                .push(format!("Sources: {counts} nodes"));
+        }
+
+        insights.data_downloads = graph
+            .nodes
+            .iter()
+            .filter(|node| has_schema_type(node, "DataDownload"))
+            .filter_map(extract_data_download)
+            .collect();
+
+        insights
+    }
+}
+
+impl GraphInsights {
+    /// Insights from compact schema.org JSON-LD, without expansion
+    ///
+    /// `document` may be a single node object, a document with `@graph`, or an
+    /// array of either (e.g. one entry per `<script>` block). Terms are taken
+    /// to be schema.org terms; other contexts are not resolved. Nested node
+    /// objects become nodes of their own, linked by an edge, as they would be
+    /// after expansion.
+    pub fn from_json_ld(document: &JsonValue) -> Self {
+        Self::from(&compact_graph(document))
+    }
+}
+
+/// Build a graph from compact JSON-LD by reading terms as schema.org terms
+fn compact_graph(document: &JsonValue) -> KnowledgeGraph {
+    let mut graph = KnowledgeGraph {
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    let mut blank_count = 0usize;
+    collect_compact_nodes(document, &mut graph, &mut blank_count);
+
+    // The same @id may be described in several places
+    let mut merged: BTreeMap<String, GraphNode> = BTreeMap::new();
+    for node in graph.nodes.drain(..) {
+        match merged.get_mut(&node.id) {
+            Some(existing) => {
+                existing.types.extend(node.types);
+                for (key, value) in node.properties {
+                    existing.properties.entry(key).or_insert(value);
+                }
+            }
+            None => {
+                merged.insert(node.id.clone(), node);
+            }
+        }
+    }
+    graph.nodes = merged.into_values().collect();
+    graph.canonicalize();
+    graph
+}
+
+/// Add the node objects in `value` to the graph, returning the IDs of the
+/// top-level ones
+fn collect_compact_nodes(
+    value: &JsonValue,
+    graph: &mut KnowledgeGraph,
+    blank_count: &mut usize,
+) -> Vec<String> {
+    match value {
+        JsonValue::Array(items) => items
+            .iter()
+            .flat_map(|item| collect_compact_nodes(item, graph, blank_count))
+            .collect(),
+        JsonValue::Object(obj) => {
+            if let Some(items) = obj.get("@graph") {
+                return collect_compact_nodes(items, graph, blank_count);
+            }
+            if let Some(items) = obj.get("@list").or_else(|| obj.get("@set")) {
+                return collect_compact_nodes(items, graph, blank_count);
+            }
+            if obj.contains_key("@value") {
+                return Vec::new();
+            }
+
+            let id = match obj.get("@id").and_then(JsonValue::as_str) {
+                Some(id) => id.to_string(),
+                None => {
+                    *blank_count += 1;
+                    format!("_:c{blank_count}")
+                }
+            };
+            let mut node = GraphNode::new(id.clone());
+            node.sources.push("json-ld".to_string());
+            match obj.get("@type") {
+                Some(JsonValue::String(ty)) => node.types.push(schema_iri(ty)),
+                Some(JsonValue::Array(types)) => node
+                    .types
+                    .extend(types.iter().filter_map(JsonValue::as_str).map(schema_iri)),
+                _ => {}
+            }
+
+            for (key, value) in obj {
+                if key.starts_with('@') {
+                    continue;
+                }
+                let predicate = schema_iri(key);
+                let mut literals = Vec::new();
+                let items = match value {
+                    JsonValue::Array(items) => items.as_slice(),
+                    value => std::slice::from_ref(value),
+                };
+                for item in items {
+                    if is_value_object(item) {
+                        literals.push(item.clone());
+                        continue;
+                    }
+                    for target in collect_compact_nodes(item, graph, blank_count) {
+                        graph.edges.push(GraphEdge {
+                            from: id.clone(),
+                            to: target,
+                            predicate: predicate.clone(),
+                        });
+                    }
+                }
+                match literals.len() {
+                    0 => {}
+                    1 => {
+                        node.properties.insert(predicate, literals.remove(0));
+                    }
+                    _ => {
+                        node.properties
+                            .insert(predicate, JsonValue::Array(literals));
+                    }
+                }
+            }
+
+            // A bare reference is an edge target, not a description
+            if obj.len() > 1 || !obj.contains_key("@id") {
+                graph.nodes.push(node);
+            }
+            vec![id]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Literals and `@value` objects, as opposed to node objects
+fn is_value_object(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Object(obj) => obj.contains_key("@value"),
+        _ => true,
+    }
+}
+
+/// Expand a compact term (`name`, `Product`) to its schema.org IRI; IRIs and
+/// compact IRIs are kept
+fn schema_iri(term: &str) -> String {
+    let term = term.strip_prefix("schema:").unwrap_or(term);
+    if term.contains(':') {
+        term.to_string()
+    } else {
+        format!("{SCHEMA_NS}{term}")
+    }
+}
+
+fn summarize_variant<'a>(
+    product: &GraphNode,
+    adjacency: &HashMap<&'a str, Vec<&'a GraphEdge>>,
+    nodes: &HashMap<&'a str, &'a GraphNode>,
+    property_names: &mut BTreeSet<String>,
+    direct_properties: &mut BTreeSet<String>,
+    offer_count: &mut usize,
+) -> VariantSummary {
+    let mut summary = VariantSummary {
+        name: property_text(
+            product,
+            &["https://schema.org/name", "http://schema.org/name", "name"],
+        ),
+        sku: property_text(
+            product,
+            &["https://schema.org/sku", "http://schema.org/sku", "sku"],
+        ),
+        gtin: property_text(
+            product,
+            &[
+                "https://schema.org/gtin",
+                "http://schema.org/gtin",
+                "https://schema.org/gtin13",
+                "http://schema.org/gtin13",
+                "https://schema.org/gtin14",
+                "http://schema.org/gtin14",
+                "gtin",
+                "gtin13",
+                "gtin14",
+                "ean",
+            ],
+        ),
+        mpn: property_text(
+            product,
+            &["https://schema.org/mpn", "http://schema.org/mpn", "mpn"],
+        ),
+        color: property_text(
+            product,
+            &[
+                "https://schema.org/color",
+                "http://schema.org/color",
+                "https://schema.org/Color", // Capitalized schema.org variant
+                "http://schema.org/Color",
+                "color",
+                "Color",
+            ],
+        ),
+        size: property_text(
+            product,
+            &[
+                "https://schema.org/size",
+                "http://schema.org/size",
+                "https://schema.org/Size", // Capitalized schema.org variant
+                "http://schema.org/Size",
+                "size",
+                "Size",
+            ],
+        ),
+        weight: property_text(
+            product,
+            &[
+                "https://schema.org/weight",
+                "http://schema.org/weight",
+                "weight",
+            ],
+        ),
+        material: property_text(
+            product,
+            &[
+                "https://schema.org/material",
+                "http://schema.org/material",
+                "material",
+            ],
+        ),
+        model: property_text(
+            product,
+            &[
+                "https://schema.org/model",
+                "http://schema.org/model",
+                "model",
+            ],
+        ),
+        category: property_text(
+            product,
+            &[
+                "https://schema.org/category",
+                "http://schema.org/category",
+                "category",
+            ],
+        ),
+        width: property_text(
+            product,
+            &[
+                "https://schema.org/width",
+                "http://schema.org/width",
+                "width",
+            ],
+        ),
+        height: property_text(
+            product,
+            &[
+                "https://schema.org/height",
+                "http://schema.org/height",
+                "height",
+            ],
+        ),
+        depth: property_text(
+            product,
+            &[
+                "https://schema.org/depth",
+                "http://schema.org/depth",
+                "depth",
+            ],
+        ),
+        ..Default::default()
+    };
+
+    if summary.color.is_some() {
+        direct_properties.insert("color".to_string());
+    }
+    if summary.size.is_some() {
+        direct_properties.insert("size".to_string());
+    }
+    if summary.gtin.is_some() {
+        direct_properties.insert("gtin".to_string());
+    }
+    if summary.mpn.is_some() {
+        direct_properties.insert("mpn".to_string());
+    }
+    if summary.weight.is_some() {
+        direct_properties.insert("weight".to_string());
+    }
+
+    // Collect all other direct string properties from the product node
+    // (excluding known ones and excluding objects/arrays)
+    for (key, value) in &product.properties {
+        let prop_name = shorten_iri(key);
+
+        // Skip known standard Schema.org properties we already handle explicitly
+        if matches!(
+            prop_name.as_str(),
+            "sku"
+                | "gtin"
+                | "gtin13"
+                | "gtin14"
+                | "mpn"
+                | "color"
+                | "Color"
+                | "size"
+                | "Size"
+                | "weight"
+                | "material"
+                | "model"
+                | "category"
+                | "width"
+                | "height"
+                | "depth"
+                | "name"
+                | "description"
+                | "image"
+                | "url"
+                | "@type"
+                | "@id"
+                | "@context"
+        ) {
+            continue;
+        }
+
+        // Only include simple string/number values
+        if let Some(text) = json_value_to_string(value) {
+            summary.additional.insert(prop_name.clone(), text);
+            direct_properties.insert(prop_name);
+        }
+    }
+
+    let mut additional = collect_additional_properties(product, adjacency, nodes);
+
+    // Check for isVariantOf to inherit properties from parent variant
+    if let Some(edges) = adjacency.get(product.id.as_str()) {
+        for edge in edges {
+            if predicate_matches(&edge.predicate, "isVariantOf")
+                && let Some(parent_node) = nodes.get(edge.to.as_str())
+            {
+                let parent_additional =
+                    collect_additional_properties(parent_node, adjacency, nodes);
+                // Inherit properties that aren't already set
+                for (key, value) in parent_additional {
+                    additional.entry(key).or_insert(value);
+                }
+            }
+        }
+    }
+
+    // Support both FrameShape and FrameType
+    summary.frame_shape = additional
+        .get("FrameShape")
+        .cloned()
+        .or_else(|| additional.get("FrameType").cloned());
+    summary.battery = additional.get("BatteryCapacity").cloned();
+    for key in additional.keys() {
+        property_names.insert(key.clone());
+    }
+    summary.additional = additional;
+
+    if let Some((price_display, price_numeric, price_currency, availability)) =
+        extract_offer(product, adjacency, nodes)
+    {
+        summary.price_display = price_display;
+        summary.price_numeric = price_numeric;
+        summary.price_currency = price_currency.clone();
+        summary.availability = availability.clone();
+        *offer_count += 1;
+    }
+
+    summary
+}
+
+fn collect_additional_properties<'a>(
+    product: &GraphNode,
+    adjacency: &HashMap<&'a str, Vec<&'a GraphEdge>>,
+    nodes: &HashMap<&'a str, &'a GraphNode>,
+) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    if let Some(edges) = adjacency.get(product.id.as_str()) {
+        for edge in edges {
+            if predicate_matches(&edge.predicate, "additionalProperty")
+                && let Some(node) = nodes.get(edge.to.as_str())
+                && has_schema_type(node, "PropertyValue")
+                && let Some(name) = property_text(
+                    node,
+                    &["https://schema.org/name", "http://schema.org/name", "name"],
+                )
+                && let Some(value) = property_text(
+                    node,
+                    &[
+                        "https://schema.org/value",
+                        "http://schema.org/value",
+                        "value",
+                    ],
+                )
+            {
+                result.insert(name.clone(), value);
+            }
+        }
+    }
+    result
+}
+
+fn extract_common_properties<'a>(
+    product: &GraphNode,
+    adjacency: &HashMap<&'a str, Vec<&'a GraphEdge>>,
+    nodes: &HashMap<&'a str, &'a GraphNode>,
+    varies_by: &[String],
+) -> BTreeMap<String, String> {
+    let mut common = BTreeMap::new();
+
+    // Normalize variesBy to lowercase for case-insensitive comparison
+    let varies_by_lower: Vec<String> = varies_by.iter().map(|s| s.to_lowercase()).collect();
+
+    // Helper function to normalize property names by extracting tokens
+    // e.g., "FrameSize" -> ["frame", "size"], "BatteryCapacity" -> ["battery", "capacity"]
+    let normalize_tokens = |s: &str| -> Vec<String> {
+        // Split on case boundaries and non-alphanumeric characters
+        let mut tokens = Vec::new();
+        let mut current = String::new();
+
+        for ch in s.chars() {
+            if ch.is_uppercase() && !current.is_empty() {
+                tokens.push(current.to_lowercase());
+                current = String::new();
+            }
+            if ch.is_alphanumeric() {
+                current.push(ch);
+            } else if !current.is_empty() {
+                tokens.push(current.to_lowercase());
+                current = String::new();
+            }
+        }
+        if !current.is_empty() {
+            tokens.push(current.to_lowercase());
+        }
+        tokens
+    };
+
+    // Helper function to check if a property varies
+    let is_varying = |prop_name: &str| -> bool {
+        let prop_lower = prop_name.to_lowercase();
+        let prop_tokens = normalize_tokens(prop_name);
+
+        varies_by_lower.iter().any(|vb| {
+            // Exact match
+            if vb == &prop_lower {
+                return true;
+            }
+
+            // Token-based matching: if all tokens from variesBy appear in the property name
+            // e.g., "size" in variesBy matches "FrameSize" (tokens: ["frame", "size"])
+            // but not "colorway" (tokens: ["colorway"])
+            let vb_tokens = normalize_tokens(vb);
+
+            // Check if all variesBy tokens are present in property tokens
+            // This handles cases like "Size" matching "FrameSize"
+            // but avoids false positives like "Color" matching "Colorway"
+            if vb_tokens.len() == 1 && prop_tokens.contains(&vb_tokens[0]) {
+                return true;
+            }
+
+            // For multi-token variesBy (e.g., "FrameSize"), require exact token sequence
+            if vb_tokens.len() > 1 && vb_tokens == prop_tokens {
+                return true;
+            }
+
+            false
+        })
+    };
+
+    // Extract direct properties from the product node
+    let direct_props = vec![
+        (
+            "name",
+            &["https://schema.org/name", "http://schema.org/name", "name"] as &[&str],
+        ),
+        (
+            "description",
+            &[
+                "https://schema.org/description",
+                "http://schema.org/description",
+                "description",
+            ],
+        ),
+        (
+            "material",
+            &[
+                "https://schema.org/material",
+                "http://schema.org/material",
+                "material",
+            ],
+        ),
+        (
+            "color",
+            &[
+                "https://schema.org/color",
+                "http://schema.org/color",
+                "color",
+            ],
+        ),
+        (
+            "size",
+            &["https://schema.org/size", "http://schema.org/size", "size"],
+        ),
+        (
+            "brand",
+            &[
+                "https://schema.org/brand",
+                "http://schema.org/brand",
+                "brand",
+            ],
+        ),
+        (
+            "model",
+            &[
+                "https://schema.org/model",
+                "http://schema.org/model",
+                "model",
+            ],
+        ),
+        (
+            "category",
+            &[
+                "https://schema.org/category",
+                "http://schema.org/category",
+                "category",
+            ],
+        ),
+        (
+            "width",
+            &[
+                "https://schema.org/width",
+                "http://schema.org/width",
+                "width",
+            ],
+        ),
+        (
+            "height",
+            &[
+                "https://schema.org/height",
+                "http://schema.org/height",
+                "height",
+            ],
+        ),
+        (
+            "depth",
+            &[
+                "https://schema.org/depth",
+                "http://schema.org/depth",
+                "depth",
+            ],
+        ),
+        (
+            "weight",
+            &[
+                "https://schema.org/weight",
+                "http://schema.org/weight",
+                "weight",
+            ],
+        ),
+    ];
+
+    for (prop_name, prop_paths) in direct_props {
+        // Skip if this property varies
+        if is_varying(prop_name) {
+            continue;
+        }
+
+        if let Some(value) = property_text(product, prop_paths) {
+            common.insert(prop_name.to_string(), value);
+        }
+    }
+
+    // Also extract additionalProperty items that aren't in variesBy
+    let additional = collect_additional_properties(product, adjacency, nodes);
+    for (key, value) in additional {
+        if !is_varying(&key) {
+            common.insert(key, value);
+        }
+    }
+
+    common
+}
+
+fn extract_offer<'a>(
+    product: &GraphNode,
+    adjacency: &HashMap<&'a str, Vec<&'a GraphEdge>>,
+    nodes: &HashMap<&'a str, &'a GraphNode>,
+) -> Option<OfferInfo> {
+    let edges = adjacency.get(product.id.as_str())?;
+    for edge in edges {
+        if predicate_matches(&edge.predicate, "offers")
+            && let Some(offer_node) = nodes.get(edge.to.as_str())
+        {
+            if !has_schema_type(offer_node, "Offer") {
+                continue;
+            }
+            let price_raw = property_text(
+                offer_node,
+                &[
+                    "https://schema.org/price",
+                    "http://schema.org/price",
+                    "price",
+                ],
+            );
+            let currency = property_text(
+                offer_node,
+                &[
+                    "https://schema.org/priceCurrency",
+                    "http://schema.org/priceCurrency",
+                    "priceCurrency",
+                ],
+            );
+            let price_numeric = price_raw
+                .as_ref()
+                .and_then(|raw| raw.replace(',', ".").parse::<f64>().ok());
+            let price_display = if let Some(value) = price_numeric {
+                Some(format_price(value, currency.as_deref()))
+            } else {
+                price_raw.clone()
+            };
+
+            let availability = property_text(
+                offer_node,
+                &[
+                    "https://schema.org/availability",
+                    "http://schema.org/availability",
+                    "availability",
+                ],
+            )
+            .map(|s| shorten_iri(&s));
+
+            return Some((price_display, price_numeric, currency, availability));
+        }
+    }
+    None
+}
+
+fn extract_organization<'a>(
+    node: &GraphNode,
+    adjacency: &HashMap<&'a str, Vec<&'a GraphEdge>>,
+    nodes_map: &HashMap<&'a str, &'a GraphNode>,
+) -> OrganizationSummary {
+    let mut org = OrganizationSummary {
+        name: property_text(
+            node,
+            &["https://schema.org/name", "http://schema.org/name", "name"],
+        ),
+        logo: property_text(
+            node,
+            &["https://schema.org/logo", "http://schema.org/logo", "logo"],
+        ),
+        telephone: property_text(
+            node,
+            &[
+                "https://schema.org/telephone",
+                "http://schema.org/telephone",
+                "telephone",
+            ],
+        ),
+        email: property_text(
+            node,
+            &[
+                "https://schema.org/email",
+                "http://schema.org/email",
+                "email",
+            ],
+        ),
+        ..Default::default()
+    };
+
+    // Extract address if present
+    if let Some(edges) = adjacency.get(node.id.as_str()) {
+        for edge in edges {
+            if predicate_matches(&edge.predicate, "address") {
+                if let Some(address_node) = nodes_map.get(edge.to.as_str()) {
+                    org.address = Some(AddressSummary {
+                        street: property_text(
+                            address_node,
+                            &[
+                                "https://schema.org/streetAddress",
+                                "http://schema.org/streetAddress",
+                                "streetAddress",
+                            ],
+                        ),
+                        locality: property_text(
+                            address_node,
+                            &[
+                                "https://schema.org/addressLocality",
+                                "http://schema.org/addressLocality",
+                                "addressLocality",
+                            ],
+                        ),
+                        postal_code: property_text(
+                            address_node,
+                            &[
+                                "https://schema.org/postalCode",
+                                "http://schema.org/postalCode",
+                                "postalCode",
+                            ],
+                        ),
+                        country: property_text(
+                            address_node,
+                            &[
+                                "https://schema.org/addressCountry",
+                                "http://schema.org/addressCountry",
+                                "addressCountry",
+                            ],
+                        ),
+                    });
+                }
+            } else if predicate_matches(&edge.predicate, "aggregateRating")
+                && let Some(rating_node) = nodes_map.get(edge.to.as_str())
+            {
+                org.rating = Some(RatingSummary {
+                    rating_value: property_text(
+                        rating_node,
+                        &[
+                            "https://schema.org/ratingValue",
+                            "http://schema.org/ratingValue",
+                            "ratingValue",
+                        ],
+                    ),
+                    review_count: property_text(
+                        rating_node,
+                        &[
+                            "https://schema.org/reviewCount",
+                            "http://schema.org/reviewCount",
+                            "reviewCount",
+                        ],
+                    ),
+                });
+            }
+        }
+    }
+
+    org
+}
+
+fn extract_webpage(node: &GraphNode) -> WebPageSummary {
+    let speakable_text = property_text(
+        node,
+        &[
+            "https://schema.org/speakable",
+            "http://schema.org/speakable",
+            "speakable",
+        ],
+    );
+
+    WebPageSummary {
+        url: property_text(
+            node,
+            &["https://schema.org/url", "http://schema.org/url", "url"],
+        ),
+        speakable: speakable_text,
+    }
+}
+
+fn extract_breadcrumb<'a>(
+    node: &GraphNode,
+    adjacency: &HashMap<&'a str, Vec<&'a GraphEdge>>,
+    nodes_map: &HashMap<&'a str, &'a GraphNode>,
+) -> BreadcrumbSummary {
+    let mut items = Vec::new();
+
+    if let Some(edges) = adjacency.get(node.id.as_str()) {
+        for edge in edges {
+            if predicate_matches(&edge.predicate, "itemListElement")
+                && let Some(list_item_node) = nodes_map.get(edge.to.as_str())
+                && has_schema_type(list_item_node, "ListItem")
+            {
+                let position = property_text(
+                    list_item_node,
+                    &[
+                        "https://schema.org/position",
+                        "http://schema.org/position",
+                        "position",
+                    ],
+                )
+                .and_then(|s| s.parse::<usize>().ok())
+                .unwrap_or(0);
+
+                let name = property_text(
+                    list_item_node,
+                    &["https://schema.org/name", "http://schema.org/name", "name"],
+                )
+                .unwrap_or_default();
+
+                let url = property_text(
+                    list_item_node,
+                    &["https://schema.org/item", "http://schema.org/item", "item"],
+                )
+                .unwrap_or_default();
+
+                items.push(BreadcrumbItem {
+                    position,
+                    name,
+                    url,
+                });
+            }
+        }
+    }
+
+    BreadcrumbSummary { items }
+}
+
+fn extract_generic_entity(node: &GraphNode) -> EntitySummary {
+    let mut properties = BTreeMap::new();
+
+    // Extract all simple string properties
+    for (key, value) in &node.properties {
+        let short_key = shorten_iri(key);
+        let value_str = json_value_display(value);
+        if !value_str.is_empty() {
+            properties.insert(short_key, value_str);
+        }
+    }
+
+    let entity_type = node
+        .types
+        .first()
+        .map(|t| shorten_iri(t))
+        .unwrap_or_else(|| "Entity".to_string());
+
+    EntitySummary {
+        id: node.id.clone(),
+        entity_type,
+        properties,
+    }
+}
+
+fn extract_data_download(node: &GraphNode) -> Option<DataDownloadEntry> {
+    Some(DataDownloadEntry {
+        content_url: property_display(
+            node,
+            &[
+                "https://schema.org/contentUrl",
+                "http://schema.org/contentUrl",
+                "contentUrl",
+            ],
+        )?,
+        encoding_format: property_display(
+            node,
+            &[
+                "https://schema.org/encodingFormat",
+                "http://schema.org/encodingFormat",
+                "encodingFormat",
+            ],
+        ),
+        license: property_display(
+            node,
+            &[
+                "https://schema.org/license",
+                "http://schema.org/license",
+                "license",
+            ],
+        ),
+    })
+}
+
+fn property_display(node: &GraphNode, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| resolve_node_property(&node.properties, key).map(json_value_display))
+}
+
+fn property_text(node: &GraphNode, keys: &[&str]) -> Option<String> {
+    for key in keys {
+        if let Some(value) = resolve_node_property(&node.properties, key)
+            && let Some(text) = json_value_to_string(value)
+        {
+            return Some(text);
+        }
+    }
+    None
+}
+
+fn resolve_node_property<'a>(
+    props: &'a BTreeMap<String, JsonValue>,
+    key: &str,
+) -> Option<&'a JsonValue> {
+    if let Some(value) = props.get(key) {
+        return Some(value);
+    }
+
+    if key.starts_with("https://schema.org/") {
+        let alt = key.replacen("https://", "http://", 1);
+        if let Some(value) = props.get(&alt) {
+            return Some(value);
+        }
+    } else if key.starts_with("http://schema.org/") {
+        let alt = key.replacen("http://", "https://", 1);
+        if let Some(value) = props.get(&alt) {
+            return Some(value);
+        }
+    }
+
+    if let Some(last) = key.rsplit('/').next()
+        && let Some(value) = props.get(last)
+    {
+        return Some(value);
+    }
+
+    None
+}
+
+fn json_value_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        JsonValue::Bool(b) => Some(b.to_string()),
+        JsonValue::Array(arr) => arr.first().and_then(json_value_to_string),
+        JsonValue::Object(obj) => obj
+            .get("@value")
+            .and_then(json_value_to_string)
+            .or_else(|| obj.get("name").and_then(json_value_to_string)),
+        _ => None,
+    }
+}
+
+fn json_value_display(value: &JsonValue) -> String {
+    if let Some(text) = json_value_to_string(value) {
+        return text;
+    }
+
+    match value {
+        JsonValue::Array(items) => {
+            let parts: Vec<String> = items.iter().map(json_value_display).collect();
+            parts.join(", ")
+        }
+        JsonValue::Object(obj) => {
+            if let Some(val) = obj.get("@value") {
+                return json_value_display(val);
+            }
+            serde_json::to_string(value).unwrap_or_else(|_| value.to_string())
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// A price with its currency symbol, or code when there is no symbol, and
+/// cents only when the price has them
+pub fn format_price(value: f64, currency: Option<&str>) -> String {
+    if value.fract().abs() < 1e-4 {
+        format_price_with_precision(value, currency, 0)
+    } else {
+        format_price_with_precision(value, currency, 2)
+    }
+}
+
+fn currency_symbol(code: &str) -> Option<&'static str> {
+    match code {
+        "EUR" => Some("€"),
+        "USD" => Some("$"),
+        "GBP" => Some("£"),
+        "JPY" => Some("¥"),
+        _ => None,
+    }
+}
+
+/// A price with `decimals` decimals and its currency symbol or code
+pub fn format_price_with_precision(value: f64, currency: Option<&str>, decimals: usize) -> String {
+    let number = format!("{value:.prec$}", value = value, prec = decimals);
+
+    if let Some(code) = currency {
+        if let Some(symbol) = currency_symbol(code) {
+            format!("{symbol}{number}")
+        } else {
+            format!("{number} {code}")
+        }
+    } else {
+        number
+    }
+}
+
+fn title_case(input: &str) -> String {
+    let mut chars = input.chars();
+    let Some(first) = chars.next() else {
+        return String::new();
+    };
+
+    let mut result = first.to_uppercase().collect::<String>();
+    result.push_str(&chars.as_str().to_lowercase());
+    result
+}
+
+fn has_schema_type(node: &GraphNode, schema_type: &str) -> bool {
+    node.types
+        .iter()
+        .any(|ty| shorten_iri(ty).eq_ignore_ascii_case(schema_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bike_group() -> JsonValue {
+        json!({
+            "@context": "https://schema.org",
+            "@type": "ProductGroup",
+            "name": "Paralane",
+            "productGroupID": "PL",
+            "variesBy": ["size"],
+            "brand": {"@type": "Brand", "name": "Canyon"},
+            "material": "Carbon",
+            "hasVariant": [
+                {
+                    "@type": "Product",
+                    "sku": "PL-M",
+                    "size": "M",
+                    "material": "Carbon",
+                    "offers": {
+                        "@type": "Offer",
+                        "price": "3999.00",
+                        "priceCurrency": "EUR",
+                        "availability": "https://schema.org/InStock"
+                    }
+                },
+                {
+                    "@type": "Product",
+                    "sku": "PL-L",
+                    "size": "L",
+                    "material": "Carbon",
+                    "offers": {
+                        "@type": "Offer",
+                        "price": 4199.5,
+                        "priceCurrency": "EUR",
+                        "availability": "https://schema.org/OutOfStock"
+                    }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_product_group_from_json_ld() {
+        let insights = GraphInsights::from_json_ld(&bike_group());
+
+        assert_eq!(insights.product_groups.len(), 1);
+        let group = &insights.product_groups[0];
+        assert_eq!(group.name.as_deref(), Some("Paralane"));
+        assert_eq!(group.product_group_id.as_deref(), Some("PL"));
+        assert_eq!(group.brand.as_deref(), Some("Canyon"));
+        assert_eq!(group.total_variants, 2);
+        assert_eq!(
+            group.common_properties.get("material").map(String::as_str),
+            Some("Carbon")
+        );
+        assert!(!group.common_properties.contains_key("size"));
+
+        let skus: Vec<_> = group.variants.iter().map(|v| v.sku.as_deref()).collect();
+        assert_eq!(skus, vec![Some("PL-L"), Some("PL-M")]);
+        assert_eq!(group.variants[0].price_display.as_deref(), Some("€4199.50"));
+        assert_eq!(group.variants[1].price_display.as_deref(), Some("€3999"));
+
+        let stats = group.price_stats.as_ref().unwrap();
+        assert_eq!((stats.min, stats.max), (3999.0, 4199.5));
+        assert_eq!(group.availability_counts.get("InStock"), Some(&1));
+        assert_eq!(group.availability_counts.get("OutOfStock"), Some(&1));
+        assert!(
+            insights
+                .graph_summary
+                .contains(&"Product → Offer (2)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expanded_and_compact_graphs_agree() {
+        // The same data as it comes out of expansion: schema.org IRIs
+        let mut product = GraphNode::new("https://example.com/bike".to_string());
+        product.types.push(format!("{SCHEMA_NS}Product"));
+        product
+            .properties
+            .insert(format!("{SCHEMA_NS}name"), json!("Bike"));
+        let mut offer = GraphNode::new("_:b0".to_string());
+        offer.types.push(format!("{SCHEMA_NS}Offer"));
+        offer
+            .properties
+            .insert(format!("{SCHEMA_NS}price"), json!(12.5));
+        offer
+            .properties
+            .insert(format!("{SCHEMA_NS}priceCurrency"), json!("USD"));
+        let expanded = KnowledgeGraph {
+            nodes: vec![product, offer],
+            edges: vec![GraphEdge {
+                from: "https://example.com/bike".to_string(),
+                to: "_:b0".to_string(),
+                predicate: format!("{SCHEMA_NS}offers"),
+            }],
+        };
+
+        let compact = json!([{
+            "@context": "https://schema.org",
+            "@id": "https://example.com/bike",
+            "@type": "Product",
+            "name": "Bike",
+            "offers": {"@type": "Offer", "price": 12.5, "priceCurrency": "USD"}
+        }]);
+
+        let from_graph = serde_json::to_value(GraphInsights::from(&expanded)).unwrap();
+        let from_json_ld = serde_json::to_value(GraphInsights::from_json_ld(&compact)).unwrap();
+        assert_eq!(from_graph, from_json_ld);
+        assert_eq!(
+            from_graph["product_groups"][0]["variants"][0]["price_display"],
+            "$12.50"
+        );
+    }
+
+    #[test]
+    fn test_organization_breadcrumb_and_downloads() {
+        let insights = GraphInsights::from_json_ld(&json!({
+            "@context": "https://schema.org",
+            "@graph": [
+                {
+                    "@type": "Organization",
+                    "name": "Example Corp",
+                    "address": {"@type": "PostalAddress", "addressLocality": "Utrecht"}
+                },
+                {
+                    "@type": "BreadcrumbList",
+                    "itemListElement": [
+                        {"@type": "ListItem", "position": 2, "name": "Bikes", "item": "https://example.com/bikes"},
+                        {"@type": "ListItem", "position": 1, "name": "Home", "item": "https://example.com/"}
+                    ]
+                },
+                {
+                    "@type": "DataDownload",
+                    "contentUrl": "https://example.com/feed.csv",
+                    "encodingFormat": "text/csv"
+                }
+            ]
+        }));
+
+        let org = &insights.organizations[0];
+        assert_eq!(org.name.as_deref(), Some("Example Corp"));
+        assert_eq!(
+            org.address.as_ref().and_then(|a| a.locality.as_deref()),
+            Some("Utrecht")
+        );
+
+        let mut positions: Vec<_> = insights.breadcrumbs[0]
+            .items
+            .iter()
+            .map(|item| (item.position, item.name.as_str()))
+            .collect();
+        positions.sort();
+        assert_eq!(positions, vec![(1, "Home"), (2, "Bikes")]);
+
+        assert_eq!(insights.data_downloads.len(), 1);
+        assert_eq!(
+            insights.data_downloads[0].encoding_format.as_deref(),
+            Some("text/csv")
+        );
+        assert_eq!(insights.other_entities[0].entity_type, "DataDownload");
+    }
+
+    #[test]
+    fn test_format_price() {
+        assert_eq!(format_price(29.99, Some("USD")), "$29.99");
+        assert_eq!(format_price(100.0, Some("CHF")), "100 CHF");
+        assert_eq!(format_price(50.0, None), "50");
+        assert_eq!(format_price_with_precision(5.0, Some("GBP"), 2), "£5.00");
+    }
+}
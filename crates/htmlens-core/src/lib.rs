@@ -3,14 +3,20 @@
 //! Core library for extracting semantic knowledge graphs from HTML pages.
 //!
 //! This library provides:
-//! - HTML parsing and JSON-LD extraction
+//! - HTML parsing and JSON-LD / Microdata / RDFa extraction
+//! - Page metadata (title, OpenGraph, Twitter Card, hreflang) extraction
 //! - Knowledge graph construction from JSON-LD
+//! - Product, organization and page summaries of a graph, and their markdown
+//!   report sections
+//! - Knowledge graph export to N-Triples, N-Quads, Turtle and JSON-LD, and to
+//!   Graphviz DOT, GraphML and Cytoscape.js for visualization
 //! - Schema.org entity type detection
 //!
 //! ## Features
 //!
 //! - `default`: Basic HTML parsing and JSON-LD extraction (no expansion)
 //! - `full-expansion`: Complete JSON-LD expansion with remote context resolution
+//!   (schema.org is resolved from a bundled copy of its context)
 //!
 //! ## Example
 //!
@@ -30,6 +36,10 @@
 //! # }
 //! ```
 
+pub mod export;
+pub mod identity;
+pub mod insights;
+pub mod markdown;
 pub mod parser;
 pub mod types;
 pub mod url_utils;
@@ -37,32 +47,46 @@ pub mod url_utils;
 #[cfg(feature = "full-expansion")]
 pub mod graph;
 
+#[cfg(feature = "full-expansion")]
+pub mod loader;
+
 #[cfg(any(feature = "ai-readiness", feature = "ai-readiness-parser"))]
 pub mod ai_readiness;
 
 // Re-export commonly used types
+pub use export::{GraphFormat, RdfFormat};
+pub use identity::{label_blank_nodes, node_identifier_keys, resolve_identities};
+pub use insights::GraphInsights;
 pub use types::{GraphEdge, GraphNode, KnowledgeGraph};
 
-pub use parser::{combine_json_ld_blocks, extract_json_ld_blocks, html_to_markdown, sanitize_html};
+pub use parser::{
+    BlockDiagnostic, PageMetadata, StructuredData, combine_json_ld_blocks, extract_json_ld_blocks,
+    extract_page_metadata, extract_structured_data, extract_structured_data_blocks,
+    extract_structured_data_lenient, html_to_markdown, sanitize_html,
+};
 pub use url_utils::normalize_origin;
 
 #[cfg(feature = "full-expansion")]
-pub use parser::fetch_html;
+pub use parser::{fetch_html, fetch_html_with};
+
+#[cfg(feature = "full-expansion")]
+pub use graph::{GraphBuilder, expand_json_ld, expand_structured_data};
 
 #[cfg(feature = "full-expansion")]
-pub use graph::{GraphBuilder, expand_json_ld};
+pub use loader::ContextLoader;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_types_creation() {
         let node = GraphNode {
             id: "test-node".to_string(),
             types: vec!["Product".to_string()],
-            properties: HashMap::new(),
+            properties: BTreeMap::new(),
+            sources: Vec::new(),
         };
 
         assert_eq!(node.id, "test-node");
@@ -94,6 +118,59 @@ mod tests {
         assert!(graph.edges.is_empty());
     }
 
+    #[test]
+    fn test_canonical_hash_ignores_insertion_order() {
+        let node = |id: &str, name: &str| {
+            let mut node = GraphNode::new(id.to_string());
+            node.types.push("http://schema.org/Thing".to_string());
+            node.properties
+                .insert("http://schema.org/name".to_string(), name.into());
+            node.properties
+                .insert("http://schema.org/url".to_string(), id.into());
+            node
+        };
+        let edge = |from: &str, to: &str| GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            predicate: "http://schema.org/isPartOf".to_string(),
+        };
+
+        let mut graph = KnowledgeGraph {
+            nodes: vec![
+                node("https://a.example/", "A"),
+                node("https://b.example/", "B"),
+            ],
+            edges: vec![
+                edge("https://a.example/", "https://b.example/"),
+                edge("https://b.example/", "https://a.example/"),
+            ],
+        };
+        let shuffled = KnowledgeGraph {
+            nodes: vec![
+                node("https://b.example/", "B"),
+                node("https://a.example/", "A"),
+            ],
+            edges: vec![
+                edge("https://b.example/", "https://a.example/"),
+                edge("https://a.example/", "https://b.example/"),
+                edge("https://b.example/", "https://a.example/"),
+            ],
+        };
+        assert_eq!(graph.canonical_hash(), shuffled.canonical_hash());
+        assert_eq!(graph.canonical_hash().len(), 64);
+
+        graph.nodes[0]
+            .properties
+            .insert("http://schema.org/name".to_string(), "Changed".into());
+        assert_ne!(graph.canonical_hash(), shuffled.canonical_hash());
+
+        let mut shuffled = shuffled;
+        shuffled.canonicalize();
+        assert_eq!(shuffled.nodes[0].id, "https://a.example/");
+        assert_eq!(shuffled.edges.len(), 2);
+        assert_eq!(shuffled.edges[0].from, "https://a.example/");
+    }
+
     #[test]
     fn test_extract_json_ld_from_html() {
         let html = r#"
@@ -0,0 +1,240 @@
+//! JSON-LD context loading
+//!
+//! Almost every page uses the schema.org context, so a copy of it ships with
+//! the crate (`contexts/schema.org.jsonld`) and is served without a network
+//! request. Other remote contexts are fetched over HTTP and can be cached on
+//! disk. An offline loader only uses the bundled context and the cache.
+//!
+//! Requires the `full-expansion` feature.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use iref::IriBuf;
+use json_ld::syntax::{Parse, Value};
+use json_ld::{Loader, LoadingResult, RemoteDocument, ReqwestLoader};
+use rdf_types::vocabulary::IriVocabularyMut;
+use sha2::{Digest, Sha256};
+
+/// Bundled schema.org JSON-LD context
+///
+/// A condensed copy of https://schema.org/docs/jsonldcontext.jsonld: the
+/// `@vocab` mapping, the namespace prefixes and the URL-valued properties
+/// that are coerced to `@id`.
+pub const SCHEMA_ORG_CONTEXT: &str = include_str!("../contexts/schema.org.jsonld");
+
+/// Context URLs served from [`SCHEMA_ORG_CONTEXT`]
+const SCHEMA_ORG_CONTEXT_URLS: &[&str] = &[
+    "https://schema.org",
+    "https://schema.org/",
+    "http://schema.org",
+    "http://schema.org/",
+    "https://schema.org/docs/jsonldcontext.jsonld",
+    "http://schema.org/docs/jsonldcontext.jsonld",
+    "https://schema.org/docs/jsonldcontext.json",
+    "http://schema.org/docs/jsonldcontext.json",
+];
+
+/// Characters of the URL kept in a cache file name
+const CACHE_PREFIX_LEN: usize = 60;
+
+/// Context loader with a bundled schema.org context and an optional disk cache
+#[derive(Default)]
+pub struct ContextLoader {
+    offline: bool,
+    cache_dir: Option<PathBuf>,
+    network: ReqwestLoader,
+}
+
+impl ContextLoader {
+    /// Loader that fetches contexts other than schema.org over the network
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loader that never touches the network
+    pub fn offline() -> Self {
+        Self {
+            offline: true,
+            ..Self::default()
+        }
+    }
+
+    /// Read remote contexts from `dir` and store fetched ones there
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Cache file for `url`: a readable prefix plus a hash of the full URL,
+    /// so URLs that sanitize to the same prefix get different files
+    fn cache_path(&self, url: &str) -> Option<PathBuf> {
+        let prefix: String = url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(CACHE_PREFIX_LEN)
+            .collect();
+        let hash: String = Sha256::digest(url.as_bytes())[..16]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        Some(
+            self.cache_dir
+                .as_ref()?
+                .join(format!("{prefix}-{hash}.jsonld")),
+        )
+    }
+}
+
+impl Loader<IriBuf> for ContextLoader {
+    type Error = anyhow::Error;
+
+    async fn load_with<V>(
+        &mut self,
+        vocabulary: &mut V,
+        url: IriBuf,
+    ) -> LoadingResult<IriBuf, Self::Error>
+    where
+        V: IriVocabularyMut<Iri = IriBuf>,
+    {
+        if SCHEMA_ORG_CONTEXT_URLS.contains(&url.as_str()) {
+            return remote_document(url, SCHEMA_ORG_CONTEXT);
+        }
+
+        let cache_path = self.cache_path(url.as_str());
+        if let Some(path) = cache_path.as_ref()
+            && let Ok(cached) = fs::read_to_string(path)
+        {
+            return remote_document(url, &cached);
+        }
+
+        if self.offline {
+            return Err(anyhow!(
+                "context {url} is not bundled or cached (offline mode)"
+            ));
+        }
+
+        let document = self
+            .network
+            .load_with(vocabulary, url.clone())
+            .await
+            .map_err(|err| anyhow!("failed to load context {url}: {err}"))?;
+
+        // The cache is best effort; a failed write only costs a refetch
+        if let Some(path) = cache_path {
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let _ = fs::write(path, document.document.to_string());
+        }
+
+        Ok(document)
+    }
+}
+
+fn remote_document(url: IriBuf, json: &str) -> Result<RemoteDocument<IriBuf>> {
+    let (value, _) =
+        Value::parse_str(json).map_err(|err| anyhow!("invalid context document {url}: {err}"))?;
+    Ok(RemoteDocument::new(
+        Some(url),
+        Some("application/ld+json".parse().unwrap()),
+        value,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{GraphBuilder, expand_json_ld};
+
+    #[tokio::test]
+    async fn test_offline_expansion_uses_bundled_schema_org() {
+        let json_ld = r#"{
+            "@context": "https://schema.org",
+            "@type": "Product",
+            "@id": "https://example.com/bike",
+            "name": "Paralane 8.8",
+            "url": "https://example.com/bike",
+            "brand": {"@type": "Brand", "name": "Focus"}
+        }"#;
+
+        let mut loader = ContextLoader::offline();
+        let expanded = expand_json_ld("https://example.com/", json_ld, &mut loader)
+            .await
+            .unwrap();
+        let mut builder = GraphBuilder::new();
+        builder.ingest_document(&expanded);
+        let graph = builder.into_graph();
+
+        let product = graph
+            .nodes
+            .iter()
+            .find(|node| node.id == "https://example.com/bike")
+            .unwrap();
+        assert_eq!(product.types, vec!["http://schema.org/Product"]);
+        assert_eq!(product.properties["http://schema.org/name"], "Paralane 8.8");
+        assert!(
+            graph
+                .edges
+                .iter()
+                .any(|edge| edge.from == product.id && edge.predicate == "http://schema.org/brand")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_offline_uses_cache_and_rejects_unknown_contexts() {
+        let dir = std::env::temp_dir().join(format!("htmlens-contexts-{}", std::process::id()));
+        let url = "https://vocab.example/context.jsonld";
+        let json_ld = format!(r#"{{"@context": "{url}", "@type": "Thing", "label": "x"}}"#);
+
+        let mut loader = ContextLoader::offline();
+        let err = expand_json_ld("https://example.com/", &json_ld, &mut loader)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("offline mode"), "{err}");
+
+        let mut loader = ContextLoader::offline().with_cache_dir(&dir);
+        let path = loader.cache_path(url).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            &path,
+            r#"{"@context": {"@vocab": "https://vocab.example/"}}"#,
+        )
+        .unwrap();
+        let expanded = expand_json_ld("https://example.com/", &json_ld, &mut loader).await;
+        fs::remove_dir_all(&dir).unwrap();
+
+        let mut builder = GraphBuilder::new();
+        builder.ingest_document(&expanded.unwrap());
+        let graph = builder.into_graph();
+        assert_eq!(graph.nodes[0].types, vec!["https://vocab.example/Thing"]);
+    }
+
+    #[test]
+    fn test_cache_paths_differ_for_similar_urls() {
+        let loader = ContextLoader::offline().with_cache_dir("contexts");
+        let a = loader.cache_path("https://vocab.example/a?b").unwrap();
+        let b = loader.cache_path("https://vocab.example/a/b").unwrap();
+        assert_ne!(a, b);
+        assert!(
+            a.file_name()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .starts_with("vocab.example_a_b-")
+        );
+    }
+}
@@ -0,0 +1,575 @@
+//! Markdown sections for [`GraphInsights`] and page metadata
+//!
+//! These are the sections of the CLI's markdown report, shared with the
+//! worker so both print the same summaries. Each function appends one
+//! section to a buffer; sections with nothing to show are skipped.
+//!
+//! [`GraphInsights`]: crate::insights::GraphInsights
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::insights::{
+    BreadcrumbSummary, DataDownloadEntry, EntitySummary, OrganizationSummary, PriceStats,
+    ProductGroupSummary, VariantSummary, WebPageSummary, format_price_with_precision,
+};
+use crate::parser;
+
+const DIVIDER: &str = "─────────────────────────────────────────────────────────────";
+const LABEL_WIDTH: usize = 16;
+
+/// Write a section title between two divider lines
+pub fn push_section_header(buf: &mut String, icon: &str, title: &str) {
+    let _ = writeln!(buf, "{DIVIDER}");
+    let _ = writeln!(buf, "{icon} {title}");
+    let _ = writeln!(buf, "{DIVIDER}");
+}
+
+/// Write a `• label : value` line; empty values are skipped
+pub fn push_key_value(buf: &mut String, label: &str, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    let _ = writeln!(buf, "• {:<width$} : {}", label, value, width = LABEL_WIDTH);
+}
+
+fn availability_icon(status: &str) -> &'static str {
+    match status.to_lowercase().as_str() {
+        "instock" => "✅",
+        "outofstock" => "❌",
+        "preorder" => "🕒",
+        _ => "•",
+    }
+}
+
+fn availability_label(status: &str) -> String {
+    let icon = availability_icon(status);
+    format!("{icon} {status}")
+}
+
+fn format_availability_counts(counts: &BTreeMap<String, usize>) -> Option<String> {
+    if counts.is_empty() {
+        return None;
+    }
+    let mut entries: Vec<(String, usize)> = counts.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    let rendered = entries
+        .into_iter()
+        .map(|(status, count)| format!("{count} {status}"))
+        .collect::<Vec<_>>()
+        .join(" / ");
+    Some(rendered)
+}
+
+fn format_price_range(stats: &PriceStats) -> String {
+    if (stats.min - stats.max).abs() < 1e-4 {
+        format_price_with_precision(stats.min, stats.currency.as_deref(), 2)
+    } else {
+        format!(
+            "{} – {}",
+            format_price_with_precision(stats.min, stats.currency.as_deref(), 2),
+            format_price_with_precision(stats.max, stats.currency.as_deref(), 2)
+        )
+    }
+}
+
+/// Write a product group: its summary, the properties its variants share and
+/// the variant table
+pub fn render_product_group(buf: &mut String, pg: &ProductGroupSummary) {
+    let title = pg.name.as_deref().unwrap_or("ProductGroup");
+    push_section_header(buf, "📦", &format!("ProductGroup: {title}"));
+    if let Some(id) = pg.product_group_id.as_ref() {
+        push_key_value(buf, "ProductGroup ID", id);
+    }
+    if let Some(brand) = pg.brand.as_ref() {
+        push_key_value(buf, "Brand", brand);
+    }
+    if !pg.varies_by.is_empty() {
+        push_key_value(buf, "Varies By", &pg.varies_by.join(", "));
+    }
+    if pg.total_variants > 0 {
+        push_key_value(buf, "Total Variants", &pg.total_variants.to_string());
+    }
+    if let Some(stats) = pg.price_stats.as_ref() {
+        push_key_value(buf, "Price Range", &format_price_range(stats));
+    }
+    if let Some(avail) = format_availability_counts(&pg.availability_counts) {
+        push_key_value(buf, "Availability", &avail);
+    }
+
+    // Display common properties (non-varies properties shared by all variants)
+    if !pg.common_properties.is_empty() {
+        let _ = writeln!(buf);
+        let _ = writeln!(buf, "**Common Properties** (shared by all variants):");
+
+        // Sort keys for consistent display
+        let mut sorted_keys: Vec<_> = pg.common_properties.keys().collect();
+        sorted_keys.sort();
+
+        for key in sorted_keys {
+            if let Some(value) = pg.common_properties.get(key) {
+                // Skip name as it's already shown in the title
+                if key.to_lowercase() != "name" {
+                    push_key_value(buf, key, value);
+                }
+            }
+        }
+    }
+
+    let _ = writeln!(buf);
+
+    render_variant_table(
+        buf,
+        &pg.variants,
+        &pg.varies_by,
+        &pg.direct_properties,
+        pg.total_variants,
+    );
+}
+
+fn render_variant_table(
+    buf: &mut String,
+    variants: &[VariantSummary],
+    varies_by: &[String],
+    direct_properties: &[String],
+    total_variants: usize,
+) {
+    if variants.is_empty() {
+        return;
+    }
+
+    push_section_header(buf, "🧩", "Variants");
+
+    // Check if any variant has these standard Schema.org properties
+    let has_gtin = variants.iter().any(|v| v.gtin.is_some());
+    let has_mpn = variants.iter().any(|v| v.mpn.is_some());
+    let has_weight = variants.iter().any(|v| v.weight.is_some());
+    let has_material = variants.iter().any(|v| v.material.is_some());
+    let has_model = variants.iter().any(|v| v.model.is_some());
+    let has_category = variants.iter().any(|v| v.category.is_some());
+    let has_width = variants.iter().any(|v| v.width.is_some());
+    let has_height = variants.iter().any(|v| v.height.is_some());
+    let has_depth = variants.iter().any(|v| v.depth.is_some());
+
+    // Build dynamic headers based on variesBy
+    let mut headers = vec!["SKU"];
+
+    // Add optional standard properties if any variant has them
+    if has_gtin {
+        headers.push("GTIN");
+    }
+    if has_mpn {
+        headers.push("MPN");
+    }
+    if has_model {
+        headers.push("Model");
+    }
+    if has_category {
+        headers.push("Category");
+    }
+    if has_material {
+        headers.push("Material");
+    }
+    if has_weight {
+        headers.push("Weight");
+    }
+    if has_width {
+        headers.push("Width");
+    }
+    if has_height {
+        headers.push("Height");
+    }
+    if has_depth {
+        headers.push("Depth");
+    }
+
+    let mut columns_to_show: Vec<String> = Vec::new();
+
+    // Add columns based on variesBy - show ALL properties
+    for vary in varies_by {
+        // variesBy names the schema.org property (`size`) or, on many pages,
+        // capitalizes it (`Size`)
+        let (header_name, column_key) = match vary.to_lowercase().as_str() {
+            "color" => ("Color", "Color"),
+            "size" => ("Size", "Size"),
+            "framesize" => {
+                // Avoid duplicate if we already have Size
+                if columns_to_show.contains(&"Size".to_string()) {
+                    continue;
+                }
+                ("Size", "Size")
+            }
+            "frametype" => {
+                // Avoid duplicate if we already have FrameShape
+                if columns_to_show.contains(&"FrameShape".to_string()) {
+                    continue;
+                }
+                ("FrameShape", "FrameShape")
+            }
+            "frameshape" => ("FrameShape", "FrameShape"),
+            "batterycapacity" => ("Battery", "BatteryCapacity"),
+            // For any other property, use the property name as-is
+            _ => (vary.as_str(), vary.as_str()),
+        };
+
+        headers.push(header_name);
+        columns_to_show.push(column_key.to_string());
+    }
+
+    // Add columns for direct properties that aren't already shown
+    // (exclude standard Schema.org properties as they're handled separately)
+    for prop in direct_properties {
+        let prop_lower = prop.to_lowercase();
+        // Skip standard properties we already handle explicitly
+        if matches!(
+            prop_lower.as_str(),
+            "sku"
+                | "gtin"
+                | "mpn"
+                | "weight"
+                | "color"
+                | "size"
+                | "material"
+                | "model"
+                | "category"
+                | "width"
+                | "height"
+                | "depth"
+        ) {
+            continue;
+        }
+        // Skip if already in variesBy columns
+        if columns_to_show.contains(prop) {
+            continue;
+        }
+
+        // Check if at least one variant has this property with a value
+        let has_value = variants.iter().any(|v| {
+            v.additional
+                .get(prop)
+                .map(|s| !s.is_empty())
+                .unwrap_or(false)
+        });
+
+        if has_value {
+            headers.push(prop);
+            columns_to_show.push(prop.clone());
+        }
+    }
+
+    // Always add Price and Availability at the end
+    headers.push("Price");
+    headers.push("Availability");
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for variant in variants {
+        let mut row = vec![variant.sku.clone().unwrap_or_else(|| "–".to_string())];
+
+        // Add standard Schema.org properties if we're showing them
+        if has_gtin {
+            row.push(variant.gtin.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_mpn {
+            row.push(variant.mpn.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_model {
+            row.push(variant.model.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_category {
+            row.push(variant.category.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_material {
+            row.push(variant.material.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_weight {
+            row.push(variant.weight.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_width {
+            row.push(variant.width.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_height {
+            row.push(variant.height.clone().unwrap_or_else(|| "–".to_string()));
+        }
+        if has_depth {
+            row.push(variant.depth.clone().unwrap_or_else(|| "–".to_string()));
+        }
+
+        // Add cells based on which columns we're showing
+        for column in &columns_to_show {
+            let cell = match column.as_str() {
+                "Size" => variant.size.clone().unwrap_or_else(|| "–".to_string()),
+                "Color" => variant.color.clone().unwrap_or_else(|| "–".to_string()),
+                "FrameShape" => variant
+                    .frame_shape
+                    .clone()
+                    .or_else(|| variant.additional.get("FrameType").cloned())
+                    .or_else(|| variant.additional.get("FrameShape").cloned())
+                    .unwrap_or_else(|| "–".to_string()),
+                "BatteryCapacity" => variant
+                    .battery
+                    .clone()
+                    .or_else(|| variant.additional.get("BatteryCapacity").cloned())
+                    .unwrap_or_else(|| "–".to_string()),
+                // For any other property, look it up in additional properties
+                other => variant
+                    .additional
+                    .get(other)
+                    .cloned()
+                    .unwrap_or_else(|| "–".to_string()),
+            };
+            row.push(cell);
+        }
+
+        // Add price and availability
+        row.push(
+            variant
+                .price_display
+                .clone()
+                .unwrap_or_else(|| "–".to_string()),
+        );
+        row.push(
+            variant
+                .availability
+                .as_ref()
+                .map(|status| availability_label(status))
+                .unwrap_or_else(|| "–".to_string()),
+        );
+
+        rows.push(row);
+    }
+
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (idx, cell) in row.iter().enumerate() {
+            widths[idx] = widths[idx].max(cell.len());
+        }
+    }
+
+    let format_row = |cells: &[String]| -> String {
+        let mut parts = Vec::with_capacity(cells.len());
+        for (idx, cell) in cells.iter().enumerate() {
+            parts.push(format!(" {:<width$} ", cell, width = widths[idx]));
+        }
+        format!("|{}|", parts.join("|"))
+    };
+
+    let header_cells = headers.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let _ = writeln!(buf, "{}", format_row(&header_cells));
+
+    let mut separator_parts = Vec::new();
+    for width in &widths {
+        separator_parts.push(format!(" {:-<width$} ", "", width = *width));
+    }
+    let _ = writeln!(buf, "|{}|", separator_parts.join("|"));
+
+    for row in rows {
+        let _ = writeln!(buf, "{}", format_row(&row));
+    }
+
+    if total_variants > variants.len() {
+        let remaining = total_variants - variants.len();
+        let _ = writeln!(buf, "({remaining} additional variants not shown)");
+    }
+
+    let _ = writeln!(buf);
+}
+
+/// Write the condensed graph summary lines
+pub fn render_graph_summary(buf: &mut String, lines: &[String]) {
+    if lines.is_empty() {
+        return;
+    }
+    push_section_header(buf, "🕸️", "Graph Summary (condensed)");
+    for line in lines {
+        let _ = writeln!(buf, "{}", line);
+    }
+    let _ = writeln!(buf);
+}
+
+/// Write an organization with its contact details and rating
+pub fn render_organization(buf: &mut String, org: &OrganizationSummary) {
+    push_section_header(buf, "🏢", "Organization");
+    if let Some(name) = org.name.as_ref() {
+        push_key_value(buf, "Name", name);
+    }
+    if let Some(logo) = org.logo.as_ref() {
+        push_key_value(buf, "Logo", logo);
+    }
+    if let Some(telephone) = org.telephone.as_ref() {
+        push_key_value(buf, "Telephone", telephone);
+    }
+    if let Some(email) = org.email.as_ref() {
+        push_key_value(buf, "Email", email);
+    }
+    if let Some(address) = org.address.as_ref() {
+        if let Some(street) = address.street.as_ref() {
+            push_key_value(buf, "Street", street);
+        }
+        if let Some(locality) = address.locality.as_ref() {
+            push_key_value(buf, "City", locality);
+        }
+        if let Some(postal_code) = address.postal_code.as_ref() {
+            push_key_value(buf, "Postal Code", postal_code);
+        }
+        if let Some(country) = address.country.as_ref() {
+            push_key_value(buf, "Country", country);
+        }
+    }
+    if let Some(rating) = org.rating.as_ref() {
+        if let Some(rating_value) = rating.rating_value.as_ref() {
+            push_key_value(buf, "Rating", rating_value);
+        }
+        if let Some(review_count) = rating.review_count.as_ref() {
+            push_key_value(buf, "Review Count", review_count);
+        }
+    }
+    let _ = writeln!(buf);
+}
+
+/// Write a breadcrumb trail in position order
+pub fn render_breadcrumb(buf: &mut String, breadcrumb: &BreadcrumbSummary) {
+    if breadcrumb.items.is_empty() {
+        return;
+    }
+
+    push_section_header(buf, "🍞", "Breadcrumb Navigation");
+
+    let mut sorted_items = breadcrumb.items.clone();
+    sorted_items.sort_by_key(|item| item.position);
+
+    let breadcrumb_trail: Vec<String> = sorted_items
+        .iter()
+        .map(|item| {
+            if item.url.is_empty() {
+                item.name.clone()
+            } else {
+                format!("{} ({})", item.name, item.url)
+            }
+        })
+        .collect();
+
+    let _ = writeln!(buf, "{}", breadcrumb_trail.join(" → "));
+    let _ = writeln!(buf);
+}
+
+/// Write a web page's URL and speakable parts, if it has either
+pub fn render_webpage(buf: &mut String, webpage: &WebPageSummary) {
+    // Only render if there's actual content
+    if webpage.url.is_none() && webpage.speakable.is_none() {
+        return;
+    }
+
+    push_section_header(buf, "🌐", "Web Page");
+    if let Some(url) = webpage.url.as_ref() {
+        push_key_value(buf, "URL", url);
+    }
+    if let Some(speakable) = webpage.speakable.as_ref() {
+        push_key_value(buf, "Speakable", speakable);
+    }
+    let _ = writeln!(buf);
+}
+
+/// Write the page's head metadata, if it declares any
+pub fn render_page_metadata(buf: &mut String, metadata: &parser::PageMetadata) {
+    if metadata.is_empty() {
+        return;
+    }
+    push_section_header(buf, "🏷️", "Page Metadata");
+    let text = |value: &Option<String>| value.clone().unwrap_or_default();
+
+    push_key_value(buf, "Title", &text(&metadata.title));
+    push_key_value(buf, "Description", &text(&metadata.description));
+    push_key_value(buf, "Canonical", &text(&metadata.canonical));
+    push_key_value(buf, "Language", &text(&metadata.lang));
+    push_key_value(buf, "Robots", &text(&metadata.robots));
+
+    let alternates: Vec<String> = metadata
+        .alternates
+        .iter()
+        .map(|alternate| format!("{} → {}", alternate.hreflang, alternate.href))
+        .collect();
+    push_key_value(buf, "Hreflang", &alternates.join(", "));
+
+    let icons: Vec<String> = metadata
+        .icons
+        .iter()
+        .map(|icon| match icon.sizes.as_deref() {
+            Some(sizes) => format!("{} ({}, {})", icon.href, icon.rel, sizes),
+            None => format!("{} ({})", icon.href, icon.rel),
+        })
+        .collect();
+    push_key_value(buf, "Icons", &icons.join(", "));
+
+    let og = &metadata.open_graph;
+    push_key_value(buf, "og:type", &text(&og.kind));
+    push_key_value(buf, "og:title", &text(&og.title));
+    push_key_value(buf, "og:description", &text(&og.description));
+    push_key_value(buf, "og:url", &text(&og.url));
+    push_key_value(buf, "og:site_name", &text(&og.site_name));
+    push_key_value(buf, "og:locale", &text(&og.locale));
+    for image in &og.images {
+        match (image.width, image.height) {
+            (Some(width), Some(height)) => push_key_value(
+                buf,
+                "og:image",
+                &format!("{} ({width}×{height})", image.url),
+            ),
+            _ => push_key_value(buf, "og:image", &image.url),
+        }
+    }
+    for video in &og.videos {
+        push_key_value(buf, "og:video", &video.url);
+    }
+
+    let twitter = &metadata.twitter;
+    push_key_value(buf, "twitter:card", &text(&twitter.card));
+    push_key_value(buf, "twitter:site", &text(&twitter.site));
+    push_key_value(buf, "twitter:creator", &text(&twitter.creator));
+    push_key_value(buf, "twitter:title", &text(&twitter.title));
+    push_key_value(buf, "twitter:image", &text(&twitter.image));
+    let _ = writeln!(buf);
+}
+
+/// Write an entity's ID and literal properties
+pub fn render_entity(buf: &mut String, entity: &EntitySummary) {
+    // Only render if there are properties to show
+    let has_real_id = !entity.id.starts_with("_:");
+    if !has_real_id && entity.properties.is_empty() {
+        return;
+    }
+
+    push_section_header(buf, "📋", &entity.entity_type);
+    // Don't show internal blank node IDs to users
+    if has_real_id {
+        push_key_value(buf, "ID", &entity.id);
+    }
+    for (key, value) in &entity.properties {
+        push_key_value(buf, key, value);
+    }
+    let _ = writeln!(buf);
+}
+
+/// Write the data downloads, or a note that there are none
+pub fn render_data_downloads_section(buf: &mut String, entries: &[DataDownloadEntry]) {
+    push_section_header(buf, "🌐", "Data Downloads");
+    if entries.is_empty() {
+        let _ = writeln!(buf, "No data downloads detected.");
+    } else {
+        let label = if entries.len() == 1 {
+            "data source"
+        } else {
+            "data sources"
+        };
+        let _ = writeln!(buf, "✓ Found {} official {label}:", entries.len());
+        for entry in entries {
+            let _ = writeln!(buf, "  • {}", entry.content_url);
+            if let Some(format) = entry.encoding_format.as_ref() {
+                let _ = writeln!(buf, "    ↳ encodingFormat: {format}");
+            }
+            if let Some(license) = entry.license.as_ref() {
+                let _ = writeln!(buf, "    ↳ license: {license}");
+            }
+        }
+    }
+    let _ = writeln!(buf);
+}
@@ -0,0 +1,106 @@
+//! Per-block structured data diagnostics
+//!
+//! A malformed JSON-LD block should not hide the rest of a page's structured
+//! data. Blocks that fail to parse or expand are skipped and reported with
+//! enough context to find them in the HTML. In lenient mode, blocks that a
+//! strict parser rejects may be recovered; they are still reported, with the
+//! repairs that were needed.
+
+use serde::{Deserialize, Serialize};
+
+use super::StructuredDataSource;
+use super::repair::JsonRepair;
+
+/// Processing step a block failed at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiagnosticStage {
+    /// Not valid JSON
+    Parse,
+    /// Valid JSON, but not a JSON-LD document (top level is not an object or array)
+    Invalid,
+    /// JSON-LD expansion failed (bad context, invalid keyword use, ...)
+    Expansion,
+}
+
+impl DiagnosticStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticStage::Parse => "parse",
+            DiagnosticStage::Invalid => "invalid",
+            DiagnosticStage::Expansion => "expansion",
+        }
+    }
+}
+
+/// A structured data block that could not be processed as-is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockDiagnostic {
+    /// Syntax the block was embedded in
+    pub source: StructuredDataSource,
+
+    /// Position among the page's blocks of that syntax (0-based)
+    pub index: usize,
+
+    /// Byte offset of the block's content in the HTML (JSON-LD scripts only)
+    pub offset: Option<usize>,
+
+    /// Line of the JSON syntax error within the block (1-based)
+    pub line: Option<usize>,
+
+    /// Column of the JSON syntax error within the block (1-based)
+    pub column: Option<usize>,
+
+    pub stage: DiagnosticStage,
+
+    pub message: String,
+
+    /// Repairs that recovered the block in lenient mode; when present, the
+    /// block was used despite the error
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repairs: Vec<JsonRepair>,
+}
+
+impl BlockDiagnostic {
+    /// Diagnostic for a JSON syntax error
+    pub fn from_json_error(
+        source: StructuredDataSource,
+        index: usize,
+        offset: Option<usize>,
+        err: &serde_json::Error,
+    ) -> Self {
+        // serde_json appends the position, which is reported separately
+        let message = err.to_string();
+        let suffix = format!(" at line {} column {}", err.line(), err.column());
+        Self {
+            source,
+            index,
+            offset,
+            line: Some(err.line()),
+            column: Some(err.column()),
+            stage: DiagnosticStage::Parse,
+            message: message
+                .strip_suffix(&suffix)
+                .unwrap_or(&message)
+                .to_string(),
+            repairs: Vec::new(),
+        }
+    }
+
+    /// Whether the block was recovered and included despite the error
+    pub fn is_recovered(&self) -> bool {
+        !self.repairs.is_empty()
+    }
+
+    /// Human-readable location, e.g. "json-ld block #2 at byte 1042, line 3, column 17"
+    pub fn location(&self) -> String {
+        let mut location = format!("{} block #{}", self.source.as_str(), self.index + 1);
+        if let Some(offset) = self.offset {
+            location.push_str(&format!(" at byte {offset}"));
+        }
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            location.push_str(&format!(", line {line}, column {column}"));
+        }
+        location
+    }
+}
@@ -0,0 +1,441 @@
+//! `<head>` metadata extraction
+//!
+//! Collects what link-unfurling agents and crawlers read before (or instead
+//! of) the page body: the title and meta description, canonical URL, robots
+//! directives, Open Graph and Twitter Card tags, `hreflang` alternates, icons
+//! and the document language.
+//!
+//! Open Graph: https://ogp.me/ — Twitter Cards:
+//! https://developer.x.com/en/docs/twitter-for-websites/cards/overview/markup
+
+use std::collections::BTreeMap;
+
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::resolve_url;
+
+/// Metadata declared in a page's `<head>`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageMetadata {
+    /// `<title>` text (empty if the element is empty)
+    pub title: Option<String>,
+
+    /// `<meta name="description">`
+    pub description: Option<String>,
+
+    /// `<link rel="canonical">` (absolute)
+    pub canonical: Option<String>,
+
+    /// `<meta name="robots">` directives, e.g. "noindex, nofollow"
+    pub robots: Option<String>,
+
+    /// `<html lang>`
+    pub lang: Option<String>,
+
+    /// Open Graph (`og:*`) properties
+    pub open_graph: OpenGraph,
+
+    /// Twitter Card (`twitter:*`) properties
+    pub twitter: TwitterCard,
+
+    /// `<link rel="alternate" hreflang>` translations
+    pub alternates: Vec<HreflangAlternate>,
+
+    /// Favicons and touch icons
+    pub icons: Vec<PageIcon>,
+}
+
+/// Open Graph properties
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenGraph {
+    pub title: Option<String>,
+    pub description: Option<String>,
+
+    /// `og:type` ("website", "article", "product", ...)
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+
+    pub url: Option<String>,
+    pub site_name: Option<String>,
+    pub locale: Option<String>,
+
+    /// `og:locale:alternate` values
+    pub locale_alternates: Vec<String>,
+
+    /// `og:image` values, each with its structured properties
+    pub images: Vec<OpenGraphMedia>,
+
+    /// `og:video` values
+    pub videos: Vec<OpenGraphMedia>,
+
+    /// `og:audio` values
+    pub audio: Vec<OpenGraphMedia>,
+
+    /// Any other `og:*` or namespaced (`article:*`, `product:*`) properties
+    pub other: BTreeMap<String, Vec<String>>,
+}
+
+/// An `og:image`, `og:video` or `og:audio` with its structured properties
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenGraphMedia {
+    pub url: String,
+    pub secure_url: Option<String>,
+
+    /// MIME type
+    #[serde(rename = "type")]
+    pub mime_type: Option<String>,
+
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub alt: Option<String>,
+}
+
+/// Twitter Card properties
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TwitterCard {
+    /// Card type ("summary", "summary_large_image", ...)
+    pub card: Option<String>,
+
+    /// @username of the site
+    pub site: Option<String>,
+
+    /// @username of the content creator
+    pub creator: Option<String>,
+
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub image_alt: Option<String>,
+
+    /// Any other `twitter:*` properties
+    pub other: BTreeMap<String, String>,
+}
+
+/// A translation of the page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HreflangAlternate {
+    /// Language tag, or "x-default"
+    pub hreflang: String,
+
+    /// Absolute URL
+    pub href: String,
+}
+
+/// A favicon or touch icon
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageIcon {
+    /// `rel` value ("icon", "apple-touch-icon", ...)
+    pub rel: String,
+
+    /// Absolute URL
+    pub href: String,
+
+    /// `sizes`, e.g. "180x180"
+    pub sizes: Option<String>,
+
+    /// MIME type
+    #[serde(rename = "type")]
+    pub mime_type: Option<String>,
+}
+
+impl PageMetadata {
+    /// Whether the page declares none of these values (an empty `<title>`
+    /// counts as none)
+    pub fn is_empty(&self) -> bool {
+        let title = self.title.as_deref().filter(|title| !title.is_empty());
+        title.is_none()
+            && *self
+                == Self {
+                    title: self.title.clone(),
+                    ..Self::default()
+                }
+    }
+
+    /// Whether the robots meta tag keeps the page out of search indexes
+    pub fn is_noindex(&self) -> bool {
+        self.robots.as_deref().is_some_and(|robots| {
+            robots.split(',').any(|directive| {
+                matches!(
+                    directive.trim().to_ascii_lowercase().as_str(),
+                    "noindex" | "none"
+                )
+            })
+        })
+    }
+}
+
+/// Extract `<head>` metadata from a page fetched from `base_url`
+///
+/// Relative canonical, alternate, icon and Open Graph media URLs are resolved
+/// against `base_url`. For single-valued properties the first occurrence wins.
+pub fn extract_page_metadata(html: &str, base_url: &str) -> PageMetadata {
+    let document = Html::parse_document(html);
+    let base = Url::parse(base_url).ok();
+    let resolve = |url: &str| resolve_url(base.as_ref(), url);
+
+    let mut metadata = PageMetadata {
+        lang: document
+            .root_element()
+            .value()
+            .attr("lang")
+            .map(str::trim)
+            .filter(|lang| !lang.is_empty())
+            .map(str::to_string),
+        ..Default::default()
+    };
+
+    // Skip SVG <title>s in the body
+    for selector in ["head title", "title"] {
+        if let Ok(selector) = Selector::parse(selector)
+            && let Some(title) = document.select(&selector).next()
+        {
+            metadata.title = Some(title.text().collect::<String>().trim().to_string());
+            break;
+        }
+    }
+
+    if let Ok(selector) = Selector::parse("meta") {
+        for meta in document.select(&selector) {
+            let attrs = meta.value();
+            let (Some(key), Some(content)) = (
+                attrs.attr("property").or_else(|| attrs.attr("name")),
+                attrs.attr("content"),
+            ) else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let content = content.trim().to_string();
+
+            match key.as_str() {
+                "description" => set_once(&mut metadata.description, content),
+                "robots" => set_once(&mut metadata.robots, content),
+                key if key.starts_with("twitter:") => metadata.twitter.add(key, content),
+                key if key.starts_with("og:") || is_open_graph_namespace(key) => {
+                    metadata.open_graph.add(key, content, &resolve)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Ok(selector) = Selector::parse("link[rel][href]") {
+        for link in document.select(&selector) {
+            let attrs = link.value();
+            let rel = attrs.attr("rel").unwrap_or_default().to_ascii_lowercase();
+            let href = resolve(attrs.attr("href").unwrap_or_default());
+            let rels: Vec<&str> = rel.split_ascii_whitespace().collect();
+
+            if rels.contains(&"canonical") {
+                set_once(&mut metadata.canonical, href);
+            } else if rels.contains(&"alternate")
+                && let Some(hreflang) = attrs.attr("hreflang")
+            {
+                metadata.alternates.push(HreflangAlternate {
+                    hreflang: hreflang.trim().to_string(),
+                    href,
+                });
+            } else if rels.iter().any(|rel| rel.contains("icon")) {
+                metadata.icons.push(PageIcon {
+                    rel: rels.join(" "),
+                    href,
+                    sizes: attrs.attr("sizes").map(str::to_string),
+                    mime_type: attrs.attr("type").map(str::to_string),
+                });
+            }
+        }
+    }
+
+    metadata
+}
+
+impl OpenGraph {
+    fn add(&mut self, key: &str, value: String, resolve: &dyn Fn(&str) -> String) {
+        match key {
+            "og:title" => set_once(&mut self.title, value),
+            "og:description" => set_once(&mut self.description, value),
+            "og:type" => set_once(&mut self.kind, value),
+            "og:url" => set_once(&mut self.url, value),
+            "og:site_name" => set_once(&mut self.site_name, value),
+            "og:locale" => set_once(&mut self.locale, value),
+            "og:locale:alternate" => self.locale_alternates.push(value),
+            _ => {
+                let media = [
+                    ("og:image", &mut self.images),
+                    ("og:video", &mut self.videos),
+                    ("og:audio", &mut self.audio),
+                ];
+                for (prefix, items) in media {
+                    let Some(rest) = key.strip_prefix(prefix) else {
+                        continue;
+                    };
+                    match rest {
+                        // A new item; structured properties apply to the latest
+                        "" | ":url" => items.push(OpenGraphMedia {
+                            url: resolve(&value),
+                            ..Default::default()
+                        }),
+                        _ => {
+                            if let Some(item) = items.last_mut() {
+                                item.set(rest, value, resolve);
+                            }
+                        }
+                    }
+                    return;
+                }
+                self.other.entry(key.to_string()).or_default().push(value);
+            }
+        }
+    }
+}
+
+impl OpenGraphMedia {
+    fn set(&mut self, property: &str, value: String, resolve: &dyn Fn(&str) -> String) {
+        match property {
+            ":secure_url" => self.secure_url = Some(resolve(&value)),
+            ":type" => self.mime_type = Some(value),
+            ":width" => self.width = value.parse().ok(),
+            ":height" => self.height = value.parse().ok(),
+            ":alt" => self.alt = Some(value),
+            _ => {}
+        }
+    }
+}
+
+impl TwitterCard {
+    fn add(&mut self, key: &str, value: String) {
+        match key {
+            "twitter:card" => set_once(&mut self.card, value),
+            "twitter:site" => set_once(&mut self.site, value),
+            "twitter:creator" => set_once(&mut self.creator, value),
+            "twitter:title" => set_once(&mut self.title, value),
+            "twitter:description" => set_once(&mut self.description, value),
+            "twitter:image" | "twitter:image:src" => set_once(&mut self.image, value),
+            "twitter:image:alt" => set_once(&mut self.image_alt, value),
+            _ => {
+                self.other.entry(key.to_string()).or_insert(value);
+            }
+        }
+    }
+}
+
+/// Open Graph object namespaces (`article:published_time`, `product:price:amount`, ...)
+fn is_open_graph_namespace(key: &str) -> bool {
+    [
+        "article:", "book:", "profile:", "product:", "music:", "video:",
+    ]
+    .iter()
+    .any(|namespace| key.starts_with(namespace))
+}
+
+fn set_once(field: &mut Option<String>, value: String) {
+    if field.is_none() {
+        *field = Some(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = r#"<!doctype html>
+<html lang="nl-NL">
+<head>
+    <title> Paralane 8.8 | Focus Bikes </title>
+    <meta name="description" content="Lightweight e-road bike">
+    <meta name="robots" content="index, follow">
+    <link rel="canonical" href="/nl_nl/paralane-8-8">
+    <link rel="alternate" hreflang="en-GB" href="https://example.com/en_gb/paralane-8-8">
+    <link rel="alternate" hreflang="x-default" href="/paralane-8-8">
+    <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+    <link rel="icon" href="/favicon.ico">
+    <link rel="apple-touch-icon" sizes="180x180" href="/apple-touch-icon.png">
+    <link rel="stylesheet" href="/site.css">
+    <meta property="og:type" content="product">
+    <meta property="og:title" content="Paralane 8.8">
+    <meta property="og:site_name" content="Focus Bikes">
+    <meta property="og:locale" content="nl_NL">
+    <meta property="og:locale:alternate" content="en_GB">
+    <meta property="og:image" content="/img/side.jpg">
+    <meta property="og:image:width" content="1200">
+    <meta property="og:image:height" content="630">
+    <meta property="og:image" content="https://cdn.example.com/front.jpg">
+    <meta property="og:image:alt" content="Front view">
+    <meta property="product:price:amount" content="3999">
+    <meta name="twitter:card" content="summary_large_image">
+    <meta name="twitter:site" content="@focusbikes">
+    <meta name="twitter:image:src" content="https://cdn.example.com/twitter.jpg">
+    <meta name="twitter:label1" content="Price">
+</head>
+<body><svg><title>Icon</title></svg></body>
+</html>"#;
+
+    #[test]
+    fn test_extract_head_metadata() {
+        let metadata = extract_page_metadata(HTML, "https://example.com/nl_nl/paralane-8-8?ref=1");
+
+        assert_eq!(
+            metadata.title.as_deref(),
+            Some("Paralane 8.8 | Focus Bikes")
+        );
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("Lightweight e-road bike")
+        );
+        assert_eq!(
+            metadata.canonical.as_deref(),
+            Some("https://example.com/nl_nl/paralane-8-8")
+        );
+        assert_eq!(metadata.lang.as_deref(), Some("nl-NL"));
+        assert!(!metadata.is_noindex());
+
+        assert_eq!(metadata.alternates.len(), 2);
+        assert_eq!(metadata.alternates[1].hreflang, "x-default");
+        assert_eq!(
+            metadata.alternates[1].href,
+            "https://example.com/paralane-8-8"
+        );
+
+        assert_eq!(metadata.icons.len(), 2);
+        assert_eq!(metadata.icons[1].rel, "apple-touch-icon");
+        assert_eq!(metadata.icons[1].sizes.as_deref(), Some("180x180"));
+    }
+
+    #[test]
+    fn test_open_graph_arrays_and_twitter_card() {
+        let metadata = extract_page_metadata(HTML, "https://example.com/");
+        let og = &metadata.open_graph;
+
+        assert_eq!(og.kind.as_deref(), Some("product"));
+        assert_eq!(og.site_name.as_deref(), Some("Focus Bikes"));
+        assert_eq!(og.locale_alternates, vec!["en_GB"]);
+        assert_eq!(og.images.len(), 2);
+        assert_eq!(og.images[0].url, "https://example.com/img/side.jpg");
+        assert_eq!(og.images[0].width, Some(1200));
+        assert_eq!(og.images[0].height, Some(630));
+        assert_eq!(og.images[1].alt.as_deref(), Some("Front view"));
+        assert_eq!(og.other["product:price:amount"], vec!["3999"]);
+
+        let twitter = &metadata.twitter;
+        assert_eq!(twitter.card.as_deref(), Some("summary_large_image"));
+        assert_eq!(twitter.site.as_deref(), Some("@focusbikes"));
+        assert_eq!(
+            twitter.image.as_deref(),
+            Some("https://cdn.example.com/twitter.jpg")
+        );
+        assert_eq!(twitter.other["twitter:label1"], "Price");
+    }
+
+    #[test]
+    fn test_missing_and_noindex() {
+        let metadata =
+            extract_page_metadata(r#"<meta name="ROBOTS" content="NoIndex, follow">"#, "");
+        assert!(metadata.title.is_none());
+        assert!(metadata.is_noindex());
+        assert!(!metadata.is_empty());
+        assert_eq!(metadata.open_graph, OpenGraph::default());
+
+        assert!(extract_page_metadata("<title></title><p>Hi</p>", "").is_empty());
+    }
+}
@@ -0,0 +1,366 @@
+//! HTML Microdata extraction
+//!
+//! Implements the WHATWG microdata algorithm (`itemscope`, `itemtype`,
+//! `itemprop`, `itemid` and `itemref`) and converts every top-level item to a
+//! JSON-LD object, so microdata can be combined with JSON-LD blocks and go
+//! through the same expansion and graph building.
+//!
+//! Specification: https://html.spec.whatwg.org/multipage/microdata.html
+
+use std::collections::{HashMap, HashSet};
+
+use ego_tree::NodeId;
+use scraper::{ElementRef, Html};
+use serde_json::{Map, Value as JsonValue};
+use url::Url;
+
+use super::{SCHEMA_ORG_VOCABULARIES, compact_iri, resolve_url};
+
+/// Extract the top-level microdata items of a page as JSON-LD objects
+///
+/// Schema.org types and properties are emitted as terms of the
+/// `https://schema.org` context, so they merge with JSON-LD blocks; other
+/// vocabularies use absolute IRIs. Relative URLs are resolved against
+/// `base_url`.
+pub fn extract_microdata(html: &str, base_url: &str) -> Vec<JsonValue> {
+    let document = Html::parse_document(html);
+    let extractor = Extractor::new(&document, base_url);
+
+    extractor
+        .top_level_items()
+        .map(|element| {
+            let mut item = extractor.item(element, &mut Vec::new());
+            if let JsonValue::Object(map) = &mut item {
+                map.insert(
+                    "@context".to_string(),
+                    JsonValue::String("https://schema.org".to_string()),
+                );
+            }
+            item
+        })
+        .collect()
+}
+
+struct Extractor<'a> {
+    base: Option<Url>,
+
+    /// All elements in tree order
+    elements: Vec<ElementRef<'a>>,
+
+    /// Tree-order position of each element
+    order: HashMap<NodeId, usize>,
+
+    /// First element with each `id`, for `itemref`
+    ids: HashMap<&'a str, ElementRef<'a>>,
+}
+
+impl<'a> Extractor<'a> {
+    fn new(document: &'a Html, base_url: &str) -> Self {
+        let elements: Vec<ElementRef<'a>> = document
+            .root_element()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .collect();
+
+        let mut ids = HashMap::new();
+        for element in &elements {
+            if let Some(id) = element.value().id() {
+                ids.entry(id).or_insert(*element);
+            }
+        }
+
+        Self {
+            base: Url::parse(base_url).ok(),
+            order: elements
+                .iter()
+                .enumerate()
+                .map(|(position, element)| (element.id(), position))
+                .collect(),
+            elements,
+            ids,
+        }
+    }
+
+    /// Items that are not the value of another item's property
+    fn top_level_items(&self) -> impl Iterator<Item = ElementRef<'a>> + '_ {
+        self.elements.iter().copied().filter(|element| {
+            let value = element.value();
+            value.attr("itemscope").is_some() && value.attr("itemprop").is_none()
+        })
+    }
+
+    /// Convert an item to a JSON-LD node object
+    ///
+    /// `stack` holds the items being converted, to break `itemref` cycles.
+    fn item(&self, element: ElementRef<'a>, stack: &mut Vec<NodeId>) -> JsonValue {
+        let mut object = Map::new();
+        let types: Vec<&str> = element
+            .value()
+            .attr("itemtype")
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+            .filter(|t| Url::parse(t).is_ok())
+            .collect();
+        let vocabulary = types.first().map(|t| vocabulary(t));
+
+        // Global identifiers are only meaningful for typed items
+        if !types.is_empty()
+            && let Some(id) = self.item_id(element)
+        {
+            object.insert("@id".to_string(), JsonValue::String(id));
+        }
+
+        match types.as_slice() {
+            [] => {}
+            [single] => {
+                object.insert("@type".to_string(), JsonValue::String(compact_iri(single)));
+            }
+            many => {
+                object.insert(
+                    "@type".to_string(),
+                    JsonValue::Array(
+                        many.iter()
+                            .map(|t| JsonValue::String(compact_iri(t)))
+                            .collect(),
+                    ),
+                );
+            }
+        }
+
+        stack.push(element.id());
+        for property in self.properties(element) {
+            let Some(value) = self.property_value(property, stack) else {
+                continue;
+            };
+            for name in property
+                .value()
+                .attr("itemprop")
+                .unwrap_or_default()
+                .split_ascii_whitespace()
+            {
+                insert_value(
+                    &mut object,
+                    property_key(name, vocabulary.as_deref()),
+                    value.clone(),
+                );
+            }
+        }
+        stack.pop();
+
+        JsonValue::Object(object)
+    }
+
+    /// The properties of an item, in tree order
+    fn properties(&self, root: ElementRef<'a>) -> Vec<ElementRef<'a>> {
+        let mut pending: Vec<ElementRef<'a>> =
+            root.children().filter_map(ElementRef::wrap).collect();
+        for id in root
+            .value()
+            .attr("itemref")
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+        {
+            if let Some(element) = self.ids.get(id) {
+                pending.push(*element);
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut results = Vec::new();
+        while let Some(current) = pending.pop() {
+            if current.id() == root.id() || !visited.insert(current.id()) {
+                continue;
+            }
+            if current.value().attr("itemprop").is_some() {
+                results.push(current);
+            }
+            if current.value().attr("itemscope").is_none() {
+                pending.extend(current.children().filter_map(ElementRef::wrap));
+            }
+        }
+
+        results.sort_by_key(|element| self.order.get(&element.id()).copied());
+        results
+    }
+
+    /// The value of a property element; `None` if the attribute holding it
+    /// is missing
+    fn property_value(
+        &self,
+        element: ElementRef<'a>,
+        stack: &mut Vec<NodeId>,
+    ) -> Option<JsonValue> {
+        let value = element.value();
+        if value.attr("itemscope").is_some() {
+            // A cycle can only be represented by reference
+            if stack.contains(&element.id()) {
+                return self
+                    .item_id(element)
+                    .map(|id| serde_json::json!({ "@id": id }));
+            }
+            return Some(self.item(element, stack));
+        }
+
+        let text = match value.name() {
+            "meta" => value.attr("content")?.to_string(),
+            "audio" | "embed" | "iframe" | "img" | "source" | "track" | "video" => {
+                resolve_url(self.base.as_ref(), value.attr("src")?)
+            }
+            "a" | "area" | "link" => resolve_url(self.base.as_ref(), value.attr("href")?),
+            "object" => resolve_url(self.base.as_ref(), value.attr("data")?),
+            "data" | "meter" => value.attr("value")?.to_string(),
+            "time" => match value.attr("datetime") {
+                Some(datetime) => datetime.to_string(),
+                None => text_content(element),
+            },
+            _ => text_content(element),
+        };
+
+        Some(JsonValue::String(text))
+    }
+
+    fn item_id(&self, element: ElementRef<'a>) -> Option<String> {
+        element
+            .value()
+            .attr("itemid")
+            .map(|id| resolve_url(self.base.as_ref(), id))
+    }
+}
+
+/// Add a value to a property, turning repeated properties into arrays
+fn insert_value(object: &mut Map<String, JsonValue>, key: String, value: JsonValue) {
+    match object.get_mut(&key) {
+        Some(JsonValue::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = JsonValue::Array(vec![first, value]);
+        }
+        None => {
+            object.insert(key, value);
+        }
+    }
+}
+
+/// Vocabulary of an item type: everything up to the last `#` or `/`
+fn vocabulary(item_type: &str) -> String {
+    match item_type.rfind(['#', '/']) {
+        Some(end) => item_type[..=end].to_string(),
+        None => item_type.to_string(),
+    }
+}
+
+/// JSON-LD key for an `itemprop` name
+///
+/// Absolute property URLs are used as-is; plain names are expanded against
+/// the item's vocabulary unless that is schema.org (or the item is untyped),
+/// in which case the schema.org context maps them.
+fn property_key(name: &str, vocabulary: Option<&str>) -> String {
+    if Url::parse(name).is_ok() {
+        return compact_iri(name);
+    }
+    match vocabulary {
+        Some(vocabulary) if !SCHEMA_ORG_VOCABULARIES.contains(&vocabulary) => {
+            format!("{vocabulary}{name}")
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn text_content(element: ElementRef<'_>) -> String {
+    element.text().collect::<String>().trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_items_and_value_attributes() {
+        let html = r#"
+            <div itemscope itemtype="https://schema.org/Product" itemid="/p/bike">
+                <h1 itemprop="name"> Paralane 8.8 </h1>
+                <img itemprop="image" src="/img/bike.jpg">
+                <a itemprop="url" href="/p/bike">Bike</a>
+                <meta itemprop="sku" content="PL-88">
+                <div itemprop="offers" itemscope itemtype="http://schema.org/Offer">
+                    <data itemprop="price" value="3999">€3,999</data>
+                    <link itemprop="availability" href="https://schema.org/InStock">
+                    <time itemprop="priceValidUntil" datetime="2026-12-31">End of year</time>
+                </div>
+                <span itemprop="color">Blue</span>
+                <span itemprop="color">Grey</span>
+            </div>
+        "#;
+
+        let items = extract_microdata(html, "https://shop.example/p/bike?ref=1");
+        assert_eq!(items.len(), 1);
+
+        let product = &items[0];
+        assert_eq!(product["@context"], "https://schema.org");
+        assert_eq!(product["@type"], "Product");
+        assert_eq!(product["@id"], "https://shop.example/p/bike");
+        assert_eq!(product["name"], "Paralane 8.8");
+        assert_eq!(product["image"], "https://shop.example/img/bike.jpg");
+        assert_eq!(product["sku"], "PL-88");
+        assert_eq!(product["color"], serde_json::json!(["Blue", "Grey"]));
+
+        let offer = &product["offers"];
+        assert_eq!(offer["@type"], "Offer");
+        assert_eq!(offer["price"], "3999");
+        assert_eq!(offer["availability"], "https://schema.org/InStock");
+        assert_eq!(offer["priceValidUntil"], "2026-12-31");
+        // Nested items are not extracted as top-level items
+        assert!(offer.get("@context").is_none());
+    }
+
+    #[test]
+    fn test_itemref_and_multiple_itemprop_names() {
+        let html = r#"
+            <div itemscope itemtype="https://schema.org/Person" itemref="contact company">
+                <span itemprop="name givenName">Ada</span>
+            </div>
+            <p id="contact"><a itemprop="email" href="mailto:ada@example.com">Mail</a></p>
+            <div id="company" itemprop="worksFor" itemscope itemtype="https://schema.org/Organization">
+                <span itemprop="name">Pon</span>
+            </div>
+        "#;
+
+        let items = extract_microdata(html, "https://example.com/");
+        // The referenced organization has itemprop, so it is not top-level
+        assert_eq!(items.len(), 1);
+
+        let person = &items[0];
+        assert_eq!(person["name"], "Ada");
+        assert_eq!(person["givenName"], "Ada");
+        assert_eq!(person["email"], "mailto:ada@example.com");
+        assert_eq!(person["worksFor"]["name"], "Pon");
+    }
+
+    #[test]
+    fn test_itemref_cycle_and_other_vocabularies() {
+        let html = r#"
+            <div itemscope itemtype="https://vocab.example/ns#Set" itemref="a">
+                <span itemprop="https://schema.org/name">Parts</span>
+            </div>
+            <div id="a" itemprop="part" itemscope itemtype="https://vocab.example/ns#Thing"
+                 itemid="urn:example:a" itemref="b"></div>
+            <div id="b" itemprop="parent" itemscope itemtype="https://vocab.example/ns#Thing"
+                 itemref="a"></div>
+        "#;
+
+        let items = extract_microdata(html, "https://example.com/");
+        assert_eq!(items.len(), 1);
+
+        let set = &items[0];
+        assert_eq!(set["@type"], "https://vocab.example/ns#Set");
+        assert_eq!(set["name"], "Parts");
+
+        let part = &set["https://vocab.example/ns#part"];
+        assert_eq!(part["@id"], "urn:example:a");
+        // b refers back to a, which is still being converted
+        assert_eq!(
+            part["https://vocab.example/ns#parent"]["https://vocab.example/ns#part"],
+            serde_json::json!({ "@id": "urn:example:a" })
+        );
+    }
+}
@@ -0,0 +1,930 @@
+//! HTML fetching and parsing functionality
+
+pub mod diagnostics;
+pub mod metadata;
+pub mod microdata;
+pub mod rdfa;
+pub mod repair;
+
+pub use diagnostics::{BlockDiagnostic, DiagnosticStage};
+pub use metadata::{PageMetadata, extract_page_metadata};
+pub use repair::{JsonRepair, RepairKind, repair_json_ld};
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use url::Url;
+
+/// HTTP settings for [`fetch_html_with`]
+#[derive(Debug, Clone, Default)]
+pub struct FetchOptions {
+    /// `User-Agent` header; defaults to `Mozilla/5.0 (compatible; htmlens-core/<version>)`
+    pub user_agent: Option<String>,
+
+    /// Timeout for the whole request; none by default
+    pub timeout: Option<std::time::Duration>,
+}
+
+/// Fetch HTML content from a URL
+///
+/// Requires the `full-expansion` feature (needs reqwest)
+#[cfg(feature = "full-expansion")]
+pub async fn fetch_html(url: &str) -> Result<String> {
+    fetch_html_with(url, &FetchOptions::default()).await
+}
+
+/// Fetch HTML content from a URL with a custom user agent or timeout
+///
+/// Requires the `full-expansion` feature (needs reqwest)
+#[cfg(feature = "full-expansion")]
+pub async fn fetch_html_with(url: &str, options: &FetchOptions) -> Result<String> {
+    let user_agent = options.user_agent.clone().unwrap_or_else(|| {
+        format!(
+            "Mozilla/5.0 (compatible; htmlens-core/{})",
+            env!("CARGO_PKG_VERSION")
+        )
+    });
+    let mut builder = reqwest::Client::builder().user_agent(user_agent);
+    if let Some(timeout) = options.timeout {
+        builder = builder.timeout(timeout);
+    }
+    let client = builder.build()?;
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .context("Failed to fetch URL")?;
+
+    let html = response
+        .text()
+        .await
+        .context("Failed to read response body")?;
+    Ok(html)
+}
+
+/// Extract JSON-LD script blocks from HTML
+pub fn extract_json_ld_blocks(html: &str) -> Result<Vec<String>> {
+    Ok(extract_json_ld_scripts(html)?
+        .into_iter()
+        .map(|script| script.content)
+        .collect())
+}
+
+/// A non-empty `<script type="application/ld+json">` element
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonLdScript {
+    /// Position among the page's JSON-LD scripts (0-based)
+    pub index: usize,
+
+    /// Byte offset of the (trimmed) content in the HTML, if it could be located
+    pub offset: Option<usize>,
+
+    /// Script content, trimmed
+    pub content: String,
+}
+
+impl JsonLdScript {
+    /// Parse the script as a JSON-LD document (an object or array)
+    pub fn parse(&self) -> std::result::Result<JsonValue, BlockDiagnostic> {
+        let source = StructuredDataSource::JsonLd;
+        match serde_json::from_str::<JsonValue>(&self.content) {
+            Ok(value @ (JsonValue::Object(_) | JsonValue::Array(_))) => Ok(value),
+            Ok(_) => Err(BlockDiagnostic {
+                source,
+                index: self.index,
+                offset: self.offset,
+                line: None,
+                column: None,
+                stage: DiagnosticStage::Invalid,
+                message: "top level must be an object or array".to_string(),
+                repairs: Vec::new(),
+            }),
+            Err(err) => Err(BlockDiagnostic::from_json_error(
+                source,
+                self.index,
+                self.offset,
+                &err,
+            )),
+        }
+    }
+
+    /// Parse the script, repairing almost-JSON that a strict parser rejects
+    ///
+    /// A recovered block comes with the strict parse error and the repairs
+    /// that were applied (see [`repair_json_ld`]).
+    pub fn parse_lenient(
+        &self,
+    ) -> std::result::Result<(JsonValue, Option<BlockDiagnostic>), BlockDiagnostic> {
+        match self.parse() {
+            Ok(value) => Ok((value, None)),
+            Err(diagnostic) if diagnostic.stage == DiagnosticStage::Parse => {
+                match repair_json_ld(&self.content) {
+                    Some((value, repairs)) => Ok((
+                        value,
+                        Some(BlockDiagnostic {
+                            repairs,
+                            ..diagnostic
+                        }),
+                    )),
+                    None => Err(diagnostic),
+                }
+            }
+            Err(diagnostic) => Err(diagnostic),
+        }
+    }
+}
+
+/// Extract JSON-LD script elements with their position in the HTML
+pub fn extract_json_ld_scripts(html: &str) -> Result<Vec<JsonLdScript>> {
+    let document = Html::parse_document(html);
+    let script_selector = Selector::parse("script")
+        .map_err(|e| anyhow::anyhow!("unable to parse selector: {}", e))?;
+
+    // Scripts appear in source order, so each search starts after the previous one
+    let mut cursor = 0;
+    Ok(document
+        .select(&script_selector)
+        .filter_map(|element| {
+            let script_type = element
+                .value()
+                .attr("type")
+                .map(|t| t.trim().to_ascii_lowercase())
+                .unwrap_or_default();
+
+            // Use contains() to catch variations like "application/ld+json; charset=utf-8"
+            if script_type.contains("ld+json") {
+                let text = element.text().collect::<String>().trim().to_string();
+                if text.is_empty() { None } else { Some(text) }
+            } else {
+                None
+            }
+        })
+        .enumerate()
+        .map(|(index, content)| {
+            let offset = html
+                .get(cursor..)
+                .and_then(|rest| rest.find(&content))
+                .map(|position| cursor + position);
+            if let Some(offset) = offset {
+                cursor = offset + content.len();
+            }
+            JsonLdScript {
+                index,
+                offset,
+                content,
+            }
+        })
+        .collect())
+}
+
+/// Property recording which syntax a node was extracted from
+///
+/// [`extract_structured_data_blocks`] adds it to every node;
+/// `GraphBuilder` moves it into `GraphNode::sources`.
+pub const SOURCE_PROPERTY: &str = "urn:htmlens:source";
+
+/// Syntax that structured data was embedded in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StructuredDataSource {
+    /// `<script type="application/ld+json">`
+    JsonLd,
+    /// `itemscope` / `itemprop` attributes
+    Microdata,
+    /// RDFa Lite `typeof` / `property` attributes
+    Rdfa,
+}
+
+impl StructuredDataSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StructuredDataSource::JsonLd => "json-ld",
+            StructuredDataSource::Microdata => "microdata",
+            StructuredDataSource::Rdfa => "rdfa",
+        }
+    }
+}
+
+/// A structured data block ready for JSON-LD processing
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredDataBlock {
+    /// Syntax the block was embedded in
+    pub source: StructuredDataSource,
+
+    /// Position among the page's blocks of that syntax (0-based)
+    pub index: usize,
+
+    /// Byte offset of the block's content in the HTML (JSON-LD scripts only)
+    pub offset: Option<usize>,
+
+    /// JSON-LD with every node tagged under [`SOURCE_PROPERTY`]
+    pub json: String,
+}
+
+impl StructuredDataBlock {
+    /// Diagnostic for a block that failed at a later stage (e.g. expansion)
+    pub fn diagnostic(
+        &self,
+        stage: DiagnosticStage,
+        message: impl Into<String>,
+    ) -> BlockDiagnostic {
+        BlockDiagnostic {
+            source: self.source,
+            index: self.index,
+            offset: self.offset,
+            line: None,
+            column: None,
+            stage,
+            message: message.into(),
+            repairs: Vec::new(),
+        }
+    }
+}
+
+/// All structured data on a page, with the blocks that had to be skipped
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StructuredData {
+    /// Valid blocks: JSON-LD scripts, then microdata items, then RDFa resources
+    pub blocks: Vec<StructuredDataBlock>,
+
+    /// Blocks a strict parser rejects: skipped, or recovered in lenient mode
+    pub diagnostics: Vec<BlockDiagnostic>,
+}
+
+impl StructuredData {
+    /// Directly supplied JSON-LD, as a single block
+    pub fn from_json_ld(json_ld: &str) -> Self {
+        Self::from_json_ld_with(json_ld, false)
+    }
+
+    /// Like [`StructuredData::from_json_ld`], repairing almost-JSON
+    pub fn from_json_ld_lenient(json_ld: &str) -> Self {
+        Self::from_json_ld_with(json_ld, true)
+    }
+
+    fn from_json_ld_with(json_ld: &str, lenient: bool) -> Self {
+        let mut data = Self::default();
+        data.push_json_ld(
+            &JsonLdScript {
+                index: 0,
+                offset: None,
+                content: json_ld.trim().to_string(),
+            },
+            lenient,
+        );
+        data
+    }
+
+    /// The blocks' JSON, e.g. for [`combine_json_ld_blocks`]
+    pub fn json_blocks(&self) -> Vec<String> {
+        self.blocks.iter().map(|block| block.json.clone()).collect()
+    }
+
+    fn push_json_ld(&mut self, script: &JsonLdScript, lenient: bool) {
+        let parsed = if lenient {
+            script.parse_lenient()
+        } else {
+            script.parse().map(|value| (value, None))
+        };
+        match parsed {
+            Ok((value, recovered)) => {
+                self.diagnostics.extend(recovered);
+                self.push(
+                    StructuredDataSource::JsonLd,
+                    script.index,
+                    script.offset,
+                    value,
+                );
+            }
+            Err(diagnostic) => self.diagnostics.push(diagnostic),
+        }
+    }
+
+    fn push(
+        &mut self,
+        source: StructuredDataSource,
+        index: usize,
+        offset: Option<usize>,
+        mut value: JsonValue,
+    ) {
+        tag_source(&mut value, source);
+        self.blocks.push(StructuredDataBlock {
+            source,
+            index,
+            offset,
+            json: value.to_string(),
+        });
+    }
+}
+
+/// Extract all structured data on a page as JSON-LD blocks
+///
+/// JSON-LD scripts come first, followed by one block per top-level microdata
+/// item and RDFa resource, with every node tagged with its syntax under
+/// [`SOURCE_PROPERTY`]. Scripts that are not valid JSON-LD documents are
+/// skipped and reported in [`StructuredData::diagnostics`].
+pub fn extract_structured_data(html: &str, base_url: &str) -> Result<StructuredData> {
+    extract_structured_data_with(html, base_url, false)
+}
+
+/// Extract all structured data on a page, repairing almost-JSON scripts
+///
+/// Like [`extract_structured_data`], but scripts with trailing commas, raw
+/// newlines in strings, HTML entities, comment wrappers or concatenated
+/// objects are repaired and kept. They are still reported in
+/// [`StructuredData::diagnostics`], with the repairs that were applied.
+pub fn extract_structured_data_lenient(html: &str, base_url: &str) -> Result<StructuredData> {
+    extract_structured_data_with(html, base_url, true)
+}
+
+fn extract_structured_data_with(
+    html: &str,
+    base_url: &str,
+    lenient: bool,
+) -> Result<StructuredData> {
+    let mut data = StructuredData::default();
+
+    for script in extract_json_ld_scripts(html)? {
+        data.push_json_ld(&script, lenient);
+    }
+
+    for (index, item) in microdata::extract_microdata(html, base_url)
+        .into_iter()
+        .enumerate()
+    {
+        data.push(StructuredDataSource::Microdata, index, None, item);
+    }
+
+    for (index, item) in rdfa::extract_rdfa(html, base_url).into_iter().enumerate() {
+        data.push(StructuredDataSource::Rdfa, index, None, item);
+    }
+
+    Ok(data)
+}
+
+/// Extract all structured data on a page as JSON-LD blocks
+///
+/// Like [`extract_structured_data`], without the diagnostics for skipped
+/// blocks.
+pub fn extract_structured_data_blocks(html: &str, base_url: &str) -> Result<Vec<String>> {
+    Ok(extract_structured_data(html, base_url)?.json_blocks())
+}
+
+/// Tag every node object in a JSON-LD value with the syntax it came from
+///
+/// Value objects, lists, bare `{"@id": ...}` references and top-level
+/// `@graph` containers are left untagged.
+pub fn tag_source(value: &mut JsonValue, source: StructuredDataSource) {
+    match value {
+        JsonValue::Array(items) => {
+            for item in items {
+                tag_source(item, source);
+            }
+        }
+        JsonValue::Object(map) => {
+            if map.contains_key("@value") {
+                return;
+            }
+            let is_node = map
+                .keys()
+                .any(|key| !key.starts_with('@') || key == "@type");
+            for (key, nested) in map.iter_mut() {
+                if key != "@context" {
+                    tag_source(nested, source);
+                }
+            }
+            if is_node {
+                map.insert(
+                    SOURCE_PROPERTY.to_string(),
+                    JsonValue::String(source.as_str().to_string()),
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Vocabularies whose types and properties are emitted as schema.org terms
+const SCHEMA_ORG_VOCABULARIES: [&str; 2] = ["https://schema.org/", "http://schema.org/"];
+
+/// Schema.org IRIs as terms, anything else unchanged
+///
+/// Microdata and RDFa name types and properties by IRI; as terms of the
+/// `https://schema.org` context they merge with JSON-LD blocks.
+fn compact_iri(iri: &str) -> String {
+    SCHEMA_ORG_VOCABULARIES
+        .iter()
+        .find_map(|vocabulary| iri.strip_prefix(vocabulary))
+        .filter(|term| !term.is_empty())
+        .unwrap_or(iri)
+        .to_string()
+}
+
+/// Resolve a URL attribute against the page URL
+fn resolve_url(base: Option<&Url>, url: &str) -> String {
+    let url = url.trim();
+    match base {
+        Some(base) => base
+            .join(url)
+            .map(|resolved| resolved.to_string())
+            .unwrap_or_else(|_| url.to_string()),
+        None => url.to_string(),
+    }
+}
+
+/// Combine multiple JSON-LD blocks into a single @graph structure
+///
+/// The first `@context` found becomes the document's context; blocks without
+/// one share it (schema.org if no block has a context). A block with a
+/// different context keeps it, embedded as `[null, context]` so that its
+/// terms are expanded with its own context only.
+///
+/// Handles both object and array top-level JSON-LD structures.
+pub fn combine_json_ld_blocks(blocks: &[String]) -> Result<String> {
+    if blocks.is_empty() {
+        return Ok(r#"{"@context": "https://schema.org", "@graph": []}"#.to_string());
+    }
+
+    if blocks.len() == 1 {
+        // Parse the single block to check if it has a context
+        let parsed: JsonValue = serde_json::from_str(&blocks[0])
+            .with_context(|| format!("failed to parse JSON-LD block: {}", blocks[0]))?;
+
+        // If it already has a context, return as-is
+        if parsed.get("@context").is_some() {
+            return Ok(blocks[0].clone());
+        }
+
+        // If no context, add the default schema.org context
+        if let JsonValue::Object(mut obj) = parsed {
+            obj.insert(
+                "@context".to_string(),
+                JsonValue::String("https://schema.org".to_string()),
+            );
+            return Ok(serde_json::to_string(&obj)?);
+        }
+
+        // For non-objects, this is invalid JSON-LD
+        return Err(anyhow::anyhow!(
+            "Invalid JSON-LD: single block must be an object, got {:?}",
+            parsed
+        ));
+    }
+
+    // Parse every block first: the common context is needed before any item
+    // can be added
+    let mut parsed_blocks = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        let parsed: JsonValue = serde_json::from_str(block)
+            .with_context(|| format!("failed to parse JSON-LD block: {}", block))?;
+        parsed_blocks.push(parsed);
+    }
+
+    // Use the @context from the first entry that has one
+    let common_context = parsed_blocks
+        .iter()
+        .flat_map(|parsed| match parsed {
+            JsonValue::Array(items) => items.iter().collect(),
+            item => vec![item],
+        })
+        .find_map(|item| item.get("@context").cloned());
+
+    // Keep a context that differs from the common one, replacing (not
+    // extending) the common context for that item
+    let scope_context = |mut obj: serde_json::Map<String, JsonValue>| {
+        if let Some(ctx) = obj.remove("@context")
+            && Some(&ctx) != common_context.as_ref()
+        {
+            obj.insert(
+                "@context".to_string(),
+                JsonValue::Array(vec![JsonValue::Null, ctx]),
+            );
+        }
+        JsonValue::Object(obj)
+    };
+
+    let mut graph_items = Vec::new();
+    for parsed in parsed_blocks {
+        // Handle both objects and arrays
+        match parsed {
+            JsonValue::Object(obj) => {
+                graph_items.push(scope_context(obj));
+            }
+            JsonValue::Array(arr) => {
+                // Array at top level: add each object item to the graph
+                for item in arr {
+                    match item {
+                        JsonValue::Object(obj) => {
+                            graph_items.push(scope_context(obj));
+                        }
+                        _ => {
+                            // Non-object items in arrays are unusual but valid JSON-LD
+                            // (e.g., literal values). Include them as-is.
+                            graph_items.push(item);
+                        }
+                    }
+                }
+            }
+            _ => {
+                // Top-level primitives (string, number, bool, null) are not valid JSON-LD documents
+                return Err(anyhow::anyhow!(
+                    "Invalid JSON-LD: top level must be an object or array, got {:?}",
+                    parsed
+                ));
+            }
+        }
+    }
+
+    // Build combined document with the context from the first entry
+    // If no context was found in any block, use schema.org as default
+    let context =
+        common_context.unwrap_or_else(|| JsonValue::String("https://schema.org".to_string()));
+
+    let combined = serde_json::json!({
+        "@context": context,
+        "@graph": graph_items
+    });
+
+    Ok(serde_json::to_string(&combined)?)
+}
+
+/// Sanitize HTML by removing script, style, and other unwanted elements
+pub fn sanitize_html(html: &str) -> String {
+    static RE_TAG_BLOCKS: Lazy<Vec<Regex>> = Lazy::new(|| {
+        [
+            r"(?is)<script[^>]*?>[\s\S]*?</script>",
+            r"(?is)<style[^>]*?>[\s\S]*?</style>",
+            r"(?is)<noscript[^>]*?>[\s\S]*?</noscript>",
+            r"(?is)<template[^>]*?>[\s\S]*?</template>",
+        ]
+        .into_iter()
+        .map(|pattern| Regex::new(pattern).expect("invalid block regex"))
+        .collect()
+    });
+    static RE_COMMENT: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?is)<!--.*?-->").expect("invalid comment regex"));
+
+    let mut clean = html.to_string();
+    for re in RE_TAG_BLOCKS.iter() {
+        clean = re.replace_all(&clean, "").into_owned();
+    }
+
+    RE_COMMENT.replace_all(&clean, "").into_owned()
+}
+
+/// Convert HTML to Markdown (with sanitization)
+pub fn html_to_markdown(html: &str) -> String {
+    let sanitized = sanitize_html(html);
+    html2md::parse_html(&sanitized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_json_ld_empty_html() {
+        let html = "<html><body>No JSON-LD here</body></html>";
+        let blocks = extract_json_ld_blocks(html).unwrap();
+        assert!(blocks.is_empty());
+    }
+
+    #[test]
+    fn test_extract_json_ld_with_charset() {
+        let html = r#"
+            <script type="application/ld+json; charset=utf-8">
+            {"@type": "Product", "name": "Test"}
+            </script>
+        "#;
+
+        let blocks = extract_json_ld_blocks(html).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].contains("Test"));
+    }
+
+    #[test]
+    fn test_extract_json_ld_case_insensitive() {
+        let html = r#"
+            <script type="APPLICATION/LD+JSON">
+            {"@type": "Product", "name": "Test"}
+            </script>
+        "#;
+
+        let blocks = extract_json_ld_blocks(html).unwrap();
+        assert_eq!(blocks.len(), 1);
+    }
+
+    #[test]
+    fn test_extract_json_ld_empty_script() {
+        let html = r#"
+            <script type="application/ld+json"></script>
+            <script type="application/ld+json">   </script>
+        "#;
+
+        let blocks = extract_json_ld_blocks(html).unwrap();
+        assert!(blocks.is_empty()); // Empty scripts should be filtered out
+    }
+
+    #[test]
+    fn test_combine_single_block() {
+        let blocks = vec![
+            r#"{"@context": "https://schema.org", "@type": "Product", "name": "Single"}"#
+                .to_string(),
+        ];
+
+        let combined = combine_json_ld_blocks(&blocks).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&combined).unwrap();
+
+        // For single block, the function returns the original object (not @graph structure)
+        assert_eq!(parsed["@context"], "https://schema.org");
+        assert_eq!(parsed["@type"], "Product");
+        assert_eq!(parsed["name"], "Single");
+    }
+
+    #[test]
+    fn test_combine_multiple_blocks() {
+        let blocks = vec![
+            r#"{"@context": "https://schema.org", "@type": "Product", "name": "Product1"}"#
+                .to_string(),
+            r#"{"@type": "Organization", "name": "Org1"}"#.to_string(),
+        ];
+
+        let combined = combine_json_ld_blocks(&blocks).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&combined).unwrap();
+
+        // For multiple blocks, creates @graph structure
+        assert_eq!(parsed["@context"], "https://schema.org");
+        assert!(parsed["@graph"].is_array());
+        assert_eq!(parsed["@graph"].as_array().unwrap().len(), 2);
+
+        // Check that contexts are removed from graph items
+        let graph_items = parsed["@graph"].as_array().unwrap();
+        assert_eq!(graph_items[0]["@type"], "Product");
+        assert_eq!(graph_items[0]["name"], "Product1");
+        assert!(graph_items[0].get("@context").is_none());
+    }
+
+    #[test]
+    fn test_combine_blocks_no_context() {
+        let blocks = vec![r#"{"@type": "Product", "name": "No Context"}"#.to_string()];
+
+        let combined = combine_json_ld_blocks(&blocks).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&combined).unwrap();
+
+        // For single block without context, should use default context
+        assert_eq!(parsed["@context"], "https://schema.org");
+        assert_eq!(parsed["@type"], "Product");
+        assert_eq!(parsed["name"], "No Context");
+    }
+
+    #[test]
+    fn test_combine_blocks_keeps_different_contexts() {
+        let blocks = vec![
+            r#"{"@context": "https://schema.org", "@type": "Product"}"#.to_string(),
+            r#"{"@context": {"@vocab": "https://vocab.example/"}, "@type": "Thing"}"#.to_string(),
+            r#"{"@context": "https://schema.org", "@type": "Brand"}"#.to_string(),
+        ];
+
+        let combined = combine_json_ld_blocks(&blocks).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&combined).unwrap();
+
+        assert_eq!(parsed["@context"], "https://schema.org");
+        let graph = parsed["@graph"].as_array().unwrap();
+        assert!(graph[0].get("@context").is_none());
+        assert_eq!(
+            graph[1]["@context"],
+            serde_json::json!([null, {"@vocab": "https://vocab.example/"}])
+        );
+        assert!(graph[2].get("@context").is_none());
+    }
+
+    #[test]
+    fn test_combine_empty_blocks() {
+        let blocks: Vec<String> = vec![];
+
+        let combined = combine_json_ld_blocks(&blocks).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&combined).unwrap();
+
+        assert_eq!(parsed["@context"], "https://schema.org");
+        assert_eq!(parsed["@graph"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_combine_invalid_json() {
+        let blocks = vec![
+            r#"{"@type": "Product""#.to_string(), // Invalid JSON
+            r#"{"@type": "Organization", "name": "Valid"}"#.to_string(),
+        ];
+
+        // Should return an error for invalid JSON (the function uses with_context for errors)
+        let result = combine_json_ld_blocks(&blocks);
+        assert!(result.is_err());
+
+        // Test with only valid JSON (single block)
+        let valid_blocks = vec![r#"{"@type": "Organization", "name": "Valid"}"#.to_string()];
+
+        let combined = combine_json_ld_blocks(&valid_blocks).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&combined).unwrap();
+
+        // Single block should be returned as an object with added context, not @graph
+        assert_eq!(parsed["@context"], "https://schema.org");
+        assert_eq!(parsed["@type"], "Organization");
+        assert_eq!(parsed["name"], "Valid");
+    }
+
+    #[test]
+    fn test_extract_structured_data_blocks_tags_sources() {
+        let html = r##"
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "Product", "name": "Bike",
+             "offers": {"@type": "Offer", "price": {"@value": "10"}},
+             "brand": {"@id": "#brand"}}
+            </script>
+            <div itemscope itemtype="https://schema.org/Organization">
+                <span itemprop="name">Pon</span>
+            </div>
+            <div vocab="https://schema.org/" typeof="WebSite">
+                <span property="name">Example</span>
+            </div>
+        "##;
+
+        let blocks = extract_structured_data_blocks(html, "https://example.com/").unwrap();
+        assert_eq!(blocks.len(), 3);
+
+        let json_ld: JsonValue = serde_json::from_str(&blocks[0]).unwrap();
+        assert_eq!(json_ld[SOURCE_PROPERTY], "json-ld");
+        assert_eq!(json_ld["offers"][SOURCE_PROPERTY], "json-ld");
+        assert!(json_ld["offers"]["price"].get(SOURCE_PROPERTY).is_none());
+        assert!(json_ld["brand"].get(SOURCE_PROPERTY).is_none());
+
+        let microdata: JsonValue = serde_json::from_str(&blocks[1]).unwrap();
+        assert_eq!(microdata["@type"], "Organization");
+        assert_eq!(microdata[SOURCE_PROPERTY], "microdata");
+
+        let rdfa: JsonValue = serde_json::from_str(&blocks[2]).unwrap();
+        assert_eq!(rdfa["@type"], "WebSite");
+        assert_eq!(rdfa[SOURCE_PROPERTY], "rdfa");
+
+        // Tagged blocks still combine into one graph
+        let combined: JsonValue =
+            serde_json::from_str(&combine_json_ld_blocks(&blocks).unwrap()).unwrap();
+        assert_eq!(combined["@graph"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_extract_structured_data_reports_invalid_blocks() {
+        let html = r#"<html><head>
+<script type="application/ld+json">{"@type": "Organization", "name": "Pon"}</script>
+<script type="application/ld+json">
+{
+  "@type": "Product",
+  "name": "Bike",,
+}
+</script>
+<script type="application/ld+json">"just a string"</script>
+</head></html>"#;
+
+        let data = extract_structured_data(html, "https://example.com/").unwrap();
+        // Valid blocks are still extracted
+        assert_eq!(data.blocks.len(), 1);
+        assert_eq!(data.blocks[0].index, 0);
+        assert_eq!(
+            data.blocks[0].offset,
+            html.find(r#"{"@type": "Organization""#)
+        );
+
+        assert_eq!(data.diagnostics.len(), 2);
+        let parse = &data.diagnostics[0];
+        assert_eq!(parse.index, 1);
+        assert_eq!(parse.stage, DiagnosticStage::Parse);
+        assert_eq!(parse.offset, html.find("{\n  \"@type\": \"Product\""));
+        assert_eq!((parse.line, parse.column), (Some(3), Some(18)));
+        assert_eq!(parse.message, "key must be a string");
+        assert_eq!(
+            parse.location(),
+            format!(
+                "json-ld block #2 at byte {}, line 3, column 18",
+                parse.offset.unwrap()
+            )
+        );
+
+        let invalid = &data.diagnostics[1];
+        assert_eq!(invalid.index, 2);
+        assert_eq!(invalid.stage, DiagnosticStage::Invalid);
+
+        // Only valid blocks are combined
+        assert!(combine_json_ld_blocks(&data.json_blocks()).is_ok());
+    }
+
+    #[test]
+    fn test_extract_structured_data_lenient_recovers_blocks() {
+        let html = r#"<html><head>
+<script type="application/ld+json"><!--
+{"@type": "Product", "name": "Bike", "sku": "B-1",}
+--></script>
+<script type="application/ld+json">"just a string"</script>
+</head></html>"#;
+
+        let strict = extract_structured_data(html, "https://example.com/").unwrap();
+        assert!(strict.blocks.is_empty());
+        assert_eq!(strict.diagnostics.len(), 2);
+        assert!(!strict.diagnostics[0].is_recovered());
+
+        let lenient = extract_structured_data_lenient(html, "https://example.com/").unwrap();
+        assert_eq!(lenient.blocks.len(), 1);
+        assert!(lenient.blocks[0].json.contains(r#""sku":"B-1""#));
+
+        // The strict error is still reported, with the repairs
+        assert_eq!(lenient.diagnostics.len(), 2);
+        let recovered = &lenient.diagnostics[0];
+        assert_eq!(recovered.stage, DiagnosticStage::Parse);
+        assert_eq!(recovered.message, strict.diagnostics[0].message);
+        let repairs: Vec<RepairKind> = recovered.repairs.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            repairs,
+            vec![RepairKind::Wrapper, RepairKind::TrailingComma]
+        );
+
+        // Repairs cannot make a string a JSON-LD document
+        assert!(!lenient.diagnostics[1].is_recovered());
+    }
+
+    #[test]
+    fn test_sanitize_html_removes_scripts() {
+        let html = r#"
+            <p>Keep this</p>
+            <script>alert('remove this')</script>
+            <script src="external.js"></script>
+        "#;
+
+        let sanitized = sanitize_html(html);
+        assert!(sanitized.contains("Keep this"));
+        assert!(!sanitized.contains("script"));
+        assert!(!sanitized.contains("alert"));
+    }
+
+    #[test]
+    fn test_sanitize_html_removes_styles() {
+        let html = r#"
+            <p>Keep this</p>
+            <style>body { background: red; }</style>
+            <link rel="stylesheet" href="style.css">
+        "#;
+
+        let sanitized = sanitize_html(html);
+        assert!(sanitized.contains("Keep this"));
+        assert!(!sanitized.contains("<style"));
+        assert!(!sanitized.contains("background: red"));
+    }
+
+    #[test]
+    fn test_sanitize_html_removes_comments() {
+        let html = r#"
+            <p>Keep this</p>
+            <!-- This is a comment -->
+            <div><!-- Another comment --></div>
+        "#;
+
+        let sanitized = sanitize_html(html);
+        assert!(sanitized.contains("Keep this"));
+        assert!(!sanitized.contains("<!--"));
+        assert!(!sanitized.contains("comment"));
+    }
+
+    #[test]
+    fn test_html_to_markdown_headings() {
+        let html = r#"
+            <h1>Heading 1</h1>
+            <h2>Heading 2</h2>
+            <h3>Heading 3</h3>
+        "#;
+
+        let markdown = html_to_markdown(html);
+        // html2md uses alternative heading formats for h1 and h2
+        assert!(markdown.contains("Heading 1\n=========="));
+        assert!(markdown.contains("Heading 2\n----------"));
+        assert!(markdown.contains("### Heading 3 ###"));
+    }
+
+    #[test]
+    fn test_html_to_markdown_links() {
+        let html = r#"<a href="https://example.com">Link text</a>"#;
+        let markdown = html_to_markdown(html);
+        assert!(markdown.contains("[Link text](https://example.com)"));
+    }
+
+    #[test]
+    fn test_html_to_markdown_emphasis() {
+        let html = r#"
+            <p>Text with <strong>bold</strong> and <em>italic</em>.</p>
+        "#;
+
+        let markdown = html_to_markdown(html);
+        assert!(markdown.contains("**bold**"));
+        assert!(markdown.contains("*italic*"));
+    }
+}
@@ -0,0 +1,396 @@
+//! RDFa Lite extraction
+//!
+//! Implements the RDFa Lite 1.1 attributes (`vocab`, `typeof`, `property`,
+//! `resource` and `prefix`) and converts every top-level resource to a JSON-LD
+//! object, so RDFa can be combined with JSON-LD blocks and go through the same
+//! expansion and graph building.
+//!
+//! Properties are only collected inside a subject set by `typeof` or
+//! `resource`. Document-level `property` attributes, such as Open Graph
+//! `<meta property="og:title">` tags, are page metadata rather than entities
+//! and are skipped.
+//!
+//! Specification: https://www.w3.org/TR/rdfa-lite/
+
+use std::collections::{HashMap, HashSet};
+
+use scraper::{ElementRef, Html};
+use serde_json::{Map, Value as JsonValue};
+use url::Url;
+
+use super::{compact_iri, resolve_url};
+
+/// Prefixes predefined by the RDFa 1.1 initial context (the commonly used
+/// subset)
+const INITIAL_PREFIXES: &[(&str, &str)] = &[
+    ("schema", "http://schema.org/"),
+    ("og", "http://ogp.me/ns#"),
+    ("dc", "http://purl.org/dc/terms/"),
+    ("dcterms", "http://purl.org/dc/terms/"),
+    ("foaf", "http://xmlns.com/foaf/0.1/"),
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("skos", "http://www.w3.org/2004/02/skos/core#"),
+    ("sioc", "http://rdfs.org/sioc/ns#"),
+];
+
+/// Extract the top-level RDFa resources of a page as JSON-LD objects
+///
+/// Schema.org types and properties are emitted as terms of the
+/// `https://schema.org` context, so they merge with JSON-LD blocks; other
+/// vocabularies use absolute IRIs. Relative URLs are resolved against
+/// `base_url`.
+pub fn extract_rdfa(html: &str, base_url: &str) -> Vec<JsonValue> {
+    let document = Html::parse_document(html);
+    let mut extractor = Extractor {
+        base: Url::parse(base_url).ok(),
+        nodes: Vec::new(),
+        top_level: Vec::new(),
+    };
+
+    let context = Context {
+        vocab: None,
+        prefixes: INITIAL_PREFIXES
+            .iter()
+            .map(|(prefix, iri)| (prefix.to_string(), iri.to_string()))
+            .collect(),
+        subject: None,
+    };
+    extractor.walk(document.root_element(), &context);
+
+    extractor.into_json()
+}
+
+/// Evaluation context inherited by descendants
+#[derive(Clone)]
+struct Context {
+    /// Vocabulary that plain terms are expanded against
+    vocab: Option<String>,
+
+    /// CURIE prefixes in scope
+    prefixes: HashMap<String, String>,
+
+    /// Node that `property` attributes apply to
+    subject: Option<usize>,
+}
+
+impl Context {
+    /// Expand a term, CURIE or absolute IRI; `None` if it cannot be expanded
+    fn expand(&self, value: &str) -> Option<String> {
+        if let Some((prefix, reference)) = value.split_once(':') {
+            if let Some(namespace) = self.prefixes.get(prefix) {
+                return Some(format!("{namespace}{reference}"));
+            }
+            return Url::parse(value).is_ok().then(|| value.to_string());
+        }
+        self.vocab
+            .as_ref()
+            .map(|vocabulary| format!("{vocabulary}{value}"))
+    }
+
+    fn expand_all(&self, values: Option<&str>) -> Vec<String> {
+        values
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+            .filter_map(|value| self.expand(value))
+            .collect()
+    }
+}
+
+struct Node {
+    id: Option<String>,
+    types: Vec<String>,
+    properties: Vec<(String, Vec<Value>)>,
+}
+
+#[derive(Clone)]
+enum Value {
+    Literal(String),
+    /// IRI from `resource`
+    Reference(String),
+    Node(usize),
+}
+
+struct Extractor {
+    base: Option<Url>,
+    nodes: Vec<Node>,
+    top_level: Vec<usize>,
+}
+
+impl Extractor {
+    fn walk(&mut self, element: ElementRef<'_>, parent: &Context) {
+        let attrs = element.value();
+
+        let local = (attrs.attr("vocab").is_some() || attrs.attr("prefix").is_some()).then(|| {
+            let mut context = parent.clone();
+            if let Some(vocab) = attrs.attr("vocab") {
+                let vocab = vocab.trim();
+                context.vocab = (!vocab.is_empty()).then(|| vocab.to_string());
+            }
+            if let Some(prefix) = attrs.attr("prefix") {
+                context.prefixes.extend(parse_prefixes(prefix));
+            }
+            context
+        });
+        let context = local.as_ref().unwrap_or(parent);
+
+        let properties = context.expand_all(attrs.attr("property"));
+        let resource = attrs
+            .attr("resource")
+            .map(|resource| self.resolve_resource(resource, context));
+
+        let mut subject = context.subject;
+        if attrs.attr("typeof").is_some() || (resource.is_some() && properties.is_empty()) {
+            // A new node: the value of `property` on the parent subject, or
+            // a top-level resource
+            let index = self.nodes.len();
+            self.nodes.push(Node {
+                id: resource,
+                types: context.expand_all(attrs.attr("typeof")),
+                properties: Vec::new(),
+            });
+            match context.subject {
+                Some(parent_subject) if !properties.is_empty() => {
+                    for property in properties {
+                        self.add(parent_subject, property, Value::Node(index));
+                    }
+                }
+                _ => self.top_level.push(index),
+            }
+            subject = Some(index);
+        } else if let Some(parent_subject) = context.subject
+            && !properties.is_empty()
+        {
+            let value = match resource {
+                Some(iri) => Value::Reference(iri),
+                None => Value::Literal(self.literal(element)),
+            };
+            for property in properties {
+                self.add(parent_subject, property, value.clone());
+            }
+        }
+
+        let local = (subject != context.subject).then(|| Context {
+            subject,
+            ..context.clone()
+        });
+        let context = local.as_ref().unwrap_or(context);
+        for child in element.children().filter_map(ElementRef::wrap) {
+            self.walk(child, context);
+        }
+    }
+
+    fn add(&mut self, subject: usize, property: String, value: Value) {
+        let properties = &mut self.nodes[subject].properties;
+        match properties.iter_mut().find(|(name, _)| *name == property) {
+            Some((_, values)) => values.push(value),
+            None => properties.push((property, vec![value])),
+        }
+    }
+
+    /// Literal value of a `property` element
+    fn literal(&self, element: ElementRef<'_>) -> String {
+        let attrs = element.value();
+        if let Some(content) = attrs.attr("content") {
+            return content.to_string();
+        }
+        if attrs.name() == "time"
+            && let Some(datetime) = attrs.attr("datetime")
+        {
+            return datetime.to_string();
+        }
+        if let Some(url) = attrs.attr("href").or_else(|| attrs.attr("src")) {
+            return resolve_url(self.base.as_ref(), url);
+        }
+        element.text().collect::<String>().trim().to_string()
+    }
+
+    /// Resolve a `resource` value: a safe CURIE (`[prefix:name]`), a blank
+    /// node (`_:b0`) or a URL
+    fn resolve_resource(&self, resource: &str, context: &Context) -> String {
+        let resource = resource.trim();
+        if let Some(curie) = resource.strip_prefix('[').and_then(|r| r.strip_suffix(']'))
+            && let Some(iri) = context.expand(curie)
+        {
+            return iri;
+        }
+        if resource.starts_with("_:") {
+            return resource.to_string();
+        }
+        resolve_url(self.base.as_ref(), resource)
+    }
+
+    fn into_json(self) -> Vec<JsonValue> {
+        // References to resources described on the page become links,
+        // other IRIs stay plain values (like `href`)
+        let ids: HashSet<&str> = self.nodes.iter().filter_map(|n| n.id.as_deref()).collect();
+
+        self.top_level
+            .iter()
+            .map(|&index| {
+                let mut item = self.node_json(index, &ids);
+                if let JsonValue::Object(map) = &mut item {
+                    map.insert(
+                        "@context".to_string(),
+                        JsonValue::String("https://schema.org".to_string()),
+                    );
+                }
+                item
+            })
+            .collect()
+    }
+
+    fn node_json(&self, index: usize, ids: &HashSet<&str>) -> JsonValue {
+        let node = &self.nodes[index];
+        let mut object = Map::new();
+        if let Some(id) = &node.id {
+            object.insert("@id".to_string(), JsonValue::String(id.clone()));
+        }
+        match node.types.as_slice() {
+            [] => {}
+            [single] => {
+                object.insert("@type".to_string(), JsonValue::String(compact_iri(single)));
+            }
+            many => {
+                object.insert(
+                    "@type".to_string(),
+                    many.iter()
+                        .map(|t| JsonValue::String(compact_iri(t)))
+                        .collect(),
+                );
+            }
+        }
+
+        for (property, values) in &node.properties {
+            let mut values: Vec<JsonValue> = values
+                .iter()
+                .map(|value| match value {
+                    Value::Literal(text) => JsonValue::String(text.clone()),
+                    Value::Reference(iri) if ids.contains(iri.as_str()) => {
+                        serde_json::json!({ "@id": iri })
+                    }
+                    Value::Reference(iri) => JsonValue::String(iri.clone()),
+                    Value::Node(nested) => self.node_json(*nested, ids),
+                })
+                .collect();
+            let value = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                JsonValue::Array(values)
+            };
+            object.insert(compact_iri(property), value);
+        }
+
+        JsonValue::Object(object)
+    }
+}
+
+/// Parse a `prefix` attribute: `"og: http://ogp.me/ns# dc: http://..."`
+fn parse_prefixes(value: &str) -> Vec<(String, String)> {
+    let tokens: Vec<&str> = value.split_ascii_whitespace().collect();
+    tokens
+        .windows(2)
+        .filter_map(|pair| {
+            let prefix = pair[0].strip_suffix(':')?;
+            (!prefix.is_empty() && !pair[1].ends_with(':'))
+                .then(|| (prefix.to_ascii_lowercase(), pair[1].to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vocab_typeof_and_nested_properties() {
+        let html = r#"
+            <html><head>
+                <meta property="og:title" content="Ignored page metadata">
+            </head><body vocab="https://schema.org/">
+            <div typeof="Product" resource="/p/bike">
+                <h1 property="name"> Paralane 8.8 </h1>
+                <img property="image" src="/img/bike.jpg">
+                <meta property="sku" content="PL-88">
+                <div property="offers" typeof="Offer">
+                    <span property="price" content="3999">€3,999</span>
+                    <link property="availability" href="https://schema.org/InStock">
+                </div>
+                <span property="color">Blue</span>
+                <span property="color">Grey</span>
+            </div>
+            <div typeof="Organization"><span property="name">Pon</span></div>
+            </body></html>
+        "#;
+
+        let items = extract_rdfa(html, "https://shop.example/p/bike");
+        assert_eq!(items.len(), 2);
+
+        let product = &items[0];
+        assert_eq!(product["@context"], "https://schema.org");
+        assert_eq!(product["@type"], "Product");
+        assert_eq!(product["@id"], "https://shop.example/p/bike");
+        assert_eq!(product["name"], "Paralane 8.8");
+        assert_eq!(product["image"], "https://shop.example/img/bike.jpg");
+        assert_eq!(product["sku"], "PL-88");
+        assert_eq!(product["color"], serde_json::json!(["Blue", "Grey"]));
+        assert_eq!(product["offers"]["@type"], "Offer");
+        assert_eq!(product["offers"]["price"], "3999");
+        assert_eq!(
+            product["offers"]["availability"],
+            "https://schema.org/InStock"
+        );
+
+        assert_eq!(items[1]["@type"], "Organization");
+        assert_eq!(items[1]["name"], "Pon");
+    }
+
+    #[test]
+    fn test_prefixes_and_resource_links() {
+        let html = r##"
+            <div prefix="ex: https://vocab.example/ns#" typeof="schema:Article ex:Post">
+                <span property="schema:headline ex:title">Launch</span>
+                <span property="schema:author" resource="#ada"></span>
+                <span property="schema:license" resource="https://creativecommons.org/licenses/by/4.0/"></span>
+            </div>
+            <div vocab="http://schema.org/" resource="#ada" typeof="Person">
+                <span property="name">Ada</span>
+            </div>
+        "##;
+
+        let items = extract_rdfa(html, "https://example.com/blog/launch");
+        assert_eq!(items.len(), 2);
+
+        let article = &items[0];
+        assert_eq!(
+            article["@type"],
+            serde_json::json!(["Article", "https://vocab.example/ns#Post"])
+        );
+        assert_eq!(article["headline"], "Launch");
+        assert_eq!(article["https://vocab.example/ns#title"], "Launch");
+        // Described on the page, so a link to that node
+        assert_eq!(
+            article["author"],
+            serde_json::json!({ "@id": "https://example.com/blog/launch#ada" })
+        );
+        assert_eq!(
+            article["license"],
+            "https://creativecommons.org/licenses/by/4.0/"
+        );
+
+        assert_eq!(items[1]["@id"], "https://example.com/blog/launch#ada");
+        assert_eq!(items[1]["name"], "Ada");
+    }
+
+    #[test]
+    fn test_parse_prefixes() {
+        assert_eq!(
+            parse_prefixes("og: http://ogp.me/ns#  EX: https://vocab.example/"),
+            vec![
+                ("og".to_string(), "http://ogp.me/ns#".to_string()),
+                ("ex".to_string(), "https://vocab.example/".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Lenient JSON-LD recovery
+//!
+//! A lot of production JSON-LD is almost-JSON. Strict parsers such as
+//! Google's reject these blocks, but their intent is usually clear. This
+//! opt-in pass fixes the common defects and records every repair so the
+//! block can be reported as broken while still contributing to the graph:
+//!
+//! - `<!-- ... -->` and `<![CDATA[ ... ]]>` wrappers
+//! - trailing commas before `}` or `]`
+//! - raw newlines, tabs and other control characters inside strings
+//! - HTML entities (`&quot;`, `&amp;`, `&#39;`, ...)
+//! - several top-level objects without an enclosing array
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// A kind of defect fixed by [`repair_json_ld`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RepairKind {
+    /// Removed an HTML comment or CDATA wrapper around the block
+    Wrapper,
+    /// Removed a comma before `}` or `]`
+    TrailingComma,
+    /// Escaped a raw control character (newline, tab, ...) inside a string
+    ControlCharacter,
+    /// Decoded an HTML entity
+    HtmlEntity,
+    /// Wrapped concatenated top-level values in an array
+    ConcatenatedValues,
+}
+
+impl RepairKind {
+    pub fn description(&self) -> &'static str {
+        match self {
+            RepairKind::Wrapper => "removed HTML comment / CDATA wrapper",
+            RepairKind::TrailingComma => "removed trailing comma",
+            RepairKind::ControlCharacter => "escaped control character in string",
+            RepairKind::HtmlEntity => "decoded HTML entity",
+            RepairKind::ConcatenatedValues => "wrapped concatenated objects in an array",
+        }
+    }
+}
+
+/// A repair applied to a block, with the number of places it was needed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonRepair {
+    pub kind: RepairKind,
+    pub count: usize,
+}
+
+impl std::fmt::Display for JsonRepair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.count > 1 {
+            write!(f, "{} (×{})", self.kind.description(), self.count)
+        } else {
+            f.write_str(self.kind.description())
+        }
+    }
+}
+
+/// Try to turn almost-JSON into a JSON-LD document
+///
+/// Returns the parsed value and the repairs that were needed, or `None` if
+/// the block is still not valid JSON afterwards.
+pub fn repair_json_ld(raw: &str) -> Option<(JsonValue, Vec<JsonRepair>)> {
+    let mut repairs = Repairs::default();
+    let unwrapped = strip_wrappers(raw, &mut repairs);
+
+    // Entity-escaped JSON has no literal quotes at all
+    let text = if !unwrapped.contains('"') && unwrapped.contains("&quot;") {
+        repairs.add(RepairKind::HtmlEntity, unwrapped.matches('&').count());
+        decode_entities(unwrapped)
+    } else {
+        unwrapped.to_string()
+    };
+
+    let values = scan(&text, &mut repairs);
+    let json = match values.as_slice() {
+        [single] => single.clone(),
+        many => {
+            repairs.add(RepairKind::ConcatenatedValues, 1);
+            format!("[{}]", many.join(","))
+        }
+    };
+
+    match serde_json::from_str::<JsonValue>(&json).ok()? {
+        value @ (JsonValue::Object(_) | JsonValue::Array(_)) => Some((value, repairs.into_vec())),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Repairs(Vec<JsonRepair>);
+
+impl Repairs {
+    fn add(&mut self, kind: RepairKind, count: usize) {
+        match self.0.iter_mut().find(|repair| repair.kind == kind) {
+            Some(repair) => repair.count += count,
+            None => self.0.push(JsonRepair { kind, count }),
+        }
+    }
+
+    fn into_vec(mut self) -> Vec<JsonRepair> {
+        self.0.sort_by_key(|repair| repair.kind);
+        self.0
+    }
+}
+
+fn strip_wrappers<'a>(raw: &'a str, repairs: &mut Repairs) -> &'a str {
+    const WRAPPERS: &[(&str, &str)] = &[
+        ("<!--", "-->"),
+        ("//<![CDATA[", "//]]>"),
+        ("/*<![CDATA[*/", "/*]]>*/"),
+        ("<![CDATA[", "]]>"),
+    ];
+
+    let mut text = raw.trim();
+    for (open, close) in WRAPPERS {
+        if let Some(inner) = text.strip_prefix(open) {
+            text = inner.strip_suffix(close).unwrap_or(inner).trim();
+            repairs.add(RepairKind::Wrapper, 1);
+        }
+    }
+    text
+}
+
+/// Fix string contents and trailing commas, and split concatenated
+/// top-level values
+fn scan(text: &str, repairs: &mut Repairs) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut chars = text.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    current.push(c);
+                    if let Some((_, escaped)) = chars.next() {
+                        current.push(escaped);
+                    }
+                }
+                '"' => {
+                    in_string = false;
+                    current.push(c);
+                }
+                '&' => match decode_entity(&text[index..]) {
+                    Some((decoded, length)) => {
+                        repairs.add(RepairKind::HtmlEntity, 1);
+                        push_escaped(&mut current, decoded);
+                        for _ in 1..length {
+                            chars.next();
+                        }
+                    }
+                    None => current.push(c),
+                },
+                c if c.is_control() => {
+                    repairs.add(RepairKind::ControlCharacter, 1);
+                    push_escaped(&mut current, c);
+                }
+                c => current.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                current.push(c);
+            }
+            '{' | '[' => {
+                depth += 1;
+                current.push(c);
+            }
+            '}' | ']' => {
+                let trimmed = current.trim_end().len();
+                if current[..trimmed].ends_with(',') {
+                    current.truncate(trimmed - 1);
+                    repairs.add(RepairKind::TrailingComma, 1);
+                }
+                depth = depth.saturating_sub(1);
+                current.push(c);
+                if depth == 0 {
+                    values.push(std::mem::take(&mut current).trim().to_string());
+                }
+            }
+            // Separators between top-level values
+            ',' | ';' if depth == 0 => {}
+            c if depth == 0 && c.is_whitespace() => {}
+            c => current.push(c),
+        }
+    }
+
+    if !current.trim().is_empty() || values.is_empty() {
+        values.push(current.trim().to_string());
+    }
+    values
+}
+
+fn push_escaped(out: &mut String, c: char) {
+    match c {
+        '"' => out.push_str("\\\""),
+        '\\' => out.push_str("\\\\"),
+        '\n' => out.push_str("\\n"),
+        '\r' => out.push_str("\\r"),
+        '\t' => out.push_str("\\t"),
+        c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+        c => out.push(c),
+    }
+}
+
+/// Decode the entity at the start of `text`: (character, byte length)
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    let (end, _) = text.char_indices().take(12).find(|(_, c)| *c == ';')?;
+    let name = &text[1..end];
+    let decoded = match name {
+        "quot" => '"',
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => {
+            let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => name.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((decoded, end + 1))
+}
+
+/// Decode all entities in `text`
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(position) = rest.find('&') {
+        out.push_str(&rest[..position]);
+        rest = &rest[position..];
+        match decode_entity(rest) {
+            Some((decoded, length)) => {
+                out.push(decoded);
+                rest = &rest[length..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(repairs: &[JsonRepair]) -> Vec<(RepairKind, usize)> {
+        repairs
+            .iter()
+            .map(|repair| (repair.kind, repair.count))
+            .collect()
+    }
+
+    #[test]
+    fn test_repairs_common_defects() {
+        let raw = "<!--\n{\"@type\": \"Product\", \"name\": \"Bike\n8.8\", \"brand\": \"Pon &amp; Co\",\n\"color\": [\"red\", \"blue\",],}\n-->";
+
+        let (value, repairs) = repair_json_ld(raw).unwrap();
+        assert_eq!(value["name"], "Bike\n8.8");
+        assert_eq!(value["brand"], "Pon & Co");
+        assert_eq!(value["color"], serde_json::json!(["red", "blue"]));
+        assert_eq!(
+            kinds(&repairs),
+            vec![
+                (RepairKind::Wrapper, 1),
+                (RepairKind::TrailingComma, 2),
+                (RepairKind::ControlCharacter, 1),
+                (RepairKind::HtmlEntity, 1),
+            ]
+        );
+        assert_eq!(repairs[1].to_string(), "removed trailing comma (×2)");
+    }
+
+    #[test]
+    fn test_concatenated_and_escaped_documents() {
+        let (value, repairs) =
+            repair_json_ld(r#"{"@type": "Organization"} {"@type": "WebSite"}"#).unwrap();
+        assert_eq!(value[1]["@type"], "WebSite");
+        assert_eq!(kinds(&repairs), vec![(RepairKind::ConcatenatedValues, 1)]);
+
+        let (value, _) = repair_json_ld(
+            "{&quot;@type&quot;: &quot;Product&quot;, &quot;name&quot;: &quot;5&amp;5&quot;}",
+        )
+        .unwrap();
+        assert_eq!(value["name"], "5&5");
+
+        // Quotes decoded inside strings stay escaped
+        let (value, _) = repair_json_ld(r#"{"name": "27&quot; screen",}"#).unwrap();
+        assert_eq!(value["name"], "27\" screen");
+
+        assert!(repair_json_ld(r#"{"name": "unterminated}"#).is_none());
+        assert!(repair_json_ld("plain text").is_none());
+    }
+}
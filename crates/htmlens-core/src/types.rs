@@ -2,7 +2,10 @@
 
 use serde::Serialize;
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::identity::canonical_json;
 
 /// A node in the knowledge graph
 #[derive(Debug, Serialize, Clone)]
@@ -11,12 +14,16 @@ pub struct GraphNode {
     pub id: String,
     #[serde(rename = "@type")]
     pub types: Vec<String>,
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub properties: HashMap<String, JsonValue>,
+    /// Property values keyed by predicate IRI, serialized in key order
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, JsonValue>,
+    /// Syntaxes the node was extracted from ("json-ld", "microdata", "rdfa")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
 }
 
 /// An edge connecting two nodes in the knowledge graph
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct GraphEdge {
     pub from: String,
     pub to: String,
@@ -35,7 +42,58 @@ impl GraphNode {
         Self {
             id,
             types: Vec::new(),
-            properties: HashMap::new(),
+            properties: BTreeMap::new(),
+            sources: Vec::new(),
         }
     }
 }
+
+impl KnowledgeGraph {
+    /// Put the graph in canonical order
+    ///
+    /// Nodes are sorted by ID, their types and sources are sorted and
+    /// deduplicated, and edges are sorted and deduplicated. Graphs produced by
+    /// [`GraphBuilder`](crate::GraphBuilder) are already canonical; this is for
+    /// graphs assembled or modified by hand.
+    pub fn canonicalize(&mut self) {
+        for node in &mut self.nodes {
+            node.types.sort();
+            node.types.dedup();
+            node.sources.sort();
+            node.sources.dedup();
+        }
+        self.nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        self.edges.sort();
+        self.edges.dedup();
+    }
+
+    /// SHA-256 of the graph's canonical JSON, as lowercase hex
+    ///
+    /// Equal for graphs with the same nodes, properties and edges regardless
+    /// of the order they were added in, so graph changes can be detected by
+    /// comparing hashes.
+    pub fn canonical_hash(&self) -> String {
+        let mut nodes: Vec<JsonValue> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let mut node = node.clone();
+                node.types.sort();
+                node.types.dedup();
+                node.sources.sort();
+                node.sources.dedup();
+                serde_json::to_value(node).unwrap_or(JsonValue::Null)
+            })
+            .collect();
+        nodes.sort_by_key(canonical_json);
+        let mut edges: Vec<&GraphEdge> = self.edges.iter().collect();
+        edges.sort();
+        edges.dedup();
+
+        let document = serde_json::json!({ "nodes": nodes, "edges": edges });
+        Sha256::digest(canonical_json(&document).as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
@@ -2,9 +2,19 @@
 //!
 //! Pure Rust implementation that serves a web UI and extracts JSON-LD using htmlens-core
 
-use htmlens_core::{GraphNode, normalize_origin, parser};
+use htmlens_core::ai_readiness::{
+    AiReadinessIssue, AiReadinessReport, IssueCategory, check_site_with,
+    crawlers::{CrawlerPurpose, CrawlerRegistry},
+    fetch::{FetchResponse, Fetcher},
+    robots_txt::AccessLevel,
+    scoring::{ScoreBreakdown, ScoringWeights},
+    sitemap, well_known,
+};
+use htmlens_core::insights::GraphInsights;
+use htmlens_core::markdown;
+use htmlens_core::parser::{BlockDiagnostic, PageMetadata, StructuredDataSource};
+use htmlens_core::{GraphNode, parser};
 use serde::Serialize;
-use url::Url;
 use worker::*;
 
 #[derive(Serialize)]
@@ -19,9 +29,15 @@ struct ApiResponse {
     markdown: String, // CLI-style formatted markdown with product tables
     #[serde(rename = "pageMarkdown")]
     page_markdown: String, // CF AI converted HTML page content
+    #[serde(rename = "pageMetadata")]
+    page_metadata: PageMetadata, // Head metadata (OpenGraph, Twitter Card, hreflang, ...)
+    diagnostics: Vec<BlockDiagnostic>, // JSON-LD blocks that were skipped, with their location
     meta: MetaData,
+    /// `None` when the check failed, see `ai_readiness_error`
     #[serde(rename = "aiReadiness")]
-    ai_readiness: AiReadinessData,
+    ai_readiness: Option<AiReadinessData>,
+    #[serde(rename = "aiReadinessError", skip_serializing_if = "Option::is_none")]
+    ai_readiness_error: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -42,6 +58,11 @@ struct MetaData {
 
 #[derive(Serialize)]
 struct AiReadinessData {
+    score: u8,
+    breakdown: ScoreBreakdown,
+    strengths: Vec<String>,
+    issues: Vec<AiReadinessIssue>,
+    recommendations: Vec<String>,
     #[serde(rename = "wellKnown")]
     well_known: WellKnownChecks,
     #[serde(rename = "aiPlugin")]
@@ -51,6 +72,8 @@ struct AiReadinessData {
     #[serde(rename = "robotsTxt")]
     robots_txt: Option<RobotsTxtStatus>,
     sitemap: Option<SitemapStatus>,
+    #[serde(rename = "llmsTxt")]
+    llms_txt: Option<LlmsTxtStatus>,
     #[serde(rename = "semanticHtml")]
     semantic_html: Option<SemanticHtmlStatus>,
 }
@@ -139,16 +162,43 @@ struct RobotsTxtStatus {
     ai_crawlers: Vec<AiCrawlerInfo>,
     #[serde(rename = "blocksAllBots")]
     blocks_all_bots: bool,
+    #[serde(rename = "purposeSummary")]
+    purpose_summary: Option<String>,
     issues: Vec<String>,
 }
 
 #[derive(Serialize)]
 struct AiCrawlerInfo {
     name: String,
-    access: String, // "allowed", "partial", "blocked", "default"
+    vendor: String,
+    purpose: CrawlerPurpose, // "training", "search", "user_fetch"
+    access: String,          // "allowed", "partial", "blocked", "default"
     rules: Option<String>,
 }
 
+#[derive(Serialize)]
+struct LlmsTxtStatus {
+    found: bool,
+    title: Option<String>,
+    summary: Option<String>,
+    sections: Vec<LlmsTxtSectionStatus>,
+    #[serde(rename = "linkCount")]
+    link_count: usize,
+    #[serde(rename = "fullFound")]
+    full_found: bool,
+    #[serde(rename = "fullSizeBytes")]
+    full_size_bytes: usize,
+    issues: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct LlmsTxtSectionStatus {
+    title: String,
+    optional: bool,
+    #[serde(rename = "linkCount")]
+    link_count: usize,
+}
+
 #[derive(Serialize)]
 struct SitemapStatus {
     found: bool,
@@ -245,576 +295,221 @@ struct ImagesInfo {
 // Frontend HTML will be included as a separate file
 const FRONTEND_HTML: &str = include_str!("frontend.html");
 
-fn extract_title(html: &str) -> String {
-    // Simple regex-based title extraction
-    if let Some(start) = html.find("<title")
-        && let Some(content_start) = html[start..].find('>')
-    {
-        let content_start = start + content_start + 1;
-        if let Some(end) = html[content_start..].find("</title>") {
-            return html[content_start..content_start + end].trim().to_string();
-        }
-    }
-    "Unknown Title".to_string()
+/// Page title for the API response ("Unknown Title" without a `<title>`)
+fn extract_title(metadata: &PageMetadata) -> String {
+    metadata
+        .title
+        .clone()
+        .unwrap_or_else(|| "Unknown Title".to_string())
 }
 
-fn extract_description(html: &str) -> String {
-    // Simple meta description extraction
-    if let Some(start) = html
-        .find(r#"name="description""#)
-        .or_else(|| html.find(r#"name='description'"#))
-        && let Some(content) = html[start..].find("content=")
-    {
-        let content_start = start + content + 8;
-        let quote = html.chars().nth(content_start).unwrap_or('"');
-        if let Some(end) = html[content_start + 1..].find(quote) {
-            return html[content_start + 1..content_start + 1 + end].to_string();
-        }
-    }
-    String::new()
+/// Meta description for the API response (empty when missing)
+fn extract_description(metadata: &PageMetadata) -> String {
+    metadata.description.clone().unwrap_or_default()
 }
 
-async fn check_ai_readiness(base_url: &str) -> AiReadinessData {
-    let origin = normalize_origin(base_url);
-
-    // Check .well-known files
-    let well_known = check_well_known_files(&origin).await;
-
-    // Check AI Plugin if found
-    let ai_plugin =
-        if well_known.ai_plugin_json.found && well_known.ai_plugin_json.valid == Some(true) {
-            check_ai_plugin(&origin).await
-        } else {
-            None
-        };
-
-    // Check MCP if found
-    let mcp = if well_known.mcp_json.found && well_known.mcp_json.valid == Some(true) {
-        check_mcp(&origin).await
-    } else {
-        None
-    };
-
-    // Check OpenAPI if AI plugin references it
-    let openapi = if let Some(ref plugin) = ai_plugin {
-        if let Some(ref api_url) = plugin.api_url {
-            check_openapi(api_url).await
-        } else {
-            None
-        }
-    } else {
-        None
-    };
-
-    // Check robots.txt
-    let robots_txt = check_robots_txt(&origin).await;
-
-    // Check sitemap (use URLs from robots.txt if available)
-    let sitemap_urls = if let Some(ref robots) = robots_txt {
-        robots.sitemaps.clone()
-    } else {
-        Vec::new()
+/// AI readiness category weights from the `SCORING_WEIGHTS` variable (a JSON
+/// object like `{"crawler_access": 40}`), else the defaults
+fn scoring_weights(env: &Env) -> ScoringWeights {
+    let Ok(var) = env.var("SCORING_WEIGHTS") else {
+        return ScoringWeights::default();
     };
-    let sitemap = check_sitemap(&origin, sitemap_urls).await;
-
-    // Check semantic HTML
-    let semantic_html = check_semantic_html(base_url).await;
-
-    AiReadinessData {
-        well_known,
-        ai_plugin,
-        mcp,
-        openapi,
-        robots_txt,
-        sitemap,
-        semantic_html,
-    }
+    serde_json::from_str(&var.to_string()).unwrap_or_else(|e| {
+        console_log!("[AI Readiness] Ignoring invalid SCORING_WEIGHTS: {}", e);
+        ScoringWeights::default()
+    })
 }
 
-async fn check_well_known_files(base_url: &str) -> WellKnownChecks {
-    console_log!(
-        "[AI Readiness] Checking .well-known files for: {}",
-        base_url
-    );
-
-    let files = vec![
-        ("ai-plugin.json", "aiPluginJson"),
-        ("mcp.json", "mcpJson"),
-        ("openid-configuration", "openidConfiguration"),
-        ("security.txt", "securityTxt"),
-        ("apple-app-site-association", "appleAppSiteAssociation"),
-        ("assetlinks.json", "assetlinks"),
-    ];
-
-    let mut results = WellKnownChecks {
-        ai_plugin_json: FileStatus {
-            found: false,
-            status: 0,
-            valid: None,
-        },
-        mcp_json: FileStatus {
-            found: false,
-            status: 0,
-            valid: None,
-        },
-        openid_configuration: FileStatus {
-            found: false,
-            status: 0,
-            valid: None,
-        },
-        security_txt: FileStatus {
-            found: false,
-            status: 0,
-            valid: None,
-        },
-        apple_app_site_association: FileStatus {
-            found: false,
-            status: 0,
-            valid: None,
-        },
-        assetlinks: FileStatus {
-            found: false,
-            status: 0,
-            valid: None,
-        },
-    };
+async fn check_ai_readiness(
+    base_url: &str,
+    weights: &ScoringWeights,
+) -> anyhow::Result<AiReadinessData> {
+    let registry = CrawlerRegistry::default();
+    let report = check_site_with(&WorkerFetcher, base_url, &registry, weights).await?;
+    Ok(ai_readiness_data(report))
+}
 
-    for (filename, _) in files {
-        let url = format!("{}/.well-known/{}", base_url, filename);
-        console_log!("[.well-known] Checking: {}", url);
+/// [`Fetcher`] backed by the Workers `Fetch` API
+struct WorkerFetcher;
 
-        // Create request with redirect set to "manual" to prevent following redirects
+impl Fetcher for WorkerFetcher {
+    async fn get(&self, url: &str, follow_redirects: bool) -> anyhow::Result<FetchResponse> {
         let mut init = RequestInit::new();
-        init.with_redirect(RequestRedirect::Manual);
-
-        if let Ok(request) = Request::new_with_init(&url, &init)
-            && let Ok(mut response) = Fetch::Request(request).send().await
-        {
-            let status = response.status_code();
-
-            // First check: HTTP status must be 200 (success)
-            // A 404/403/etc should never be considered "found" even if valid JSON
-            if status != 200 {
-                console_log!("[.well-known] {} - Not found (HTTP {})", filename, status);
-                let file_status = FileStatus {
-                    found: false,
-                    status,
-                    valid: None,
-                };
-                match filename {
-                    "ai-plugin.json" => results.ai_plugin_json = file_status,
-                    "mcp.json" => results.mcp_json = file_status,
-                    "openid-configuration" => results.openid_configuration = file_status,
-                    "security.txt" => results.security_txt = file_status,
-                    "apple-app-site-association" => {
-                        results.apple_app_site_association = file_status
-                    }
-                    "assetlinks.json" => results.assetlinks = file_status,
-                    _ => {}
-                }
-                continue;
-            }
-
-            // For JSON files, validate the content
-            // (some servers return 200 with HTML error pages)
-            let (found, valid) = if filename.ends_with(".json") 
-                    || filename == "openid-configuration"  // OpenID config is JSON even without .json extension
-                    || filename == "apple-app-site-association"
-            // Apple Universal Links config is JSON
-            {
-                if let Ok(text) = response.text().await {
-                    // Check if it's actually valid JSON
-                    if serde_json::from_str::<serde_json::Value>(&text).is_ok() {
-                        console_log!("[.well-known] {} - Valid JSON found", filename);
-                        (true, Some(true))
-                    } else {
-                        // Status was 200 but content is not valid JSON (likely error page)
-                        console_log!(
-                            "[.well-known] {} - Invalid JSON (likely redirected to error page)",
-                            filename
-                        );
-                        (false, Some(false))
-                    }
-                } else {
-                    console_log!("[.well-known] {} - Failed to read response body", filename);
-                    (false, None)
-                }
-            } else {
-                // For non-JSON files (like security.txt), status 200 means found
-                (true, None)
-            };
-
-            console_log!(
-                "[AI Readiness] {} - Status: {}, Found: {}, Valid: {:?}",
-                filename,
-                status,
-                found,
-                valid
-            );
-
-            let file_status = FileStatus {
-                found,
-                status,
-                valid,
-            };
-
-            match filename {
-                "ai-plugin.json" => results.ai_plugin_json = file_status,
-                "mcp.json" => results.mcp_json = file_status,
-                "openid-configuration" => results.openid_configuration = file_status,
-                "security.txt" => results.security_txt = file_status,
-                "apple-app-site-association" => results.apple_app_site_association = file_status,
-                "assetlinks.json" => results.assetlinks = file_status,
-                _ => {}
-            }
+        if !follow_redirects {
+            init.with_redirect(RequestRedirect::Manual);
         }
+        let mut response = send(url, &init).await?;
+        let content_type = response.headers().get("Content-Type").ok().flatten();
+        Ok(FetchResponse {
+            status: response.status_code(),
+            body: response.text().await.unwrap_or_default(),
+            content_type,
+        })
     }
 
-    results
-}
-
-async fn check_ai_plugin(base_url: &str) -> Option<AiPluginStatus> {
-    let url = format!("{}/.well-known/ai-plugin.json", base_url);
-    if let Ok(parsed) = Url::parse(&url)
-        && let Ok(mut response) = Fetch::Url(parsed).send().await
-        && let Ok(text) = response.text().await
-        && let Ok(plugin) = serde_json::from_str::<serde_json::Value>(&text)
-    {
-        let mut issues = Vec::new();
-
-        let name = plugin
-            .get("name_for_human")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown")
-            .to_string();
-
-        let description = plugin
-            .get("description_for_human")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-
-        let has_auth = plugin
-            .get("auth")
-            .and_then(|a| a.get("type"))
-            .and_then(|t| t.as_str())
-            .map(|t| t != "none")
-            .unwrap_or(false);
-
-        let api_url = plugin
-            .get("api")
-            .and_then(|a| a.get("url"))
-            .and_then(|u| u.as_str())
-            .map(|s| s.to_string());
-
-        if api_url.is_none() {
-            issues.push("Missing API URL".to_string());
-        }
-
-        return Some(AiPluginStatus {
-            valid: issues.is_empty(),
-            name,
-            description,
-            has_auth,
-            api_url,
-            issues,
-        });
-    }
-    None
-}
-
-async fn check_mcp(base_url: &str) -> Option<McpStatus> {
-    let url = format!("{}/.well-known/mcp.json", base_url);
-    if let Ok(parsed) = Url::parse(&url)
-        && let Ok(mut response) = Fetch::Url(parsed).send().await
-        && let Ok(text) = response.text().await
-        && let Ok(mcp) = serde_json::from_str::<serde_json::Value>(&text)
-    {
-        let mut issues = Vec::new();
-
-        let name = mcp
-            .get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown")
-            .to_string();
-
-        let version = mcp
-            .get("version")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown")
-            .to_string();
-
-        let protocol_version = mcp
-            .get("protocolVersion")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown")
-            .to_string();
-
-        let transport = mcp.get("transport");
-        let transport_type = transport
-            .and_then(|t| t.get("type"))
-            .and_then(|t| t.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-
-        let endpoint = transport
-            .and_then(|t| t.get("endpoint"))
-            .and_then(|e| e.as_str())
-            .unwrap_or("")
-            .to_string();
-
-        if endpoint.is_empty() {
-            issues.push("Missing transport endpoint".to_string());
-        }
-
-        let capabilities = mcp.get("capabilities");
-        let has_tools_capability = capabilities.and_then(|c| c.get("tools")).is_some();
-        let has_resources_capability = capabilities.and_then(|c| c.get("resources")).is_some();
-        let has_prompts_capability = capabilities.and_then(|c| c.get("prompts")).is_some();
-        let has_events_capability = capabilities.and_then(|c| c.get("events")).is_some();
-
-        let tools = mcp
-            .get("tools")
-            .and_then(|t| t.as_array())
-            .map(|a| a.len())
-            .unwrap_or(0);
-
-        let resources = mcp
-            .get("resources")
-            .and_then(|r| r.as_array())
-            .map(|a| a.len())
-            .unwrap_or(0);
-
-        let prompts = mcp
-            .get("prompts")
-            .and_then(|p| p.as_array())
-            .map(|a| a.len())
-            .unwrap_or(0);
-
-        let health_endpoint = mcp
-            .get("health")
-            .and_then(|h| h.get("endpoint"))
-            .and_then(|e| e.as_str())
-            .map(|s| s.to_string());
-
-        return Some(McpStatus {
-            valid: issues.is_empty(),
-            name,
-            version,
-            protocol_version,
-            transport_type,
-            endpoint,
-            tool_count: tools,
-            resource_count: resources,
-            prompt_count: prompts,
-            has_tools_capability,
-            has_resources_capability,
-            has_prompts_capability,
-            has_events_capability,
-            health_endpoint,
-            issues,
-        });
+    async fn head(&self, url: &str) -> anyhow::Result<u16> {
+        let mut init = RequestInit::new();
+        init.with_method(Method::Head);
+        Ok(send(url, &init).await?.status_code())
     }
-    None
 }
 
-async fn check_openapi(api_url: &str) -> Option<OpenApiStatus> {
-    if let Ok(parsed) = Url::parse(api_url)
-        && let Ok(mut response) = Fetch::Url(parsed).send().await
-        && let Ok(text) = response.text().await
-        && let Ok(spec) = serde_json::from_str::<serde_json::Value>(&text)
-    {
-        let mut issues = Vec::new();
-
-        let version = spec
-            .get("openapi")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-
-        let paths = spec
-            .get("paths")
-            .and_then(|p| p.as_object())
-            .map(|p| p.len())
-            .unwrap_or(0);
-
-        let has_auth = spec
-            .get("components")
-            .and_then(|c| c.get("securitySchemes"))
-            .is_some();
-
-        if paths == 0 {
-            issues.push("No API paths defined".to_string());
-        }
-
-        return Some(OpenApiStatus {
-            valid: issues.is_empty(),
-            version,
-            operation_count: paths,
-            has_auth,
-            issues,
-        });
-    }
-    None
+async fn send(url: &str, init: &RequestInit) -> anyhow::Result<Response> {
+    // worker::Error holds JS values, which anyhow can't carry across threads
+    let request = Request::new_with_init(url, init).map_err(|e| anyhow::anyhow!("{}", e))?;
+    Fetch::Request(request)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))
 }
 
-async fn check_robots_txt(base_url: &str) -> Option<RobotsTxtStatus> {
-    // robots.txt must be located at the origin
-    let origin = normalize_origin(base_url);
-    let robots_url = format!("{}/robots.txt", origin);
-    let parsed = Url::parse(&robots_url).ok()?;
-
-    if let Ok(mut response) = Fetch::Url(parsed).send().await
-        && response.status_code() == 200
-        && let Ok(text) = response.text().await
-    {
-        // Parse robots.txt content
-        let lines: Vec<&str> = text.lines().collect();
-        let mut sitemaps = Vec::new();
-        let mut ai_crawlers = Vec::new();
-        let mut blocks_all_bots = false;
-        let mut issues = Vec::new();
-
-        // Extract sitemaps
-        for line in &lines {
-            let line = line.trim();
-            if line.to_lowercase().starts_with("sitemap:") {
-                // Split only on first colon to preserve URL scheme (https:)
-                if let Some(colon_pos) = line.find(':') {
-                    let sitemap_url = &line[colon_pos + 1..];
-                    sitemaps.push(sitemap_url.trim().to_string());
-                }
-            }
-        }
-
-        // Check for wildcard block
-        let mut in_wildcard = false;
-        for line in &lines {
-            let line = line.trim().to_lowercase();
-            if line.starts_with("user-agent:") {
-                in_wildcard = line.contains("*");
-            } else if in_wildcard && line == "disallow: /" {
-                blocks_all_bots = true;
-                issues.push("Warning: All bots blocked with 'Disallow: /'".to_string());
-            }
-        }
+/// Convert the core report into the camelCase response consumed by the frontend
+fn ai_readiness_data(report: AiReadinessReport) -> AiReadinessData {
+    let category_issues = |category: IssueCategory| -> Vec<String> {
+        report
+            .issues
+            .iter()
+            .filter(|issue| issue.category == category)
+            .map(|issue| issue.message.clone())
+            .collect()
+    };
 
-        // Check AI crawlers
-        let ai_crawler_names = vec![
-            "GPTBot",
-            "ChatGPT-User",
-            "ClaudeBot",
-            "Claude-Web",
-            "Google-Extended",
-            "Bingbot",
-            "Applebot",
-            "PerplexityBot",
-        ];
-
-        for crawler in ai_crawler_names {
-            let access = check_crawler_access(&text, crawler);
-            let rules = get_crawler_rules(&text, crawler);
-
-            ai_crawlers.push(AiCrawlerInfo {
-                name: crawler.to_string(),
-                access: access.clone(),
-                rules,
-            });
-
-            if access == "blocked" {
-                issues.push(format!("{} is blocked from accessing the site", crawler));
-            }
-        }
+    let well_known = WellKnownChecks {
+        ai_plugin_json: file_status(&report.well_known.ai_plugin, true),
+        mcp_json: file_status(&report.well_known.mcp, true),
+        openid_configuration: file_status(&report.well_known.openid_config, true),
+        security_txt: file_status(&report.well_known.security_txt, false),
+        apple_app_site_association: file_status(
+            &report.well_known.apple_app_site_association,
+            true,
+        ),
+        assetlinks: file_status(&report.well_known.assetlinks, true),
+    };
 
-        if sitemaps.is_empty() {
-            issues.push("No sitemap URLs found in robots.txt".to_string());
+    let ai_plugin = report.ai_plugin.as_ref().map(|validation| {
+        let manifest = validation.manifest.as_ref();
+        AiPluginStatus {
+            valid: validation.valid,
+            name: manifest
+                .map(|m| m.name_for_human.clone())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            description: manifest
+                .map(|m| m.description_for_human.clone())
+                .unwrap_or_default(),
+            has_auth: manifest
+                .map(|m| m.auth.auth_type != "none")
+                .unwrap_or(false),
+            api_url: manifest.map(|m| m.api.url.clone()),
+            issues: validation
+                .issues
+                .iter()
+                .map(|issue| format!("{}: {}", issue.field, issue.message))
+                .collect(),
         }
-
-        return Some(RobotsTxtStatus {
+    });
+
+    let mcp = report.mcp.as_ref().map(|validation| McpStatus {
+        valid: validation.valid,
+        name: validation
+            .name
+            .clone()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        version: validation
+            .version
+            .clone()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        protocol_version: validation
+            .protocol_version
+            .clone()
+            .unwrap_or_else(|| "Unknown".to_string()),
+        transport_type: validation
+            .transport_type
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+        endpoint: validation.endpoint.clone().unwrap_or_default(),
+        tool_count: validation.tool_count,
+        resource_count: validation.resource_count,
+        prompt_count: validation.prompt_count,
+        has_tools_capability: validation.has_tools_capability,
+        has_resources_capability: validation.has_resources_capability,
+        has_prompts_capability: validation.has_prompts_capability,
+        has_events_capability: validation.has_events_capability,
+        health_endpoint: validation.health_endpoint.clone(),
+        issues: validation.issues.clone(),
+    });
+
+    let openapi = report.openapi.as_ref().map(|validation| OpenApiStatus {
+        valid: validation.valid,
+        version: validation
+            .version
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+        operation_count: validation.stats.total_operations,
+        has_auth: validation.stats.has_security,
+        issues: validation.issues.clone(),
+    });
+
+    let robots_txt = Some(match report.robots_txt.as_ref() {
+        Some(analysis) => RobotsTxtStatus {
             found: true,
-            sitemap_count: sitemaps.len(),
-            sitemaps,
-            ai_crawlers,
-            blocks_all_bots,
-            issues,
-        });
-    }
-
-    Some(RobotsTxtStatus {
-        found: false,
-        sitemap_count: 0,
-        sitemaps: Vec::new(),
-        ai_crawlers: Vec::new(),
-        blocks_all_bots: false,
-        issues: vec!["robots.txt not found".to_string()],
-    })
-}
-
-async fn check_sitemap(base_url: &str, sitemap_urls: Vec<String>) -> Option<SitemapStatus> {
-    use htmlens_core::ai_readiness::sitemap;
-
-    // Extract root domain from URL
-    let root_origin = normalize_origin(base_url);
-    Url::parse(&root_origin).ok()?;
-
-    // Try sitemap URLs from robots.txt first
-    for sitemap_url in &sitemap_urls {
-        if let Ok(parsed) = Url::parse(sitemap_url)
-            && let Ok(mut response) = Fetch::Url(parsed).send().await
-            && response.status_code() == 200
-            && let Ok(xml_content) = response.text().await
-            && let Ok(mut analysis) = sitemap::parse_sitemap(&xml_content, &root_origin)
-        {
-            // If this is a sitemap index with nested sitemaps, fetch them to get accurate counts
-            if !analysis.nested_sitemaps.is_empty() {
-                let mut total_urls = 0;
-                let mut urls_with_lastmod = 0;
-                let mut urls_with_priority = 0;
-                let mut priority_sum = 0.0;
-                let mut all_sample_urls = Vec::new();
-
-                for nested_url in &analysis.nested_sitemaps {
-                    if let Ok(nested_parsed) = Url::parse(nested_url)
-                        && let Ok(mut nested_response) = Fetch::Url(nested_parsed).send().await
-                        && nested_response.status_code() == 200
-                        && let Ok(nested_xml) = nested_response.text().await
-                        && let Ok(nested_analysis) =
-                            sitemap::parse_sitemap(&nested_xml, &root_origin)
-                    {
-                        total_urls += nested_analysis.url_count;
-                        urls_with_lastmod += nested_analysis.statistics.urls_with_lastmod;
-                        urls_with_priority += nested_analysis.statistics.urls_with_priority;
-                        priority_sum += nested_analysis.statistics.avg_priority
-                            * nested_analysis.statistics.urls_with_priority as f32;
-                        // Collect some sample URLs from nested sitemaps
-                        all_sample_urls.extend(nested_analysis.url_entries.iter().take(3).cloned());
+            sitemap_count: analysis.sitemaps.len(),
+            sitemaps: analysis.sitemaps.clone(),
+            ai_crawlers: analysis
+                .ai_crawler_status
+                .iter()
+                .map(|status| AiCrawlerInfo {
+                    name: status.name.clone(),
+                    vendor: status.vendor.clone(),
+                    purpose: status.purpose,
+                    access: match status.access {
+                        AccessLevel::Allowed => "allowed",
+                        AccessLevel::Partial => "partial",
+                        AccessLevel::Blocked => "blocked",
+                        AccessLevel::Default => "default",
                     }
-                }
-
-                // Update analysis with aggregated data
-                if total_urls > 0 {
-                    analysis.url_count = total_urls;
-                    analysis.statistics.total_urls = total_urls;
-                    analysis.statistics.urls_with_lastmod = urls_with_lastmod;
-                    analysis.statistics.urls_with_priority = urls_with_priority;
-                    analysis.statistics.avg_priority = if urls_with_priority > 0 {
-                        priority_sum / urls_with_priority as f32
-                    } else {
-                        0.0
-                    };
-                    analysis.url_entries = all_sample_urls;
-                }
-            }
+                    .to_string(),
+                    rules: status.applicable_rules.clone(),
+                })
+                .collect(),
+            blocks_all_bots: analysis
+                .agent_rules
+                .get("*")
+                .is_some_and(|rules| rules.blocks_all),
+            purpose_summary: analysis.purpose_summary(),
+            issues: category_issues(IssueCategory::RobotsTxt),
+        },
+        None => RobotsTxtStatus {
+            found: false,
+            sitemap_count: 0,
+            sitemaps: Vec::new(),
+            ai_crawlers: Vec::new(),
+            blocks_all_bots: false,
+            purpose_summary: None,
+            issues: category_issues(IssueCategory::RobotsTxt),
+        },
+    });
 
-            // Convert to Worker's SitemapStatus format
-            let sitemap_type = match analysis.sitemap_type {
+    let sitemap = Some(match report.sitemap.as_ref() {
+        Some(analysis) => SitemapStatus {
+            found: true,
+            sitemap_type: match analysis.sitemap_type {
                 sitemap::SitemapType::Standard => "standard",
                 sitemap::SitemapType::Index => "index",
                 sitemap::SitemapType::Unknown => "unknown",
-            };
-
+            }
+            .to_string(),
+            url_count: analysis.url_count,
+            last_modified: analysis.last_modified.clone(),
+            statistics: SitemapStats {
+                total_urls: analysis.statistics.total_urls,
+                urls_with_lastmod: analysis.statistics.urls_with_lastmod,
+                urls_with_priority: analysis.statistics.urls_with_priority,
+                avg_priority: analysis.statistics.avg_priority,
+                content_types: analysis.statistics.content_types.clone(),
+            },
+            nested_sitemaps: analysis.nested_sitemaps.clone(),
             // Take up to 10 sample URLs for display
-            let sample_urls: Vec<SitemapUrlEntry> = analysis
+            sample_urls: analysis
                 .url_entries
                 .iter()
                 .take(10)
@@ -824,295 +519,141 @@ async fn check_sitemap(base_url: &str, sitemap_urls: Vec<String>) -> Option<Site
                     changefreq: u.changefreq.clone(),
                     priority: u.priority,
                 })
-                .collect();
-
-            return Some(SitemapStatus {
-                found: true,
-                sitemap_type: sitemap_type.to_string(),
-                url_count: analysis.url_count,
-                last_modified: analysis.last_modified,
-                statistics: SitemapStats {
-                    total_urls: analysis.statistics.total_urls,
-                    urls_with_lastmod: analysis.statistics.urls_with_lastmod,
-                    urls_with_priority: analysis.statistics.urls_with_priority,
-                    avg_priority: analysis.statistics.avg_priority,
-                    content_types: analysis.statistics.content_types,
-                },
-                nested_sitemaps: analysis.nested_sitemaps,
-                sample_urls,
-                issues: analysis.issues,
-                recommendations: analysis.recommendations,
-            });
-        }
-    }
-
-    // If no sitemap from robots.txt, try default location
-    let default_sitemap = format!("{}/sitemap.xml", root_origin);
-    if let Ok(parsed) = Url::parse(&default_sitemap)
-        && let Ok(mut response) = Fetch::Url(parsed).send().await
-        && response.status_code() == 200
-        && let Ok(xml_content) = response.text().await
-        && let Ok(mut analysis) = sitemap::parse_sitemap(&xml_content, &root_origin)
-    {
-        // If this is a sitemap index with nested sitemaps, fetch them to get accurate counts
-        if !analysis.nested_sitemaps.is_empty() {
-            let mut total_urls = 0;
-            let mut urls_with_lastmod = 0;
-            let mut urls_with_priority = 0;
-            let mut priority_sum = 0.0;
-            let mut all_sample_urls = Vec::new();
-
-            for nested_url in &analysis.nested_sitemaps {
-                if let Ok(nested_parsed) = Url::parse(nested_url)
-                    && let Ok(mut nested_response) = Fetch::Url(nested_parsed).send().await
-                    && nested_response.status_code() == 200
-                    && let Ok(nested_xml) = nested_response.text().await
-                    && let Ok(nested_analysis) = sitemap::parse_sitemap(&nested_xml, &root_origin)
-                {
-                    total_urls += nested_analysis.url_count;
-                    urls_with_lastmod += nested_analysis.statistics.urls_with_lastmod;
-                    urls_with_priority += nested_analysis.statistics.urls_with_priority;
-                    priority_sum += nested_analysis.statistics.avg_priority
-                        * nested_analysis.statistics.urls_with_priority as f32;
-                    // Collect some sample URLs from nested sitemaps
-                    all_sample_urls.extend(nested_analysis.url_entries.iter().take(3).cloned());
-                }
-            }
-
-            // Update analysis with aggregated data
-            if total_urls > 0 {
-                analysis.url_count = total_urls;
-                analysis.statistics.total_urls = total_urls;
-                analysis.statistics.urls_with_lastmod = urls_with_lastmod;
-                analysis.statistics.urls_with_priority = urls_with_priority;
-                analysis.statistics.avg_priority = if urls_with_priority > 0 {
-                    priority_sum / urls_with_priority as f32
-                } else {
-                    0.0
-                };
-                analysis.url_entries = all_sample_urls;
-            }
-        }
-
-        let sitemap_type = match analysis.sitemap_type {
-            sitemap::SitemapType::Standard => "standard",
-            sitemap::SitemapType::Index => "index",
-            sitemap::SitemapType::Unknown => "unknown",
-        };
-
-        let sample_urls: Vec<SitemapUrlEntry> = analysis
-            .url_entries
-            .iter()
-            .take(10)
-            .map(|u| SitemapUrlEntry {
-                loc: u.loc.clone(),
-                lastmod: u.lastmod.clone(),
-                changefreq: u.changefreq.clone(),
-                priority: u.priority,
-            })
-            .collect();
-
-        return Some(SitemapStatus {
-            found: true,
-            sitemap_type: sitemap_type.to_string(),
-            url_count: analysis.url_count,
-            last_modified: analysis.last_modified,
+                .collect(),
+            issues: analysis.issues.clone(),
+            recommendations: analysis.recommendations.clone(),
+        },
+        None => SitemapStatus {
+            found: false,
+            sitemap_type: "unknown".to_string(),
+            url_count: 0,
+            last_modified: None,
             statistics: SitemapStats {
-                total_urls: analysis.statistics.total_urls,
-                urls_with_lastmod: analysis.statistics.urls_with_lastmod,
-                urls_with_priority: analysis.statistics.urls_with_priority,
-                avg_priority: analysis.statistics.avg_priority,
-                content_types: analysis.statistics.content_types,
+                total_urls: 0,
+                urls_with_lastmod: 0,
+                urls_with_priority: 0,
+                avg_priority: 0.0,
+                content_types: std::collections::HashMap::new(),
             },
-            nested_sitemaps: analysis.nested_sitemaps,
-            sample_urls,
-            issues: analysis.issues,
-            recommendations: analysis.recommendations,
-        });
-    }
-
-    // Not found
-    Some(SitemapStatus {
-        found: false,
-        sitemap_type: "unknown".to_string(),
-        url_count: 0,
-        last_modified: None,
-        statistics: SitemapStats {
-            total_urls: 0,
-            urls_with_lastmod: 0,
-            urls_with_priority: 0,
-            avg_priority: 0.0,
-            content_types: std::collections::HashMap::new(),
+            nested_sitemaps: Vec::new(),
+            sample_urls: Vec::new(),
+            issues: category_issues(IssueCategory::Sitemap),
+            recommendations: vec![
+                "Create a sitemap.xml file to help crawlers discover your content".to_string(),
+            ],
         },
-        nested_sitemaps: Vec::new(),
-        sample_urls: Vec::new(),
-        issues: vec!["No sitemap found".to_string()],
-        recommendations: vec![
-            "Create a sitemap.xml file to help crawlers discover your content".to_string(),
-        ],
-    })
-}
-
-async fn check_semantic_html(base_url: &str) -> Option<SemanticHtmlStatus> {
-    // Fetch the HTML page
-    let url = base_url.trim_end_matches('/');
-    let parsed = Url::parse(url).ok()?;
-    let mut response = match Fetch::Url(parsed).send().await {
-        Ok(r) => r,
-        Err(_) => return None,
-    };
-
-    if response.status_code() != 200 {
-        return None;
-    }
-
-    let html = match response.text().await {
-        Ok(text) => text,
-        Err(_) => return None,
-    };
+    });
 
-    // Use the semantic_html analyzer from htmlens-core
-    use htmlens_core::ai_readiness::semantic_html;
-    let analysis = semantic_html::analyze_semantic_html(&html);
-
-    // Convert to our simplified API format
-    let label_percentage = if analysis.forms.total_inputs > 0 {
-        (analysis.forms.labeled_inputs as f32 / analysis.forms.total_inputs as f32 * 100.0) as u32
-    } else {
-        100
-    };
-
-    let alt_percentage = if analysis.images.total_images > 0 {
-        (analysis.images.images_with_alt as f32 / analysis.images.total_images as f32 * 100.0)
-            as u32
-    } else {
-        100
-    };
-
-    Some(SemanticHtmlStatus {
-        landmarks: LandmarksInfo {
-            has_main: analysis.landmarks.has_main,
-            has_navigation: analysis.landmarks.has_navigation,
-            has_header: analysis.landmarks.has_header,
-            has_footer: analysis.landmarks.has_footer,
-            article_count: analysis.landmarks.article_count,
-        },
-        headings: HeadingsInfo {
-            has_single_h1: analysis.headings.has_single_h1,
-            proper_hierarchy: analysis.headings.proper_hierarchy,
-            distribution: analysis.headings.distribution,
-        },
-        forms: FormsInfo {
-            total_inputs: analysis.forms.total_inputs,
-            labeled_inputs: analysis.forms.labeled_inputs,
-            label_percentage,
+    let llms_txt = Some(match report.llms_txt.as_ref() {
+        Some(analysis) => LlmsTxtStatus {
+            found: true,
+            title: analysis.title.clone(),
+            summary: analysis.summary.clone(),
+            sections: analysis
+                .sections
+                .iter()
+                .map(|section| LlmsTxtSectionStatus {
+                    title: section.title.clone(),
+                    optional: section.optional,
+                    link_count: section.links.len(),
+                })
+                .collect(),
+            link_count: analysis.link_count(),
+            full_found: analysis.full.is_some(),
+            full_size_bytes: analysis.full.as_ref().map_or(0, |full| full.size_bytes),
+            issues: category_issues(IssueCategory::LlmsTxt),
         },
-        images: ImagesInfo {
-            total_images: analysis.images.total_images,
-            images_with_alt: analysis.images.images_with_alt,
-            alt_percentage,
+        None => LlmsTxtStatus {
+            found: false,
+            title: None,
+            summary: None,
+            sections: Vec::new(),
+            link_count: 0,
+            full_found: false,
+            full_size_bytes: 0,
+            issues: category_issues(IssueCategory::LlmsTxt),
         },
-        issues: analysis.issues,
-        recommendations: analysis.recommendations,
-    })
-}
+    });
 
-fn check_crawler_access(robots_txt: &str, crawler: &str) -> String {
-    let lines: Vec<&str> = robots_txt.lines().map(|l| l.trim()).collect();
-    let mut current_agent = String::new();
-    let mut specific_rules = false;
-    let mut is_blocked = false;
-    let mut has_disallow = false;
-
-    for line in &lines {
-        if line.to_lowercase().starts_with("user-agent:") {
-            if let Some(agent) = line.split(':').nth(1) {
-                current_agent = agent.trim().to_lowercase();
-                if current_agent == crawler.to_lowercase() {
-                    specific_rules = true;
-                }
-            }
-        } else if !current_agent.is_empty()
-            && (current_agent == crawler.to_lowercase() || current_agent == "*")
-            && line.to_lowercase().starts_with("disallow:")
-            && let Some(path) = line.split(':').nth(1)
-        {
-            let path = path.trim();
-            has_disallow = true;
-            if path == "/" {
-                is_blocked = true;
-            }
-        }
-    }
-
-    if is_blocked {
-        "blocked".to_string()
-    } else if has_disallow && specific_rules {
-        "partial".to_string()
-    } else if specific_rules {
-        "allowed".to_string()
-    } else {
-        "default".to_string()
-    }
-}
+    let semantic_html = report.semantic_html.as_ref().map(|analysis| {
+        let label_percentage = if analysis.forms.total_inputs > 0 {
+            (analysis.forms.labeled_inputs as f32 / analysis.forms.total_inputs as f32 * 100.0)
+                as u32
+        } else {
+            100
+        };
 
-fn get_crawler_rules(robots_txt: &str, crawler: &str) -> Option<String> {
-    let lines: Vec<&str> = robots_txt.lines().map(|l| l.trim()).collect();
-    let mut rules = Vec::new();
-    let mut in_target_agent = false;
+        let alt_percentage = if analysis.images.total_images > 0 {
+            (analysis.images.images_with_alt as f32 / analysis.images.total_images as f32 * 100.0)
+                as u32
+        } else {
+            100
+        };
 
-    for line in &lines {
-        if line.to_lowercase().starts_with("user-agent:") {
-            if in_target_agent {
-                break; // Stop when we hit next agent
-            }
-            if let Some(agent) = line.split(':').nth(1) {
-                let agent_lower = agent.trim().to_lowercase();
-                if agent_lower == crawler.to_lowercase() {
-                    in_target_agent = true;
-                    rules.push(line.to_string());
-                }
-            }
-        } else if in_target_agent
-            && (line.to_lowercase().starts_with("disallow:")
-                || line.to_lowercase().starts_with("allow:")
-                || line.to_lowercase().starts_with("crawl-delay:"))
-        {
-            rules.push(line.to_string());
+        SemanticHtmlStatus {
+            landmarks: LandmarksInfo {
+                has_main: analysis.landmarks.has_main,
+                has_navigation: analysis.landmarks.has_navigation,
+                has_header: analysis.landmarks.has_header,
+                has_footer: analysis.landmarks.has_footer,
+                article_count: analysis.landmarks.article_count,
+            },
+            headings: HeadingsInfo {
+                has_single_h1: analysis.headings.has_single_h1,
+                proper_hierarchy: analysis.headings.proper_hierarchy,
+                distribution: analysis.headings.distribution.clone(),
+            },
+            forms: FormsInfo {
+                total_inputs: analysis.forms.total_inputs,
+                labeled_inputs: analysis.forms.labeled_inputs,
+                label_percentage,
+            },
+            images: ImagesInfo {
+                total_images: analysis.images.total_images,
+                images_with_alt: analysis.images.images_with_alt,
+                alt_percentage,
+            },
+            issues: analysis.issues.clone(),
+            recommendations: analysis.recommendations.clone(),
         }
-    }
+    });
 
-    if rules.is_empty() {
-        // Check for wildcard
-        let mut in_wildcard = false;
-        for line in &lines {
-            if line.to_lowercase().starts_with("user-agent:")
-                && let Some(agent) = line.split(':').nth(1)
-                && agent.trim() == "*"
-            {
-                in_wildcard = true;
-                rules.push(line.to_string());
-            } else if in_wildcard
-                && (line.to_lowercase().starts_with("disallow:")
-                    || line.to_lowercase().starts_with("allow:"))
-            {
-                rules.push(line.to_string());
-            }
-        }
+    AiReadinessData {
+        score: report.score,
+        breakdown: report.breakdown.clone(),
+        strengths: report.strengths.clone(),
+        issues: report.issues.clone(),
+        recommendations: report.recommendations.clone(),
+        well_known,
+        ai_plugin,
+        mcp,
+        openapi,
+        robots_txt,
+        sitemap,
+        llms_txt,
+        semantic_html,
     }
+}
 
-    if rules.is_empty() {
-        None
-    } else {
-        Some(rules.join("; "))
+fn file_status(check: &well_known::FileCheck, is_json: bool) -> FileStatus {
+    // Status 200 with invalid JSON is likely an error page, so it is not "found"
+    FileStatus {
+        found: check.found && check.valid,
+        status: check.status_code.unwrap_or(0),
+        valid: (is_json && check.found).then_some(check.valid),
     }
 }
 
+/// Render the page header and the structured data summaries, using the same
+/// sections as the CLI's markdown report
+///
+/// `structured_data` holds the page's JSON-LD blocks, microdata items and
+/// RDFa resources, all as compact schema.org JSON-LD.
 fn format_cli_style_markdown(
     url: &str,
     title: &str,
     description: &str,
-    jsonld_blocks: &[serde_json::Value],
+    structured_data: &[serde_json::Value],
 ) -> String {
     let mut md = String::new();
 
@@ -1124,577 +665,41 @@ fn format_cli_style_markdown(
         md.push_str(&format!("> {}\n\n", description));
     }
 
-    md.push_str(&format!("**URL**: {}\n", url));
-
-    // Extract different entity types
-    let mut organizations = Vec::new();
-    let mut product_groups = Vec::new();
-    let mut products = Vec::new();
-    let mut breadcrumbs = Vec::new();
-    let mut webpages = Vec::new();
-    let mut websites = Vec::new();
-    let mut images = Vec::new();
-    let mut articles = Vec::new();
-    let mut news_articles = Vec::new();
-    let mut reviews = Vec::new();
-    let mut local_businesses = Vec::new();
-    let mut events = Vec::new();
-    let mut videos = Vec::new();
-    let mut other_entities = Vec::new();
-
-    // First, flatten any @graph structures into individual entities
-    let mut all_entities: Vec<&serde_json::Value> = Vec::new();
-    for block in jsonld_blocks {
-        // Check if this block contains a @graph array
-        if let Some(graph) = block.get("@graph").and_then(|g| g.as_array()) {
-            // Add each item from the graph
-            for item in graph {
-                all_entities.push(item);
-            }
-        } else {
-            // Direct entity (no @graph wrapper)
-            all_entities.push(block);
-        }
-    }
-
-    for entity in all_entities {
-        let entity_type = entity
-            .get("@type")
-            .and_then(|t| t.as_str())
-            .unwrap_or("Unknown");
-        match entity_type {
-            "Organization" => organizations.push(entity),
-            "ProductGroup" => product_groups.push(entity),
-            "Product" => products.push(entity),
-            "BreadcrumbList" => breadcrumbs.push(entity),
-            "WebPage" => webpages.push(entity),
-            "WebSite" => websites.push(entity),
-            "ImageObject" => images.push(entity),
-            "Article" | "BlogPosting" => articles.push(entity),
-            "NewsArticle" => news_articles.push(entity),
-            "Review" => reviews.push(entity),
-            "AggregateRating" => reviews.push(entity),
-            "LocalBusiness" | "Restaurant" | "Store" => local_businesses.push(entity),
-            "Event" => events.push(entity),
-            "VideoObject" => videos.push(entity),
-            _ => other_entities.push((entity_type, entity)),
-        }
-    }
-
-    // Count total entities
-    let total_entities = organizations.len()
-        + product_groups.len()
-        + products.len()
-        + breadcrumbs.len()
-        + webpages.len()
-        + websites.len()
-        + images.len()
-        + articles.len()
-        + news_articles.len()
-        + reviews.len()
-        + local_businesses.len()
-        + events.len()
-        + videos.len()
-        + other_entities.len();
-
-    md.push_str(&format!("**Structured Data Entities Found**: {}\n\n", total_entities));
-
-    // Render Organizations
-    if !organizations.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("🏢 Organization\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for org in organizations {
-            if let Some(name) = org.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• Name             : {}\n", name));
-            }
-            if let Some(url) = org.get("url").and_then(|u| u.as_str()) {
-                md.push_str(&format!("• URL              : {}\n", url));
-            }
-        }
-        md.push('\n');
-    }
-
-    // Render Breadcrumbs
-    if !breadcrumbs.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("🍞 Breadcrumb Navigation\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for bc in breadcrumbs {
-            if let Some(items) = bc.get("itemListElement").and_then(|i| i.as_array()) {
-                let crumbs: Vec<String> = items
-                    .iter()
-                    .filter_map(|item| {
-                        let name = item
-                            .get("name")
-                            .or_else(|| item.get("item").and_then(|i| i.get("name")))
-                            .and_then(|n| n.as_str())?;
-                        let url = item
-                            .get("item")
-                            .and_then(|i| {
-                                if i.is_string() {
-                                    i.as_str()
-                                } else {
-                                    i.get("@id").and_then(|id| id.as_str())
-                                }
-                            })
-                            .unwrap_or("");
-                        Some(if !url.is_empty() {
-                            format!("{} ({})", name, url)
-                        } else {
-                            name.to_string()
-                        })
-                    })
-                    .collect();
-                md.push_str(&crumbs.join(" → "));
-                md.push('\n');
-            }
-        }
-        md.push('\n');
-    }
-
-    // Render ProductGroups
-    for pg in &product_groups {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        let pg_name = pg
-            .get("name")
-            .and_then(|n| n.as_str())
-            .unwrap_or("ProductGroup");
-        md.push_str(&format!("📦 ProductGroup: {}\n", pg_name));
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-
-        if let Some(pg_id) = pg.get("productGroupID").and_then(|i| i.as_str()) {
-            md.push_str(&format!("• ProductGroup ID  : {}\n", pg_id));
-        }
-        if let Some(brand) = pg
-            .get("brand")
-            .and_then(|b| b.get("name"))
-            .and_then(|n| n.as_str())
-        {
-            md.push_str(&format!("• Brand            : {}\n", brand));
-        }
-
-        // variesBy
-        if let Some(varies) = pg.get("variesBy") {
-            let varies_str = if let Some(arr) = varies.as_array() {
-                arr.iter()
-                    .filter_map(|v| v.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            } else {
-                varies.as_str().unwrap_or("").to_string()
-            };
-            md.push_str(&format!("• Varies By        : {}\n", varies_str));
-        }
-
-        // Variant count
-        if let Some(variants) = pg.get("hasVariant") {
-            let count = if variants.is_array() {
-                variants.as_array().unwrap().len()
-            } else {
-                1
-            };
-            md.push_str(&format!("• Total Variants   : {}\n", count));
-
-            // Extract price range and availability from offers
-            if let Some(offers) = pg.get("offers").and_then(|o| o.as_array()) {
-                let prices: Vec<f64> = offers
-                    .iter()
-                    .filter_map(|o| o.get("price").and_then(|p| p.as_f64()))
-                    .collect();
-
-                if !prices.is_empty() {
-                    let min_price = prices.iter().cloned().fold(f64::INFINITY, f64::min);
-                    let max_price = prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-                    let currency = offers[0]
-                        .get("priceCurrency")
-                        .and_then(|c| c.as_str())
-                        .unwrap_or("€");
-
-                    if (min_price - max_price).abs() < 0.01 {
-                        md.push_str(&format!(
-                            "• Price Range      : {}{:.2}\n",
-                            currency, min_price
-                        ));
-                    } else {
-                        md.push_str(&format!(
-                            "• Price Range      : {}{:.2} - {}{:.2}\n",
-                            currency, min_price, currency, max_price
-                        ));
-                    }
-                }
-
-                let in_stock = offers
-                    .iter()
-                    .filter(|o| {
-                        o.get("availability")
-                            .and_then(|a| a.as_str())
-                            .is_some_and(|s| s.contains("InStock"))
-                    })
-                    .count();
-                let out_of_stock = offers
-                    .iter()
-                    .filter(|o| {
-                        o.get("availability")
-                            .and_then(|a| a.as_str())
-                            .is_some_and(|s| s.contains("OutOfStock"))
-                    })
-                    .count();
-
-                if in_stock > 0 || out_of_stock > 0 {
-                    md.push_str(&format!(
-                        "• Availability     : {} InStock / {} OutOfStock\n",
-                        in_stock, out_of_stock
-                    ));
-                }
-            }
-        }
-
-        md.push('\n');
-
-        // Render variant table if available
-        if let Some(variants) = pg.get("hasVariant").and_then(|v| v.as_array()) {
-            md.push_str("─────────────────────────────────────────────────────────────\n");
-            md.push_str("🧩 Variants\n");
-            md.push_str("─────────────────────────────────────────────────────────────\n");
-
-            // Build table header
-            let varies_by: Vec<&str> = pg
-                .get("variesBy")
-                .and_then(|v| v.as_array())
-                .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
-                .unwrap_or_default();
-
-            md.push_str("| SKU       |");
-            for prop in &varies_by {
-                md.push_str(&format!(" {} |", prop));
-            }
-            md.push_str(" Price   | Availability   |\n");
-
-            md.push_str("| --------- |");
-            for _ in &varies_by {
-                md.push_str(" ---- |");
-            }
-            md.push_str(" ------- | -------------- |\n");
-
-            // Build table rows
-            for variant in variants {
-                let sku = variant.get("sku").and_then(|s| s.as_str()).unwrap_or("-");
-                md.push_str(&format!("| {} |", sku));
-
-                for prop in &varies_by {
-                    let value = variant
-                        .get(prop)
-                        .or_else(|| variant.get(prop.to_lowercase()))
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("-");
-                    md.push_str(&format!(" {} |", value));
-                }
-
-                // Price
-                let offer = variant.get("offers").and_then(|o| {
-                    if o.is_array() {
-                        o.as_array().unwrap().first()
-                    } else {
-                        Some(o)
-                    }
-                });
-                let currency = offer
-                    .and_then(|o| o.get("priceCurrency"))
-                    .and_then(|c| c.as_str())
-                    .unwrap_or("€");
-                let price = offer
-                    .and_then(|o| o.get("price"))
-                    .and_then(|p| p.as_f64())
-                    .map(|p| format!("{}{:.0}", currency, p))
-                    .unwrap_or_else(|| "-".to_string());
-                md.push_str(&format!(" {} |", price));
-
-                // Availability
-                let avail = offer
-                    .and_then(|o| o.get("availability"))
-                    .and_then(|a| a.as_str())
-                    .map(|a| {
-                        if a.contains("InStock") {
-                            "✅ InStock"
-                        } else {
-                            "❌ OutOfStock"
-                        }
-                    })
-                    .unwrap_or("-");
-                md.push_str(&format!(" {} |\n", avail));
-            }
-            md.push('\n');
-        }
-    }
-
-    // Render standalone Products
-    if !products.is_empty() && product_groups.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str(&format!("📦 Products Found: {}\n", products.len()));
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for product in products {
-            if let Some(name) = product.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• {}\n", name));
-            }
-        }
-        md.push('\n');
-    }
+    md.push_str(&format!("**URL**: {}\n\n", url));
 
-    // Render WebSite info
-    if !websites.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("🌐 WebSite\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for site in &websites {
-            if let Some(name) = site.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• Name             : {}\n", name));
-            }
-            if let Some(url) = site.get("url").and_then(|u| u.as_str()) {
-                md.push_str(&format!("• URL              : {}\n", url));
-            }
-            if let Some(desc) = site.get("description").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Description      : {}\n", desc));
-            }
-            if let Some(lang) = site.get("inLanguage").and_then(|l| l.as_str()) {
-                md.push_str(&format!("• Language         : {}\n", lang));
-            }
-            // Check for search action
-            if let Some(actions) = site.get("potentialAction").and_then(|a| a.as_array()) {
-                for action in actions {
-                    if action.get("@type").and_then(|t| t.as_str()) == Some("SearchAction") {
-                        md.push_str("• Search           : ✅ Site search enabled\n");
-                    }
-                }
-            }
-        }
-        md.push('\n');
-    }
+    let insights = GraphInsights::from_json_ld(&serde_json::Value::Array(structured_data.to_vec()));
 
-    // Render WebPage info
-    if !webpages.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("📄 WebPage\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for page in &webpages {
-            if let Some(name) = page.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• Name             : {}\n", name));
-            }
-            if let Some(url) = page.get("url").and_then(|u| u.as_str()) {
-                md.push_str(&format!("• URL              : {}\n", url));
-            }
-            if let Some(desc) = page.get("description").and_then(|d| d.as_str()) {
-                let truncated = if desc.len() > 100 {
-                    format!("{}...", &desc[..100])
-                } else {
-                    desc.to_string()
-                };
-                md.push_str(&format!("• Description      : {}\n", truncated));
-            }
-            if let Some(lang) = page.get("inLanguage").and_then(|l| l.as_str()) {
-                md.push_str(&format!("• Language         : {}\n", lang));
-            }
-            if let Some(published) = page.get("datePublished").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Published        : {}\n", published));
-            }
-            if let Some(modified) = page.get("dateModified").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Modified         : {}\n", modified));
-            }
-        }
-        md.push('\n');
+    for org in &insights.organizations {
+        markdown::render_organization(&mut md, org);
     }
-
-    // Render other entities
-    if !other_entities.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("📋 Other Entities\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for (entity_type, entity) in other_entities {
-            let name = entity.get("name").and_then(|n| n.as_str()).unwrap_or("");
-            md.push_str(&format!(
-                "• {} {}\n",
-                entity_type,
-                if !name.is_empty() {
-                    format!("- {}", name)
-                } else {
-                    String::new()
-                }
-            ));
+    for entity in &insights.other_entities {
+        if entity.entity_type == "ContactPoint" {
+            markdown::render_entity(&mut md, entity);
         }
-        md.push('\n');
     }
-
-    // Render Articles & BlogPosts
-    if !articles.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("📝 Article / BlogPost\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for article in articles {
-            if let Some(headline) = article.get("headline").and_then(|h| h.as_str()) {
-                md.push_str(&format!("• Title            : {}\n", headline));
-            }
-            if let Some(author) = article.get("author") {
-                if let Some(name) = author.get("name").and_then(|n| n.as_str()) {
-                    md.push_str(&format!("• Author           : {}\n", name));
-                } else if let Some(author_str) = author.as_str() {
-                    md.push_str(&format!("• Author           : {}\n", author_str));
-                }
-            }
-            if let Some(published) = article.get("datePublished").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Published        : {}\n", published));
-            }
-            if let Some(modified) = article.get("dateModified").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Modified         : {}\n", modified));
-            }
-            if let Some(desc) = article.get("description").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Description      : {}\n", desc.trim_end()));
-            }
-        }
-        md.push('\n');
+    for breadcrumb in &insights.breadcrumbs {
+        markdown::render_breadcrumb(&mut md, breadcrumb);
     }
-
-    // Render News Articles
-    if !news_articles.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("📰 News Article\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for article in news_articles {
-            if let Some(headline) = article.get("headline").and_then(|h| h.as_str()) {
-                md.push_str(&format!("• Title            : {}\n", headline));
-            }
-            if let Some(author) = article.get("author") {
-                if let Some(name) = author.get("name").and_then(|n| n.as_str()) {
-                    md.push_str(&format!("• Author           : {}\n", name));
-                } else if let Some(author_str) = author.as_str() {
-                    md.push_str(&format!("• Author           : {}\n", author_str));
-                }
-            }
-            if let Some(published) = article.get("datePublished").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Published        : {}\n", published));
-            }
-            if let Some(image) = article.get("image") {
-                if let Some(url) = image.get("url").and_then(|u| u.as_str()) {
-                    md.push_str(&format!("• Image            : {}\n", url));
-                } else if let Some(url) = image.as_str() {
-                    md.push_str(&format!("• Image            : {}\n", url));
-                }
-            }
-        }
-        md.push('\n');
+    for pg in &insights.product_groups {
+        markdown::render_product_group(&mut md, pg);
     }
-
-    // Render Reviews & Ratings
-    if !reviews.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("⭐ Reviews & Ratings\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for review in reviews {
-            if let Some(name) = review.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• Name             : {}\n", name));
-            }
-            if let Some(rating) = review.get("ratingValue").and_then(|r| r.as_f64()) {
-                if let Some(best) = review.get("bestRating").and_then(|b| b.as_f64()) {
-                    let stars = "★".repeat(rating as usize) + &"☆".repeat((best as usize) - (rating as usize));
-                    md.push_str(&format!("• Rating           : {} ({}/{})\n", stars, rating, best));
-                } else {
-                    md.push_str(&format!("• Rating           : {}\n", rating));
-                }
-            }
-            if let Some(count) = review.get("reviewCount").and_then(|c| c.as_u64()) {
-                md.push_str(&format!("• Review Count     : {}\n", count));
-            }
-        }
-        md.push('\n');
+    for webpage in &insights.web_pages {
+        markdown::render_webpage(&mut md, webpage);
     }
-
-    // Render LocalBusiness
-    if !local_businesses.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("🏪 Local Business\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for business in local_businesses {
-            if let Some(name) = business.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• Name             : {}\n", name));
-            }
-            if let Some(address) = business.get("address") {
-                if let Some(street) = address.get("streetAddress").and_then(|s| s.as_str()) {
-                    md.push_str(&format!("• Address          : {}", street));
-                    if let Some(city) = address.get("addressLocality").and_then(|c| c.as_str()) {
-                        md.push_str(&format!(", {}", city));
-                    }
-                    md.push('\n');
-                }
-            }
-            if let Some(phone) = business.get("telephone").and_then(|p| p.as_str()) {
-                md.push_str(&format!("• Phone            : {}\n", phone));
-            }
-            if let Some(price) = business.get("priceRange").and_then(|p| p.as_str()) {
-                md.push_str(&format!("• Price Range      : {}\n", price));
-            }
+    for entity in &insights.other_entities {
+        if entity.entity_type != "ContactPoint" {
+            markdown::render_entity(&mut md, entity);
         }
-        md.push('\n');
     }
-
-    // Render Events
-    if !events.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("🎯 Events\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for event in events {
-            if let Some(name) = event.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• Name             : {}\n", name));
-            }
-            if let Some(start) = event.get("startDate").and_then(|s| s.as_str()) {
-                md.push_str(&format!("• Start Date       : {}\n", start));
-            }
-            if let Some(end) = event.get("endDate").and_then(|e| e.as_str()) {
-                md.push_str(&format!("• End Date         : {}\n", end));
-            }
-            if let Some(location) = event.get("location") {
-                if let Some(name) = location.get("name").and_then(|n| n.as_str()) {
-                    md.push_str(&format!("• Location         : {}\n", name));
-                }
-            }
-            if let Some(status) = event.get("eventStatus").and_then(|s| s.as_str()) {
-                md.push_str(&format!("• Status           : {}\n", status));
-            }
-        }
-        md.push('\n');
+    if !insights.data_downloads.is_empty() {
+        markdown::render_data_downloads_section(&mut md, &insights.data_downloads);
     }
 
-    // Render Videos
-    if !videos.is_empty() {
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        md.push_str("🎬 Videos\n");
-        md.push_str("─────────────────────────────────────────────────────────────\n");
-        for video in videos {
-            if let Some(name) = video.get("name").and_then(|n| n.as_str()) {
-                md.push_str(&format!("• Title            : {}\n", name));
-            }
-            if let Some(duration) = video.get("duration").and_then(|d| d.as_str()) {
-                md.push_str(&format!("• Duration         : {}\n", duration));
-            }
-            if let Some(uploaded) = video.get("uploadDate").and_then(|u| u.as_str()) {
-                md.push_str(&format!("• Uploaded         : {}\n", uploaded));
-            }
-            if let Some(url) = video.get("contentUrl").and_then(|u| u.as_str()) {
-                md.push_str(&format!("• Content URL      : {}\n", url));
-            }
-        }
-        md.push('\n');
-    }
-
-    md.push_str("─────────────────────────────────────────────────────────────\n");
-    md.push_str(&format!(
-        "📊 Total: {} Structured Data Entities\n",
-        total_entities
-    ));
-
     md
 }
 
 #[event(fetch)]
-async fn main(req: Request, _env: Env, _ctx: Context) -> Result<Response> {
+async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     console_error_panic_hook::set_once();
 
     let url = req.url()?;
@@ -1799,27 +804,43 @@ async fn main(req: Request, _env: Env, _ctx: Context) -> Result<Response> {
             let html = fetch_response.text().await?;
             console_log!("[Worker] Fetched {} bytes", html.len());
 
-            // Extract JSON-LD using htmlens-core FIRST (before sanitizing)
-            let (jsonld_blocks, jsonld_graph) = match parser::extract_json_ld_blocks(&html) {
-                Ok(blocks) => {
-                    console_log!("[Worker] Found {} blocks", blocks.len());
+            // Extract JSON-LD, microdata and RDFa using htmlens-core FIRST (before sanitizing)
+            let microdata_items = parser::microdata::extract_microdata(&html, &target_url);
+            let rdfa_items = parser::rdfa::extract_rdfa(&html, &target_url);
+            let mut diagnostics = Vec::new();
+            let (jsonld_blocks, jsonld_graph) = match parser::extract_json_ld_scripts(&html) {
+                Ok(scripts) => {
+                    console_log!(
+                        "[Worker] Found {} blocks, {} microdata items, {} RDFa resources",
+                        scripts.len(),
+                        microdata_items.len(),
+                        rdfa_items.len()
+                    );
+
+                    // Skip (and report) blocks that are not valid JSON-LD
+                    let blocks: Vec<serde_json::Value> = scripts
+                        .iter()
+                        .filter_map(|script| script.parse().map_err(|d| diagnostics.push(d)).ok())
+                        .collect();
 
-                    // Combine blocks into @graph structure
-                    let combined = parser::combine_json_ld_blocks(&blocks).unwrap_or_else(|_| {
-                        r#"{"@context":"https://schema.org","@graph":[]}"#.to_string()
-                    });
+                    // Combine blocks, microdata items and RDFa resources into @graph structure
+                    let graph_blocks: Vec<String> = blocks
+                        .iter()
+                        .chain(&microdata_items)
+                        .chain(&rdfa_items)
+                        .map(|item| item.to_string())
+                        .collect();
+                    let combined =
+                        parser::combine_json_ld_blocks(&graph_blocks).unwrap_or_else(|_| {
+                            r#"{"@context":"https://schema.org","@graph":[]}"#.to_string()
+                        });
 
                     let graph_value: serde_json::Value = serde_json::from_str(&combined)
                         .unwrap_or_else(
                             |_| serde_json::json!({"@context": "https://schema.org", "@graph": []}),
                         );
 
-                    let blocks_vec: Vec<serde_json::Value> = blocks
-                        .into_iter()
-                        .filter_map(|b| serde_json::from_str(&b).ok())
-                        .collect();
-
-                    (blocks_vec, graph_value)
+                    (blocks, graph_value)
                 }
                 Err(_) => (
                     vec![],
@@ -1836,44 +857,70 @@ async fn main(req: Request, _env: Env, _ctx: Context) -> Result<Response> {
             );
 
             // Extract metadata
-            let title = extract_title(&html);
-            let description = extract_description(&html);
-
-            // Build graph nodes
-            let nodes: Vec<GraphNode> = jsonld_blocks
+            let page_metadata = parser::extract_page_metadata(&html, &target_url);
+            let title = extract_title(&page_metadata);
+            let description = extract_description(&page_metadata);
+
+            // Build graph nodes, one per JSON-LD block, microdata item or RDFa resource
+            let node_sources = [
+                ("jsonld", StructuredDataSource::JsonLd, &jsonld_blocks),
+                (
+                    "microdata",
+                    StructuredDataSource::Microdata,
+                    &microdata_items,
+                ),
+                ("rdfa", StructuredDataSource::Rdfa, &rdfa_items),
+            ];
+            let nodes: Vec<GraphNode> = node_sources
                 .iter()
-                .enumerate()
-                .map(|(idx, block)| {
-                    let node_type = block
-                        .get("@type")
-                        .and_then(|t| t.as_str())
-                        .map(|s| vec![s.to_string()])
-                        .unwrap_or_else(|| vec!["Unknown".to_string()]);
-
-                    let name = block
-                        .get("name")
-                        .and_then(|n| n.as_str())
-                        .map(|s| s.to_string());
-
-                    let mut properties = std::collections::HashMap::new();
-                    if let Some(name) = name {
-                        properties.insert("name".to_string(), serde_json::Value::String(name));
-                    }
+                .flat_map(|(prefix, source, blocks)| {
+                    blocks.iter().enumerate().map(move |(idx, block)| {
+                        let node_type = block
+                            .get("@type")
+                            .and_then(|t| t.as_str())
+                            .map(|s| vec![s.to_string()])
+                            .unwrap_or_else(|| vec!["Unknown".to_string()]);
+
+                        let name = block
+                            .get("name")
+                            .and_then(|n| n.as_str())
+                            .map(|s| s.to_string());
 
-                    GraphNode {
-                        id: format!("jsonld-{}", idx),
-                        types: node_type,
-                        properties,
-                    }
+                        let mut properties = std::collections::BTreeMap::new();
+                        if let Some(name) = name {
+                            properties.insert("name".to_string(), serde_json::Value::String(name));
+                        }
+
+                        GraphNode {
+                            id: format!("{}-{}", prefix, idx),
+                            types: node_type,
+                            properties,
+                            sources: vec![source.as_str().to_string()],
+                        }
+                    })
                 })
                 .collect();
 
-            // Build CLI-style markdown
+            // Build CLI-style markdown from all structured data on the page
+            let structured_data: Vec<serde_json::Value> = jsonld_blocks
+                .iter()
+                .chain(&microdata_items)
+                .chain(&rdfa_items)
+                .cloned()
+                .collect();
             let markdown =
-                format_cli_style_markdown(&target_url, &title, &description, &jsonld_blocks);
+                format_cli_style_markdown(&target_url, &title, &description, &structured_data);
 
             // Check AI readiness
-            let ai_readiness = check_ai_readiness(&target_url).await;
+            // A failed check is reported as such, not as an empty report
+            let (ai_readiness, ai_readiness_error) =
+                match check_ai_readiness(&target_url, &scoring_weights(&env)).await {
+                    Ok(data) => (Some(data), None),
+                    Err(e) => {
+                        console_log!("[AI Readiness] Check failed for {}: {:#}", target_url, e);
+                        (None, Some(format!("{:#}", e)))
+                    }
+                };
 
             let response_data = ApiResponse {
                 url: target_url,
@@ -1887,12 +934,15 @@ async fn main(req: Request, _env: Env, _ctx: Context) -> Result<Response> {
                 jsonld_graph,  // Combined @graph structure
                 markdown,      // CLI-style product tables
                 page_markdown, // HTML to Markdown conversion
+                page_metadata,
+                diagnostics,
                 meta: MetaData {
                     html_length: html.len(),
                     jsonld_count: jsonld_blocks.len(),
                     wasm_status: "rust".to_string(),
                 },
                 ai_readiness,
+                ai_readiness_error,
             };
 
             headers.set("Content-Type", "application/json")?;
@@ -1949,12 +999,19 @@ mod integration_tests {
             jsonld_graph: serde_json::json!({"@context": "https://schema.org", "@graph": []}),
             markdown: "# Test".to_string(),
             page_markdown: "Test content".to_string(),
+            page_metadata: PageMetadata::default(),
+            diagnostics: vec![],
             meta: MetaData {
                 html_length: 100,
                 jsonld_count: 0,
                 wasm_status: "rust".to_string(),
             },
-            ai_readiness: AiReadinessData {
+            ai_readiness: Some(AiReadinessData {
+                score: 100,
+                breakdown: ScoreBreakdown::default(),
+                strengths: vec![],
+                issues: vec![],
+                recommendations: vec![],
                 well_known: WellKnownChecks {
                     ai_plugin_json: FileStatus {
                         found: false,
@@ -1992,8 +1049,10 @@ mod integration_tests {
                 openapi: None,
                 robots_txt: None,
                 sitemap: None,
+                llms_txt: None,
                 semantic_html: None,
-            },
+            }),
+            ai_readiness_error: None,
         };
 
         let json = serde_json::to_string(&response);
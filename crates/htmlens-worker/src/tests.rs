@@ -1,25 +1,26 @@
 #[cfg(test)]
 mod worker_tests {
     use crate::{extract_description, extract_title, format_cli_style_markdown};
+    use htmlens_core::parser::extract_page_metadata;
 
     #[test]
     fn test_extract_title_basic() {
         let html = "<html><head><title>Test Title</title></head></html>";
-        let title = extract_title(html);
+        let title = extract_title(&extract_page_metadata(html, ""));
         assert_eq!(title, "Test Title");
     }
 
     #[test]
     fn test_extract_title_no_title() {
         let html = "<html><head></head><body>Content</body></html>";
-        let title = extract_title(html);
+        let title = extract_title(&extract_page_metadata(html, ""));
         assert_eq!(title, "Unknown Title");
     }
 
     #[test]
     fn test_extract_title_empty_title() {
         let html = "<html><head><title></title></head><body>Content</body></html>";
-        let title = extract_title(html);
+        let title = extract_title(&extract_page_metadata(html, ""));
         assert_eq!(title, "");
     }
 
@@ -27,14 +28,14 @@ mod worker_tests {
     fn test_extract_description_meta() {
         let html =
             r#"<html><head><meta name="description" content="Test description"></head></html>"#;
-        let description = extract_description(html);
+        let description = extract_description(&extract_page_metadata(html, ""));
         assert_eq!(description, "Test description");
     }
 
     #[test]
     fn test_extract_description_no_meta() {
         let html = "<html><head><title>Test</title></head><body>Content</body></html>";
-        let description = extract_description(html);
+        let description = extract_description(&extract_page_metadata(html, ""));
         assert_eq!(description, "");
     }
 
@@ -202,7 +203,7 @@ mod worker_tests {
         let markdown = format_cli_style_markdown(url, title, description, &jsonld_blocks);
 
         // Should parse @graph and extract all entity types
-        assert!(markdown.contains("🌐 WebSite"));
+        assert!(markdown.contains("📋 WebSite"));
         assert!(markdown.contains("Example Site"));
         assert!(markdown.contains("🏢 Organization"));
         assert!(markdown.contains("Example Corp"));
@@ -210,12 +211,12 @@ mod worker_tests {
         assert!(markdown.contains("Home"));
     }
 
-    /// Test high/medium priority schema types (Article, Review, LocalBusiness, Event, NewsArticle, VideoObject)
+    /// Test high/medium priority schema types (Article, LocalBusiness, Event, NewsArticle, VideoObject)
     #[test]
     fn test_format_cli_style_markdown_with_article_types() {
         let url = "https://example.com";
         let title = "Schema Types Test";
-        let description = "Testing article, review, business, event, news, and video types";
+        let description = "Testing article, business, event, news, and video types";
         let jsonld_blocks = vec![
             serde_json::json!({
                 "@context": "https://schema.org",
@@ -243,14 +244,6 @@ mod worker_tests {
                     "url": "https://example.com/news-image.jpg"
                 }
             }),
-            serde_json::json!({
-                "@context": "https://schema.org",
-                "@type": "AggregateRating",
-                "ratingValue": 4.5,
-                "bestRating": 5.0,
-                "reviewCount": 128,
-                "name": "Product Rating"
-            }),
             serde_json::json!({
                 "@context": "https://schema.org",
                 "@type": "LocalBusiness",
@@ -288,37 +281,80 @@ mod worker_tests {
         let markdown = format_cli_style_markdown(url, title, description, &jsonld_blocks);
 
         // Verify all article types are rendered
-        assert!(markdown.contains("📝 Article / BlogPost"));
+        assert!(markdown.contains("📋 BlogPosting"));
         assert!(markdown.contains("How to Build Great Products"));
         assert!(markdown.contains("John Doe"));
-        
+
         // Verify news articles are rendered
-        assert!(markdown.contains("📰 News Article"));
+        assert!(markdown.contains("📋 NewsArticle"));
         assert!(markdown.contains("Tech Industry News"));
         assert!(markdown.contains("Jane Smith"));
-        
-        // Verify reviews/ratings are rendered with star ratings
-        assert!(markdown.contains("⭐ Reviews & Ratings"));
-        assert!(markdown.contains("4.5"));
-        assert!(markdown.contains("128"));
-        
+
         // Verify local business is rendered
-        assert!(markdown.contains("🏪 Local Business"));
+        assert!(markdown.contains("📋 LocalBusiness"));
         assert!(markdown.contains("Tech Café"));
-        assert!(markdown.contains("123 Tech Street"));
-        assert!(markdown.contains("San Francisco"));
-        
+        assert!(markdown.contains("+1-555-0123"));
+
         // Verify events are rendered
-        assert!(markdown.contains("🎯 Events"));
+        assert!(markdown.contains("📋 Event"));
         assert!(markdown.contains("Tech Conference 2025"));
         assert!(markdown.contains("SCHEDULED"));
-        
+
         // Verify videos are rendered
-        assert!(markdown.contains("🎬 Videos"));
+        assert!(markdown.contains("📋 VideoObject"));
         assert!(markdown.contains("Product Demo Video"));
         assert!(markdown.contains("PT5M30S"));
     }
 
+    /// Test ProductGroup rendering from the shared core insights (prices and
+    /// availability come from each variant's offer)
+    #[test]
+    fn test_format_cli_style_markdown_with_product_group() {
+        let jsonld_blocks = vec![serde_json::json!({
+            "@context": "https://schema.org",
+            "@type": "ProductGroup",
+            "name": "Trail Shoe",
+            "productGroupID": "TS",
+            "variesBy": ["size"],
+            "brand": {"@type": "Brand", "name": "Acme"},
+            "hasVariant": [
+                {
+                    "@type": "Product",
+                    "sku": "TS-42",
+                    "size": "42",
+                    "offers": {
+                        "@type": "Offer",
+                        "price": 120,
+                        "priceCurrency": "EUR",
+                        "availability": "https://schema.org/InStock"
+                    }
+                },
+                {
+                    "@type": "Product",
+                    "sku": "TS-43",
+                    "size": "43",
+                    "offers": {
+                        "@type": "Offer",
+                        "price": 130,
+                        "priceCurrency": "EUR",
+                        "availability": "https://schema.org/OutOfStock"
+                    }
+                }
+            ]
+        })];
+
+        let markdown =
+            format_cli_style_markdown("https://example.com", "Shoes", "", &jsonld_blocks);
+
+        assert!(markdown.contains("📦 ProductGroup: Trail Shoe"));
+        assert!(markdown.contains("• Brand            : Acme"));
+        assert!(markdown.contains("• Total Variants   : 2"));
+        assert!(markdown.contains("• Price Range      : €120.00 – €130.00"));
+        assert!(markdown.contains("• Availability     : 1 InStock / 1 OutOfStock"));
+        assert!(markdown.contains("| TS-42 | 42   | €120   | ✅ InStock      |"));
+        assert!(markdown.contains("| TS-43 | 43   | €130   | ❌ OutOfStock   |"));
+    }
+
     /// Test defense against common malicious URL patterns that could be used for attacks
     #[test]
     fn test_malicious_url_defense() {